@@ -8,7 +8,7 @@ import {
 	type AccountMeta,
 } from '@solana/web3.js';
 import {
-	getMarketplaceProgram, getMarketPda, getOrderAccount, getVerificationPda, getEventAuthority, marketplaceProgramId, fetchOrderByAddress, getTokenProgramFromMint, getNftProgramFromMint, getAtaAddress,
+	getMarketplaceProgram, getMarketPda, getOrderAccount, getOrderEpochPda, getWalletPda, getVerificationPda, getEventAuthority, marketplaceProgramId, fetchOrderByAddress, getTokenProgramFromMint, getNftProgramFromMint, getAtaAddress,
 	getRemainingAccountsForMint,
 	type WnsAccountParams,
 	fetchMarketByAddress,
@@ -20,6 +20,7 @@ export type CreateOrderArgs = {
 	paymentMint: string;
 	size: number;
 	price: number;
+	expiry?: number; // Unix timestamp, unset for orders that never expire
 	extraAccountParams: WnsAccountParams | undefined; // Add metaplex
 };
 // List NFT
@@ -45,6 +46,7 @@ export const getListNft = async (provider: Provider, listingArgs: CreateOrderArg
 	const nftProgram = await getNftProgramFromMint(provider, nftMint);
 
 	const order = getOrderAccount(nonce.toString(), market.toString(), initializer);
+	const orderEpoch = getOrderEpochPda(initializer);
 	const initializerNftTa = getAtaAddress(nftMint, initializer, nftTokenProgram.toString());
 
 	// const verification = getVerificationPda(market.toString(), nftMint);
@@ -58,14 +60,22 @@ export const getListNft = async (provider: Provider, listingArgs: CreateOrderArg
 			nonce,
 			paymentMint: new PublicKey(listingArgs.paymentMint),
 			price: new BN(listingArgs.price),
-			size: new BN(listingArgs.size)
+			size: new BN(listingArgs.size),
+			expiry: listingArgs.expiry === undefined ? null : new BN(listingArgs.expiry),
+			endPrice: null,
+			endTime: null,
+			auction: null,
+			taker: null,
+			escrow: null,
 		})
 		.accountsStrict({
 			initializer: provider.publicKey,
 			market,
 			nftMint,
 			order,
+			orderEpoch,
 			initializerNftTa,
+			orderNftTa: null,
 			nftProgram: nftProgram ?? PublicKey.default,
 			nftTokenProgram,
 			sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
@@ -97,6 +107,7 @@ export const getBid = async (provider: Provider, biddingArgs: CreateOrderArgs) =
 	const nonce = nonceKp.publicKey;
 
 	const order = getOrderAccount(nonce.toString(), market.toString(), initializer);
+	const orderEpoch = getOrderEpochPda(initializer);
 	const initializerPaymentTa = getAtaAddress(biddingArgs.paymentMint, initializer, paymentTokenProgram.toString());
 	const orderPaymentTa = getAtaAddress(biddingArgs.paymentMint, order.toString(), paymentTokenProgram.toString());
 
@@ -107,12 +118,16 @@ export const getBid = async (provider: Provider, biddingArgs: CreateOrderArgs) =
 			nonce,
 			price: new BN(biddingArgs.price),
 			size: new BN(biddingArgs.size),
+			expiry: biddingArgs.expiry === undefined ? null : new BN(biddingArgs.expiry),
+			merkleRoot: null,
+			walletBacked: null,
 		})
 		.accountsStrict({
 			initializer: provider.publicKey,
 			market,
-			nftMint: biddingArgs.nftMint ?? PublicKey.default,
+			nftMint: biddingArgs.nftMint ?? null,
 			order,
+			orderEpoch,
 			initializerPaymentTa,
 			orderPaymentTa,
 			paymentTokenProgram,
@@ -127,7 +142,7 @@ export const getBid = async (provider: Provider, biddingArgs: CreateOrderArgs) =
 	return ix;
 };
 
-export const fillOrder = async (provider: Provider, orderAddress: string, amountToFill: number, nftMint: string, extraAccountParams: WnsAccountParams | undefined) => {
+export const fillOrder = async (provider: Provider, orderAddress: string, amountToFill: number, nftMint: string, extraAccountParams: WnsAccountParams | undefined, merkleProof?: number[][]) => {
 	const marketProgram = getMarketplaceProgram(provider);
 	console.log(orderAddress);
 	const initializer = provider.publicKey?.toString();
@@ -155,13 +170,19 @@ export const fillOrder = async (provider: Provider, orderAddress: string, amount
 
 	const isBuy = order.side === 0;
 
+	const orderEpoch = getOrderEpochPda(order.owner.toString());
+	// Wallet backed bids pay out of the wallet account of the bidder
+	const wallet = order.walletBacked ? getWalletPda(order.owner.toString()) : null;
+	const bidPayer = wallet?.toString() ?? orderAddress;
+
 	const nftRecipient = isBuy ? order.owner.toString() : initializer;
-	const nftFunder = isBuy ? initializer : order.owner.toString();
-	const paymentFunder = isBuy ? orderAddress : initializer.toString();
+	const nftFunder = isBuy ? initializer : (order.escrowed ? orderAddress : order.owner.toString());
+	const paymentFunder = isBuy ? bidPayer : initializer.toString();
 	const paymentRecipient = isBuy ? initializer : order.owner.toString();
 
 	const buyerPaymentTa = getAtaAddress(order.paymentMint.toString(), paymentFunder, paymentTokenProgram.toString());
 	const sellerPaymentTa = getAtaAddress(order.paymentMint.toString(), paymentRecipient, paymentTokenProgram.toString());
+	const makerPaymentTa = getAtaAddress(order.paymentMint.toString(), order.owner.toString(), paymentTokenProgram.toString());
 	const buyerNftTa = getAtaAddress(nftMint, nftRecipient, nftTokenProgram.toString());
 	const sellerNftTa = getAtaAddress(nftMint, nftFunder, nftTokenProgram.toString());
 
@@ -173,16 +194,19 @@ export const fillOrder = async (provider: Provider, orderAddress: string, amount
 	const remainingAccounts: AccountMeta[] = await getRemainingAccountsForMint(provider, nftMint, extraAccountParams);
 
 	const ix = await marketProgram.methods
-		.fillOrder(new BN(amountToFill))
+		.fillOrder(new BN(amountToFill), merkleProof ?? null)
 		.accountsStrict({
 			taker: provider.publicKey,
 			maker: order.owner,
 			market: order.market,
 			order: orderAddress,
+			orderEpoch,
+			wallet,
 			buyerNftTa,
 			buyerPaymentTa,
 			sellerNftTa,
 			sellerPaymentTa,
+			makerPaymentTa,
 			nftTokenProgram,
 			paymentTokenProgram,
 			nftProgram: nftProgram ?? PublicKey.default,
@@ -223,6 +247,7 @@ export const getCancelListing = async (provider: Provider, orderAddress: PublicK
 	}
 
 	const initializerNftTa = getAtaAddress(nftMint.toString(), initializer, nftTokenProgram.toString());
+	const orderNftTa = order.escrowed ? getAtaAddress(nftMint.toString(), orderAddress.toString(), nftTokenProgram.toString()) : null;
 
 	const nftProgram = await getNftProgramFromMint(provider, nftMint.toString());
 
@@ -237,6 +262,7 @@ export const getCancelListing = async (provider: Provider, orderAddress: PublicK
 			nftMint,
 			order: orderAddress,
 			initializerNftTa,
+			orderNftTa,
 			nftProgram: nftProgram ?? PublicKey.default,
 			nftTokenProgram,
 			sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
@@ -36,6 +36,7 @@ export type Marketplace = {
         },
         {
           "name": "market",
+          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -87,9 +88,34 @@ export type Marketplace = {
             ]
           }
         },
+        {
+          "name": "orderEpoch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  112,
+                  111,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "initializer"
+              }
+            ]
+          }
+        },
         {
           "name": "initializerPaymentTa",
+          "docs": [
+            "payment accounts are left out for bids in native SOL"
+          ],
           "writable": true,
+          "optional": true,
           "pda": {
             "seeds": [
               {
@@ -147,6 +173,7 @@ export type Marketplace = {
         {
           "name": "orderPaymentTa",
           "writable": true,
+          "optional": true,
           "pda": {
             "seeds": [
               {
@@ -203,13 +230,16 @@ export type Marketplace = {
         },
         {
           "name": "paymentMint",
-          "writable": true
+          "writable": true,
+          "optional": true
         },
         {
-          "name": "paymentTokenProgram"
+          "name": "paymentTokenProgram",
+          "optional": true
         },
         {
-          "name": "nftMint"
+          "name": "nftMint",
+          "optional": true
         },
         {
           "name": "systemProgram",
@@ -264,26 +294,49 @@ export type Marketplace = {
       ]
     },
     {
-      "name": "cancelBid",
+      "name": "bidAuction",
       "docs": [
-        "cancel a buy order"
+        "outbid the highest bidder of an english auction"
       ],
       "discriminator": [
-        40,
-        243,
-        190,
+        174,
+        254,
+        177,
+        29,
+        20,
+        29,
         217,
-        208,
-        253,
-        86,
-        206
+        156
       ],
       "accounts": [
         {
-          "name": "initializer",
+          "name": "bidder",
           "writable": true,
           "signer": true
         },
+        {
+          "name": "market",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
         {
           "name": "order",
           "writable": true,
@@ -311,42 +364,54 @@ export type Marketplace = {
               },
               {
                 "kind": "account",
-                "path": "initializer"
+                "path": "order.owner",
+                "account": "order"
               }
             ]
           }
         },
         {
-          "name": "market",
+          "name": "orderEpoch",
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  109,
-                  97,
-                  114,
-                  107,
                   101,
-                  116
+                  112,
+                  111,
+                  99,
+                  104
                 ]
               },
               {
                 "kind": "account",
-                "path": "market.market_identifier",
-                "account": "market"
+                "path": "order.owner",
+                "account": "order"
               }
             ]
           }
         },
         {
-          "name": "initializerPaymentTa",
+          "name": "previousBidder",
+          "docs": [
+            "left out for the first bid of the auction"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "bidderPaymentTa",
+          "docs": [
+            "payment accounts are left out for auctions in native SOL"
+          ],
           "writable": true,
+          "optional": true,
           "pda": {
             "seeds": [
               {
                 "kind": "account",
-                "path": "initializer"
+                "path": "bidder"
               },
               {
                 "kind": "account",
@@ -396,9 +461,15 @@ export type Marketplace = {
             }
           }
         },
+        {
+          "name": "previousBidderPaymentTa",
+          "writable": true,
+          "optional": true
+        },
         {
           "name": "orderPaymentTa",
           "writable": true,
+          "optional": true,
           "pda": {
             "seeds": [
               {
@@ -455,10 +526,11 @@ export type Marketplace = {
         },
         {
           "name": "paymentMint",
-          "writable": true
+          "optional": true
         },
         {
-          "name": "paymentTokenProgram"
+          "name": "paymentTokenProgram",
+          "optional": true
         },
         {
           "name": "systemProgram",
@@ -501,110 +573,60 @@ export type Marketplace = {
           "name": "program"
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "name": "cancelListing",
+      "name": "cancelAllOrders",
       "docs": [
-        "cancel a sell order"
+        "cancel every order of the signer by moving to a new order epoch"
       ],
       "discriminator": [
-        41,
-        183,
-        50,
-        232,
-        230,
-        233,
-        157,
-        70
+        196,
+        83,
+        243,
+        171,
+        17,
+        100,
+        160,
+        143
       ],
       "accounts": [
         {
-          "name": "initializer",
+          "name": "owner",
           "writable": true,
           "signer": true
         },
         {
-          "name": "order",
+          "name": "orderEpoch",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  111,
-                  114,
-                  100,
-                  101,
-                  114
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "order.nonce",
-                "account": "order"
-              },
-              {
-                "kind": "account",
-                "path": "order.market",
-                "account": "order"
-              },
-              {
-                "kind": "account",
-                "path": "initializer"
-              }
-            ]
-          }
-        },
-        {
-          "name": "market",
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  109,
-                  97,
-                  114,
-                  107,
                   101,
-                  116
+                  112,
+                  111,
+                  99,
+                  104
                 ]
               },
               {
                 "kind": "account",
-                "path": "market.market_identifier",
-                "account": "market"
+                "path": "owner"
               }
             ]
           }
         },
-        {
-          "name": "nftMint",
-          "writable": true
-        },
-        {
-          "name": "initializerNftTa",
-          "writable": true
-        },
         {
           "name": "systemProgram",
           "address": "11111111111111111111111111111111"
         },
-        {
-          "name": "sysvarInstructions",
-          "address": "Sysvar1nstructions1111111111111111111111111"
-        },
-        {
-          "name": "associatedTokenProgram",
-          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
-        },
-        {
-          "name": "nftTokenProgram"
-        },
-        {
-          "name": "nftProgram"
-        },
         {
           "name": "eventAuthority",
           "pda": {
@@ -641,32 +663,61 @@ export type Marketplace = {
       "args": []
     },
     {
-      "name": "fillOrder",
+      "name": "cancelBid",
       "docs": [
-        "fill a listing"
+        "cancel a buy order"
       ],
       "discriminator": [
-        232,
-        122,
-        115,
-        25,
-        199,
-        143,
-        136,
-        162
+        40,
+        243,
+        190,
+        217,
+        208,
+        253,
+        86,
+        206
       ],
       "accounts": [
         {
-          "name": "taker",
+          "name": "initializer",
           "writable": true,
           "signer": true
         },
         {
-          "name": "maker",
-          "writable": true
+          "name": "order",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.nonce",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.market",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "initializer"
+              }
+            ]
+          }
         },
         {
           "name": "market",
+          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -689,86 +740,136 @@ export type Marketplace = {
           }
         },
         {
-          "name": "order",
+          "name": "initializerPaymentTa",
+          "docs": [
+            "payment accounts are left out for bids in native SOL"
+          ],
           "writable": true,
+          "optional": true,
           "pda": {
             "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  111,
-                  114,
-                  100,
-                  101,
-                  114
-                ]
-              },
               {
                 "kind": "account",
-                "path": "order.nonce",
-                "account": "order"
+                "path": "initializer"
               },
               {
                 "kind": "account",
-                "path": "order.market",
-                "account": "order"
+                "path": "paymentTokenProgram"
               },
               {
                 "kind": "account",
-                "path": "order.owner",
-                "account": "order"
+                "path": "paymentMint"
               }
-            ]
-          }
-        },
-        {
-          "name": "nftMint",
-          "writable": true
-        },
-        {
-          "name": "sellerNftTa",
-          "writable": true
-        },
-        {
-          "name": "buyerNftTa",
-          "writable": true
-        },
-        {
-          "name": "feeRecipient",
-          "writable": true
-        },
-        {
-          "name": "feeRecipientTa",
-          "writable": true
-        },
-        {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
-        },
-        {
-          "name": "nftTokenProgram"
-        },
-        {
-          "name": "nftProgram"
-        },
-        {
-          "name": "sellerPaymentTa",
-          "writable": true
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
         },
         {
-          "name": "buyerPaymentTa",
-          "writable": true
+          "name": "orderPaymentTa",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "order"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
         },
         {
           "name": "paymentMint",
-          "writable": true
+          "writable": true,
+          "optional": true
         },
         {
-          "name": "paymentTokenProgram"
+          "name": "paymentTokenProgram",
+          "optional": true
         },
         {
-          "name": "sysvarInstructions",
-          "address": "Sysvar1nstructions1111111111111111111111111"
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
         },
         {
           "name": "associatedTokenProgram",
@@ -807,27 +908,22 @@ export type Marketplace = {
           "name": "program"
         }
       ],
-      "args": [
-        {
-          "name": "amount",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "initMarket",
+      "name": "cancelBundle",
       "docs": [
-        "initializer a new market"
+        "cancel a bundle listing"
       ],
       "discriminator": [
-        33,
-        253,
-        15,
-        116,
-        89,
-        25,
-        127,
-        236
+        166,
+        121,
+        4,
+        129,
+        237,
+        255,
+        233,
+        127
       ],
       "accounts": [
         {
@@ -836,7 +932,59 @@ export type Marketplace = {
           "signer": true
         },
         {
-          "name": "marketIdentifier"
+          "name": "order",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.nonce",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.market",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "initializer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bundle",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  110,
+                  100,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order"
+              }
+            ]
+          }
         },
         {
           "name": "market",
@@ -856,14 +1004,14 @@ export type Marketplace = {
               },
               {
                 "kind": "account",
-                "path": "marketIdentifier"
+                "path": "market.market_identifier",
+                "account": "market"
               }
             ]
           }
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "nftTokenProgram"
         },
         {
           "name": "eventAuthority",
@@ -898,31 +1046,22 @@ export type Marketplace = {
           "name": "program"
         }
       ],
-      "args": [
-        {
-          "name": "params",
-          "type": {
-            "defined": {
-              "name": "initMarketParams"
-            }
-          }
-        }
-      ]
+      "args": []
     },
     {
-      "name": "list",
+      "name": "cancelCompressedListing",
       "docs": [
-        "initializer a new listing"
+        "cancel a compressed nft listing"
       ],
       "discriminator": [
-        54,
-        174,
-        193,
-        67,
-        17,
-        41,
-        132,
-        38
+        12,
+        108,
+        154,
+        147,
+        228,
+        206,
+        118,
+        205
       ],
       "accounts": [
         {
@@ -930,29 +1069,6 @@ export type Marketplace = {
           "writable": true,
           "signer": true
         },
-        {
-          "name": "market",
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  109,
-                  97,
-                  114,
-                  107,
-                  101,
-                  116
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "market.market_identifier",
-                "account": "market"
-              }
-            ]
-          }
-        },
         {
           "name": "order",
           "writable": true,
@@ -969,12 +1085,14 @@ export type Marketplace = {
                 ]
               },
               {
-                "kind": "arg",
-                "path": "data.nonce"
+                "kind": "account",
+                "path": "order.nonce",
+                "account": "order"
               },
               {
                 "kind": "account",
-                "path": "market"
+                "path": "order.market",
+                "account": "order"
               },
               {
                 "kind": "account",
@@ -984,82 +1102,50 @@ export type Marketplace = {
           }
         },
         {
-          "name": "nftMint"
-        },
-        {
-          "name": "initializerNftTa",
+          "name": "market",
           "writable": true,
           "pda": {
             "seeds": [
               {
-                "kind": "account",
-                "path": "initializer"
-              },
-              {
-                "kind": "account",
-                "path": "nftTokenProgram"
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
               },
               {
                 "kind": "account",
-                "path": "nftMint"
+                "path": "market.market_identifier",
+                "account": "market"
               }
-            ],
-            "program": {
-              "kind": "const",
-              "value": [
-                140,
-                151,
-                37,
-                143,
-                78,
-                36,
-                137,
-                241,
-                187,
-                61,
-                16,
-                41,
-                20,
-                142,
-                13,
-                131,
-                11,
-                90,
-                19,
-                153,
-                218,
-                255,
-                16,
-                132,
-                4,
-                142,
-                123,
-                216,
-                219,
-                233,
-                248,
-                89
-              ]
-            }
+            ]
           }
         },
         {
-          "name": "sysvarInstructions",
-          "address": "Sysvar1nstructions1111111111111111111111111"
+          "name": "treeConfig",
+          "writable": true
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "merkleTree",
+          "writable": true
         },
         {
-          "name": "nftTokenProgram"
+          "name": "logWrapper"
         },
         {
-          "name": "associatedTokenProgram",
-          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+          "name": "compressionProgram"
         },
         {
-          "name": "nftProgram"
+          "name": "mplBubblegum",
+          "address": "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
         },
         {
           "name": "eventAuthority",
@@ -1099,26 +1185,26 @@ export type Marketplace = {
           "name": "data",
           "type": {
             "defined": {
-              "name": "listData"
+              "name": "compressedFillOrderData"
             }
           }
         }
       ]
     },
     {
-      "name": "verifyMint",
+      "name": "cancelCoreListing",
       "docs": [
-        "initializer a new market"
+        "cancel a core asset listing"
       ],
       "discriminator": [
-        57,
-        93,
-        52,
-        66,
+        58,
+        30,
+        81,
+        251,
         75,
-        249,
-        244,
-        143
+        37,
+        173,
+        211
       ],
       "accounts": [
         {
@@ -1127,258 +1213,6591 @@ export type Marketplace = {
           "signer": true
         },
         {
-          "name": "market"
-        },
-        {
-          "name": "nftMint"
-        },
-        {
-          "name": "verification",
+          "name": "order",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  118,
-                  101,
-                  114,
-                  105,
-                  102,
-                  105,
-                  99,
-                  97,
-                  116,
-                  105,
                   111,
-                  110
+                  114,
+                  100,
+                  101,
+                  114
                 ]
               },
               {
                 "kind": "account",
-                "path": "nftMint"
+                "path": "order.nonce",
+                "account": "order"
               },
               {
                 "kind": "account",
-                "path": "market"
+                "path": "order.market",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "initializer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
               }
             ]
           }
         },
+        {
+          "name": "asset",
+          "writable": true
+        },
+        {
+          "name": "collection",
+          "writable": true
+        },
+        {
+          "name": "coreProgram",
+          "address": "CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d"
+        },
         {
           "name": "systemProgram",
           "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
         }
       ],
       "args": []
-    }
-  ],
-  "accounts": [
-    {
-      "name": "market",
-      "discriminator": [
-        219,
-        190,
-        213,
-        55,
-        0,
-        227,
-        198,
-        154
-      ]
     },
     {
-      "name": "mintVerification",
+      "name": "cancelListing",
+      "docs": [
+        "cancel a sell order"
+      ],
       "discriminator": [
-        152,
+        41,
         183,
-        224,
-        35,
-        143,
-        133,
-        78,
-        176
-      ]
-    },
-    {
-      "name": "order",
-      "discriminator": [
-        134,
-        173,
-        223,
-        185,
-        77,
-        86,
-        28,
-        51
-      ]
-    }
-  ],
-  "events": [
-    {
-      "name": "marketEditEvent",
-      "discriminator": [
-        160,
+        50,
+        232,
         230,
-        206,
-        202,
-        154,
-        28,
-        249,
-        246
-      ]
-    },
-    {
-      "name": "orderEditEvent",
-      "discriminator": [
-        155,
-        223,
-        223,
-        45,
-        200,
-        107,
-        177,
-        149
-      ]
+        233,
+        157,
+        70
+      ],
+      "accounts": [
+        {
+          "name": "initializer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "order",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.nonce",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.market",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "initializer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "nftMint",
+          "writable": true
+        },
+        {
+          "name": "initializerNftTa",
+          "writable": true
+        },
+        {
+          "name": "orderNftTa",
+          "docs": [
+            "only needed for escrow listings"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "sysvarInstructions",
+          "address": "Sysvar1nstructions1111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "nftTokenProgram"
+        },
+        {
+          "name": "nftProgram"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "cancelSignedListings",
+      "docs": [
+        "cancel listings signed off-chain"
+      ],
+      "discriminator": [
+        17,
+        23,
+        3,
+        211,
+        23,
+        52,
+        236,
+        99
+      ],
+      "accounts": [
+        {
+          "name": "owner",
+          "signer": true
+        },
+        {
+          "name": "nonceBitmap",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  110,
+                  111,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "nonce_bitmap.market",
+                "account": "nonceBitmap"
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "nonces",
+          "type": {
+            "vec": "u64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "cancelSwap",
+      "docs": [
+        "cancel a swap order"
+      ],
+      "discriminator": [
+        88,
+        174,
+        98,
+        148,
+        24,
+        252,
+        93,
+        89
+      ],
+      "accounts": [
+        {
+          "name": "initializer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "order",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.nonce",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.market",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "initializer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bundle",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  110,
+                  100,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "initializerPaymentTa",
+          "docs": [
+            "payment accounts are only needed to refund a sweetener escrowed in a token"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "orderPaymentTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "paymentTokenProgram",
+          "optional": true
+        },
+        {
+          "name": "nftTokenProgram"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeExpiredOrder",
+      "docs": [
+        "close an expired order, callable by anyone"
+      ],
+      "discriminator": [
+        205,
+        27,
+        199,
+        110,
+        149,
+        40,
+        220,
+        44
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "docs": [
+            "anyone can close an expired order"
+          ],
+          "signer": true
+        },
+        {
+          "name": "owner",
+          "writable": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "order",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.nonce",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.market",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.owner",
+                "account": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerNftTa",
+          "docs": [
+            "nft accounts are only needed when closing a sell order"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "account",
+                "path": "nftTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "order.nft_mint",
+                "account": "order"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "orderNftTa",
+          "docs": [
+            "escrow accounts are only needed when closing an escrow listing"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "nftMint",
+          "optional": true
+        },
+        {
+          "name": "nftTokenProgram",
+          "optional": true
+        },
+        {
+          "name": "ownerPaymentTa",
+          "docs": [
+            "payment accounts are only needed when closing a buy order"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "orderPaymentTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "paymentTokenProgram",
+          "optional": true
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeMarket",
+      "docs": [
+        "close a market account"
+      ],
+      "discriminator": [
+        88,
+        154,
+        248,
+        186,
+        48,
+        14,
+        123,
+        244
+      ],
+      "accounts": [
+        {
+          "name": "initializer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "closeStaleOrders",
+      "docs": [
+        "close orders cancelled by an order epoch increase, callable by anyone"
+      ],
+      "discriminator": [
+        217,
+        168,
+        1,
+        174,
+        1,
+        72,
+        231,
+        99
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "docs": [
+            "anyone can close the stale orders of an owner"
+          ],
+          "signer": true
+        },
+        {
+          "name": "owner",
+          "writable": true
+        },
+        {
+          "name": "orderEpoch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  112,
+                  111,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              }
+            ]
+          }
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram",
+          "docs": [
+            "token programs are only needed by the orders holding accounts of that program"
+          ],
+          "optional": true,
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "token2022Program",
+          "optional": true,
+          "address": "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "createSwap",
+      "docs": [
+        "offer nfts for a wanted nft, with an optional sweetener"
+      ],
+      "discriminator": [
+        176,
+        207,
+        238,
+        60,
+        195,
+        2,
+        203,
+        91
+      ],
+      "accounts": [
+        {
+          "name": "initializer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "order",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "data.nonce"
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "initializer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "orderEpoch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  112,
+                  111,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "initializer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bundle",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  110,
+                  100,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "initializerPaymentTa",
+          "docs": [
+            "payment accounts are only needed when the maker pays a sweetener in a token"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "initializer"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "orderPaymentTa",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "order"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "paymentTokenProgram",
+          "optional": true
+        },
+        {
+          "name": "nftTokenProgram"
+        },
+        {
+          "name": "nftProgram"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "data",
+          "type": {
+            "defined": {
+              "name": "swapData"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "depositWallet",
+      "docs": [
+        "add to the bidding balance backing wallet backed bids"
+      ],
+      "discriminator": [
+        232,
+        10,
+        183,
+        22,
+        119,
+        51,
+        79,
+        25
+      ],
+      "accounts": [
+        {
+          "name": "owner",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "wallet",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  119,
+                  97,
+                  108,
+                  108,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerPaymentTa",
+          "docs": [
+            "payment accounts are left out for deposits in native SOL"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "walletPaymentTa",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "wallet"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "paymentTokenProgram",
+          "optional": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "editOrder",
+      "docs": [
+        "edit the price or size of an order"
+      ],
+      "discriminator": [
+        254,
+        208,
+        118,
+        29,
+        173,
+        248,
+        200,
+        70
+      ],
+      "accounts": [
+        {
+          "name": "initializer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "market",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "order",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.nonce",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.market",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "initializer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "initializerPaymentTa",
+          "docs": [
+            "payment accounts are only needed when editing a buy order priced in a token"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "orderPaymentTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "paymentTokenProgram",
+          "optional": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "data",
+          "type": {
+            "defined": {
+              "name": "editOrderData"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "fillBundle",
+      "docs": [
+        "buy every nft of a bundle listing"
+      ],
+      "discriminator": [
+        139,
+        9,
+        244,
+        195,
+        228,
+        35,
+        236,
+        160
+      ],
+      "accounts": [
+        {
+          "name": "taker",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "maker",
+          "writable": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "order",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.nonce",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.market",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.owner",
+                "account": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "orderEpoch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  112,
+                  111,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.owner",
+                "account": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bundle",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  110,
+                  100,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeRecipient",
+          "writable": true
+        },
+        {
+          "name": "feeRecipientTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "sellerPaymentTa",
+          "docs": [
+            "payment token accounts, mint and program are left out for bundles in native SOL"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "buyerPaymentTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "paymentMint",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "paymentTokenProgram",
+          "optional": true
+        },
+        {
+          "name": "nftTokenProgram"
+        },
+        {
+          "name": "nftProgram"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "data",
+          "type": {
+            "defined": {
+              "name": "bundleFillData"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "fillCompressedBid",
+      "docs": [
+        "fill a bid with a compressed nft"
+      ],
+      "discriminator": [
+        34,
+        228,
+        194,
+        146,
+        102,
+        20,
+        193,
+        206
+      ],
+      "accounts": [
+        {
+          "name": "taker",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "maker",
+          "writable": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "order",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.nonce",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.market",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.owner",
+                "account": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "orderEpoch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  112,
+                  111,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.owner",
+                "account": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "leafDelegate"
+        },
+        {
+          "name": "treeConfig",
+          "writable": true
+        },
+        {
+          "name": "merkleTree",
+          "writable": true
+        },
+        {
+          "name": "logWrapper"
+        },
+        {
+          "name": "compressionProgram"
+        },
+        {
+          "name": "mplBubblegum",
+          "address": "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY"
+        },
+        {
+          "name": "orderPaymentTa",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "order"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "sellerPaymentTa",
+          "writable": true
+        },
+        {
+          "name": "makerPaymentTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "feeRecipient",
+          "writable": true
+        },
+        {
+          "name": "feeRecipientTa",
+          "writable": true
+        },
+        {
+          "name": "paymentMint"
+        },
+        {
+          "name": "paymentTokenProgram"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "data",
+          "type": {
+            "defined": {
+              "name": "compressedFillOrderData"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "fillCompressedListing",
+      "docs": [
+        "fill a compressed nft listing"
+      ],
+      "discriminator": [
+        107,
+        2,
+        210,
+        32,
+        120,
+        191,
+        144,
+        194
+      ],
+      "accounts": [
+        {
+          "name": "taker",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "maker",
+          "writable": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "order",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.nonce",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.market",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.owner",
+                "account": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "orderEpoch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  112,
+                  111,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.owner",
+                "account": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "treeConfig",
+          "writable": true
+        },
+        {
+          "name": "merkleTree",
+          "writable": true
+        },
+        {
+          "name": "logWrapper"
+        },
+        {
+          "name": "compressionProgram"
+        },
+        {
+          "name": "mplBubblegum",
+          "address": "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY"
+        },
+        {
+          "name": "buyerPaymentTa",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "taker"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "sellerPaymentTa",
+          "writable": true
+        },
+        {
+          "name": "feeRecipient",
+          "writable": true
+        },
+        {
+          "name": "feeRecipientTa",
+          "writable": true
+        },
+        {
+          "name": "paymentMint"
+        },
+        {
+          "name": "paymentTokenProgram"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "data",
+          "type": {
+            "defined": {
+              "name": "compressedFillOrderData"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "fillCoreOrder",
+      "docs": [
+        "fill a core asset listing or bid"
+      ],
+      "discriminator": [
+        201,
+        33,
+        202,
+        177,
+        129,
+        132,
+        24,
+        201
+      ],
+      "accounts": [
+        {
+          "name": "taker",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "maker",
+          "writable": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "order",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.nonce",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.market",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.owner",
+                "account": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "orderEpoch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  112,
+                  111,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.owner",
+                "account": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "asset",
+          "writable": true
+        },
+        {
+          "name": "collection",
+          "writable": true
+        },
+        {
+          "name": "coreProgram",
+          "address": "CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d"
+        },
+        {
+          "name": "sellerPaymentTa",
+          "docs": [
+            "payment accounts are left out for orders in native SOL"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "buyerPaymentTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "makerPaymentTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "feeRecipient",
+          "writable": true
+        },
+        {
+          "name": "feeRecipientTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "paymentTokenProgram",
+          "optional": true
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "merkleProof",
+          "type": {
+            "option": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "fillOrder",
+      "docs": [
+        "fill a listing"
+      ],
+      "discriminator": [
+        232,
+        122,
+        115,
+        25,
+        199,
+        143,
+        136,
+        162
+      ],
+      "accounts": [
+        {
+          "name": "taker",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "maker",
+          "writable": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "order",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.nonce",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.market",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.owner",
+                "account": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "orderEpoch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  112,
+                  111,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.owner",
+                "account": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "wallet",
+          "docs": [
+            "wallet of the bidder, only needed to fill wallet backed bids"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  119,
+                  97,
+                  108,
+                  108,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.owner",
+                "account": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "nftMint",
+          "writable": true
+        },
+        {
+          "name": "sellerNftTa",
+          "writable": true
+        },
+        {
+          "name": "buyerNftTa",
+          "writable": true
+        },
+        {
+          "name": "feeRecipient",
+          "writable": true
+        },
+        {
+          "name": "feeRecipientTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "nftTokenProgram"
+        },
+        {
+          "name": "nftProgram"
+        },
+        {
+          "name": "sellerPaymentTa",
+          "docs": [
+            "payment token accounts, mint and program are left out for orders in native SOL"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "buyerPaymentTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "makerPaymentTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "paymentMint",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "paymentTokenProgram",
+          "optional": true
+        },
+        {
+          "name": "sysvarInstructions",
+          "address": "Sysvar1nstructions1111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "merkleProof",
+          "type": {
+            "option": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "fillSignedListing",
+      "docs": [
+        "fill a listing signed off-chain"
+      ],
+      "discriminator": [
+        177,
+        55,
+        44,
+        120,
+        75,
+        6,
+        3,
+        60
+      ],
+      "accounts": [
+        {
+          "name": "taker",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "seller",
+          "writable": true
+        },
+        {
+          "name": "market",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "nonceBitmap",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  110,
+                  111,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "seller"
+              }
+            ]
+          }
+        },
+        {
+          "name": "orderEpoch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  112,
+                  111,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "seller"
+              }
+            ]
+          }
+        },
+        {
+          "name": "nftMint"
+        },
+        {
+          "name": "sellerNftTa",
+          "writable": true
+        },
+        {
+          "name": "buyerNftTa",
+          "writable": true
+        },
+        {
+          "name": "feeRecipient",
+          "writable": true
+        },
+        {
+          "name": "feeRecipientTa",
+          "docs": [
+            "payment accounts are left out for listings in native SOL"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "sellerPaymentTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "buyerPaymentTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "paymentTokenProgram",
+          "optional": true
+        },
+        {
+          "name": "nftTokenProgram"
+        },
+        {
+          "name": "nftProgram"
+        },
+        {
+          "name": "sysvarInstructions",
+          "address": "Sysvar1nstructions1111111111111111111111111"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "listing",
+          "type": {
+            "defined": {
+              "name": "signedListing"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "fillSwap",
+      "docs": [
+        "trade the wanted nft for the nfts offered by a swap order"
+      ],
+      "discriminator": [
+        204,
+        165,
+        172,
+        86,
+        47,
+        94,
+        168,
+        3
+      ],
+      "accounts": [
+        {
+          "name": "taker",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "maker",
+          "writable": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "order",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.nonce",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.market",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.owner",
+                "account": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "orderEpoch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  112,
+                  111,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.owner",
+                "account": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bundle",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  110,
+                  100,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeRecipient",
+          "writable": true
+        },
+        {
+          "name": "makerPaymentTa",
+          "docs": [
+            "payment accounts are only needed for a sweetener in a token"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "takerPaymentTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "feeRecipientTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "orderPaymentTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "paymentTokenProgram",
+          "optional": true
+        },
+        {
+          "name": "nftTokenProgram"
+        },
+        {
+          "name": "nftProgram"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "data",
+          "type": {
+            "defined": {
+              "name": "swapFillData"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "initMarket",
+      "docs": [
+        "initializer a new market"
+      ],
+      "discriminator": [
+        33,
+        253,
+        15,
+        116,
+        89,
+        25,
+        127,
+        236
+      ],
+      "accounts": [
+        {
+          "name": "initializer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "marketIdentifier"
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "marketIdentifier"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "params",
+          "type": {
+            "defined": {
+              "name": "initMarketParams"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "initNonceBitmap",
+      "docs": [
+        "create the nonce bitmap used to list by signature"
+      ],
+      "discriminator": [
+        214,
+        13,
+        125,
+        121,
+        72,
+        220,
+        241,
+        42
+      ],
+      "accounts": [
+        {
+          "name": "owner",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "market",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "nonceBitmap",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  110,
+                  111,
+                  110,
+                  99,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "list",
+      "docs": [
+        "initializer a new listing"
+      ],
+      "discriminator": [
+        54,
+        174,
+        193,
+        67,
+        17,
+        41,
+        132,
+        38
+      ],
+      "accounts": [
+        {
+          "name": "initializer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "order",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "data.nonce"
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "initializer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "orderEpoch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  112,
+                  111,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "initializer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "nftMint"
+        },
+        {
+          "name": "initializerNftTa",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "initializer"
+              },
+              {
+                "kind": "account",
+                "path": "nftTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "nftMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "orderNftTa",
+          "docs": [
+            "only needed for escrow listings"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "order"
+              },
+              {
+                "kind": "account",
+                "path": "nftTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "nftMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "sysvarInstructions",
+          "address": "Sysvar1nstructions1111111111111111111111111"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "nftTokenProgram"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "nftProgram"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "data",
+          "type": {
+            "defined": {
+              "name": "listData"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "listBundle",
+      "docs": [
+        "list several nfts for one total price"
+      ],
+      "discriminator": [
+        200,
+        204,
+        246,
+        213,
+        53,
+        71,
+        210,
+        219
+      ],
+      "accounts": [
+        {
+          "name": "initializer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "order",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "data.nonce"
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "initializer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "orderEpoch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  112,
+                  111,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "initializer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "bundle",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  110,
+                  100,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "nftTokenProgram"
+        },
+        {
+          "name": "nftProgram"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "data",
+          "type": {
+            "defined": {
+              "name": "bundleListData"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "listCompressed",
+      "docs": [
+        "list a compressed nft, held by the order until filled or cancelled"
+      ],
+      "discriminator": [
+        65,
+        179,
+        218,
+        25,
+        150,
+        115,
+        111,
+        171
+      ],
+      "accounts": [
+        {
+          "name": "initializer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "order",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "data.nonce"
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "initializer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "orderEpoch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  112,
+                  111,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "initializer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "verification"
+        },
+        {
+          "name": "leafDelegate"
+        },
+        {
+          "name": "treeConfig",
+          "writable": true
+        },
+        {
+          "name": "merkleTree",
+          "writable": true
+        },
+        {
+          "name": "logWrapper"
+        },
+        {
+          "name": "compressionProgram"
+        },
+        {
+          "name": "mplBubblegum",
+          "address": "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "data",
+          "type": {
+            "defined": {
+              "name": "compressedOrderData"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "listCore",
+      "docs": [
+        "list a core asset with the order as its transfer delegate"
+      ],
+      "discriminator": [
+        173,
+        76,
+        167,
+        125,
+        118,
+        71,
+        1,
+        153
+      ],
+      "accounts": [
+        {
+          "name": "initializer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "order",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "data.nonce"
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              },
+              {
+                "kind": "account",
+                "path": "initializer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "orderEpoch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  112,
+                  111,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "initializer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "asset",
+          "writable": true
+        },
+        {
+          "name": "collection",
+          "writable": true
+        },
+        {
+          "name": "coreProgram",
+          "address": "CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "data",
+          "type": {
+            "defined": {
+              "name": "coreListData"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "matchOrders",
+      "docs": [
+        "settle a bid against a listing it crosses"
+      ],
+      "discriminator": [
+        17,
+        1,
+        201,
+        93,
+        7,
+        51,
+        251,
+        134
+      ],
+      "accounts": [
+        {
+          "name": "keeper",
+          "docs": [
+            "anyone can match crossed orders and earn the keeper tip"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "buyOrder",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "buy_order.nonce",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "buy_order.market",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "buy_order.owner",
+                "account": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "sellOrder",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "sell_order.nonce",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "sell_order.market",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "sell_order.owner",
+                "account": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "buyer",
+          "writable": true
+        },
+        {
+          "name": "seller",
+          "writable": true
+        },
+        {
+          "name": "buyerOrderEpoch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  112,
+                  111,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "buyer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "sellerOrderEpoch",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  101,
+                  112,
+                  111,
+                  99,
+                  104
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "seller"
+              }
+            ]
+          }
+        },
+        {
+          "name": "nftMint",
+          "writable": true
+        },
+        {
+          "name": "sellerNftTa",
+          "writable": true
+        },
+        {
+          "name": "buyerNftTa",
+          "writable": true
+        },
+        {
+          "name": "feeRecipient",
+          "writable": true
+        },
+        {
+          "name": "feeRecipientTa",
+          "docs": [
+            "payment accounts are left out for orders in native SOL"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "orderPaymentTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "sellerPaymentTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "buyerPaymentTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "keeperPaymentTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "paymentTokenProgram",
+          "optional": true
+        },
+        {
+          "name": "nftTokenProgram"
+        },
+        {
+          "name": "nftProgram"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "merkleProof",
+          "type": {
+            "option": {
+              "vec": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "settleAuction",
+      "docs": [
+        "settle an ended english auction with the highest bidder"
+      ],
+      "discriminator": [
+        246,
+        196,
+        183,
+        98,
+        222,
+        139,
+        46,
+        133
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "docs": [
+            "anyone can settle an auction once it has ended"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "maker",
+          "writable": true
+        },
+        {
+          "name": "winner",
+          "writable": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "order",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  111,
+                  114,
+                  100,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "order.nonce",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.market",
+                "account": "order"
+              },
+              {
+                "kind": "account",
+                "path": "order.owner",
+                "account": "order"
+              }
+            ]
+          }
+        },
+        {
+          "name": "nftMint",
+          "writable": true
+        },
+        {
+          "name": "sellerNftTa",
+          "writable": true
+        },
+        {
+          "name": "winnerNftTa",
+          "writable": true
+        },
+        {
+          "name": "feeRecipient",
+          "writable": true
+        },
+        {
+          "name": "feeRecipientTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "nftTokenProgram"
+        },
+        {
+          "name": "nftProgram"
+        },
+        {
+          "name": "sellerPaymentTa",
+          "docs": [
+            "payment token accounts, mint and program are left out for auctions in native SOL"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "winnerPaymentTa",
+          "docs": [
+            "only needed to refund the winner when the seller no longer holds the nft"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "orderPaymentTa",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "order"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "paymentMint",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "paymentTokenProgram",
+          "optional": true
+        },
+        {
+          "name": "sysvarInstructions",
+          "address": "Sysvar1nstructions1111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "sweep",
+      "docs": [
+        "fill several listings of a market in one instruction"
+      ],
+      "discriminator": [
+        40,
+        23,
+        234,
+        175,
+        14,
+        61,
+        154,
+        177
+      ],
+      "accounts": [
+        {
+          "name": "taker",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "feeRecipient",
+          "writable": true
+        },
+        {
+          "name": "feeRecipientTa",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "buyerPaymentTa",
+          "docs": [
+            "payment accounts are left out when sweeping listings in native SOL"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "taker"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "paymentTokenProgram",
+          "optional": true
+        },
+        {
+          "name": "nftTokenProgram"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "maxSpend",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "updateMarket",
+      "docs": [
+        "update market fee settings, state and payment mint allowlist"
+      ],
+      "discriminator": [
+        153,
+        39,
+        2,
+        197,
+        179,
+        50,
+        199,
+        217
+      ],
+      "accounts": [
+        {
+          "name": "initializer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "market",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  97,
+                  114,
+                  107,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "market.market_identifier",
+                "account": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "params",
+          "type": {
+            "defined": {
+              "name": "updateMarketParams"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "verifyMint",
+      "docs": [
+        "initializer a new market"
+      ],
+      "discriminator": [
+        57,
+        93,
+        52,
+        66,
+        75,
+        249,
+        244,
+        143
+      ],
+      "accounts": [
+        {
+          "name": "initializer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "market"
+        },
+        {
+          "name": "nftMint"
+        },
+        {
+          "name": "verification",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  118,
+                  101,
+                  114,
+                  105,
+                  102,
+                  105,
+                  99,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "nftMint"
+              },
+              {
+                "kind": "account",
+                "path": "market"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "withdrawWallet",
+      "docs": [
+        "take back part of the bidding balance"
+      ],
+      "discriminator": [
+        213,
+        162,
+        33,
+        176,
+        95,
+        20,
+        37,
+        244
+      ],
+      "accounts": [
+        {
+          "name": "owner",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "wallet",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  119,
+                  97,
+                  108,
+                  108,
+                  101,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "owner"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerPaymentTa",
+          "docs": [
+            "payment accounts are left out for withdrawals in native SOL"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "walletPaymentTa",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "wallet"
+              },
+              {
+                "kind": "account",
+                "path": "paymentTokenProgram"
+              },
+              {
+                "kind": "account",
+                "path": "paymentMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "paymentMint",
+          "optional": true
+        },
+        {
+          "name": "paymentTokenProgram",
+          "optional": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "eventAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  95,
+                  95,
+                  101,
+                  118,
+                  101,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "program"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "bundle",
+      "discriminator": [
+        15,
+        82,
+        167,
+        230,
+        37,
+        214,
+        82,
+        80
+      ]
+    },
+    {
+      "name": "market",
+      "discriminator": [
+        219,
+        190,
+        213,
+        55,
+        0,
+        227,
+        198,
+        154
+      ]
+    },
+    {
+      "name": "mintVerification",
+      "discriminator": [
+        152,
+        183,
+        224,
+        35,
+        143,
+        133,
+        78,
+        176
+      ]
+    },
+    {
+      "name": "nonceBitmap",
+      "discriminator": [
+        154,
+        14,
+        30,
+        131,
+        149,
+        1,
+        169,
+        146
+      ]
+    },
+    {
+      "name": "order",
+      "discriminator": [
+        134,
+        173,
+        223,
+        185,
+        77,
+        86,
+        28,
+        51
+      ]
+    },
+    {
+      "name": "orderEpoch",
+      "discriminator": [
+        175,
+        231,
+        193,
+        140,
+        6,
+        109,
+        36,
+        161
+      ]
+    },
+    {
+      "name": "wallet",
+      "discriminator": [
+        24,
+        89,
+        59,
+        139,
+        81,
+        154,
+        232,
+        95
+      ]
+    }
+  ],
+  "events": [
+    {
+      "name": "marketEditEvent",
+      "discriminator": [
+        160,
+        230,
+        206,
+        202,
+        154,
+        28,
+        249,
+        246
+      ]
+    },
+    {
+      "name": "orderEditEvent",
+      "discriminator": [
+        155,
+        223,
+        223,
+        45,
+        200,
+        107,
+        177,
+        149
+      ]
+    },
+    {
+      "name": "orderEpochEvent",
+      "discriminator": [
+        170,
+        95,
+        167,
+        249,
+        38,
+        132,
+        18,
+        151
+      ]
+    },
+    {
+      "name": "orderFillEvent",
+      "discriminator": [
+        192,
+        9,
+        71,
+        11,
+        130,
+        252,
+        155,
+        178
+      ]
+    },
+    {
+      "name": "walletEditEvent",
+      "discriminator": [
+        161,
+        175,
+        55,
+        103,
+        90,
+        119,
+        137,
+        198
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "wrongAccount",
+      "msg": "Account passed in incorrectly"
+    },
+    {
+      "code": 6001,
+      "name": "insufficientOrderSize",
+      "msg": "Order too small"
+    },
+    {
+      "code": 6002,
+      "name": "amountOverflow",
+      "msg": "Amount overflow"
+    },
+    {
+      "code": 6003,
+      "name": "amountUnderflow",
+      "msg": "Amount underflow"
+    },
+    {
+      "code": 6004,
+      "name": "unsupportedNft",
+      "msg": "Unsupported NFT Type"
+    },
+    {
+      "code": 6005,
+      "name": "invalidNft",
+      "msg": "Invalid NFT for Market"
+    },
+    {
+      "code": 6006,
+      "name": "invalidFeeBps",
+      "msg": "Fee basis points too high"
+    },
+    {
+      "code": 6007,
+      "name": "invalidOrderEdit",
+      "msg": "Order cannot be edited this way"
+    },
+    {
+      "code": 6008,
+      "name": "orderExpired",
+      "msg": "Order has expired"
+    },
+    {
+      "code": 6009,
+      "name": "orderNotExpired",
+      "msg": "Order has not expired"
+    },
+    {
+      "code": 6010,
+      "name": "merkleProofRequired",
+      "msg": "Merkle proof required"
+    },
+    {
+      "code": 6011,
+      "name": "invalidMerkleProof",
+      "msg": "Invalid merkle proof"
+    },
+    {
+      "code": 6012,
+      "name": "invalidAuctionParams",
+      "msg": "Invalid auction parameters"
+    },
+    {
+      "code": 6013,
+      "name": "auctionEnded",
+      "msg": "Auction has ended"
+    },
+    {
+      "code": 6014,
+      "name": "auctionNotEnded",
+      "msg": "Auction has not ended"
+    },
+    {
+      "code": 6015,
+      "name": "bidTooLow",
+      "msg": "Bid too low"
+    },
+    {
+      "code": 6016,
+      "name": "invalidTaker",
+      "msg": "Order is reserved for another taker"
+    },
+    {
+      "code": 6017,
+      "name": "invalidSwapParams",
+      "msg": "Invalid swap parameters"
+    },
+    {
+      "code": 6018,
+      "name": "paymentMintNotAllowed",
+      "msg": "Payment mint is not allowed by the market"
+    },
+    {
+      "code": 6019,
+      "name": "invalidPaymentMints",
+      "msg": "Invalid payment mint allowlist"
+    },
+    {
+      "code": 6020,
+      "name": "ordersNotCrossed",
+      "msg": "Bid does not cover the ask"
+    },
+    {
+      "code": 6021,
+      "name": "invalidSignature",
+      "msg": "Listing signature is invalid"
+    },
+    {
+      "code": 6022,
+      "name": "nonceUsed",
+      "msg": "Nonce has already been used"
+    },
+    {
+      "code": 6023,
+      "name": "staleOrder",
+      "msg": "Order was cancelled by its owner's order epoch"
+    },
+    {
+      "code": 6024,
+      "name": "orderNotStale",
+      "msg": "Order is not stale"
+    },
+    {
+      "code": 6025,
+      "name": "insufficientBalance",
+      "msg": "Wallet balance is too low"
+    },
+    {
+      "code": 6026,
+      "name": "marketHasOpenOrders",
+      "msg": "Market still has open orders"
+    },
+    {
+      "code": 6027,
+      "name": "ownerMustRevoke",
+      "msg": "Listing delegate can only be revoked by its owner"
+    },
+    {
+      "code": 6028,
+      "name": "marketOrdersUncounted",
+      "msg": "Market predates the open order count and cannot be closed"
     }
   ],
-  "errors": [
+  "types": [
+    {
+      "name": "auctionData",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "endTime",
+            "type": "i64"
+          },
+          {
+            "name": "minIncrement",
+            "type": "u64"
+          },
+          {
+            "name": "timeExtension",
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "bidData",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "nonce",
+            "type": "pubkey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "size",
+            "type": "u64"
+          },
+          {
+            "name": "expiry",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "merkleRoot",
+            "docs": [
+              "root of the eligible mints for a trait bid, nft_mint is left out"
+            ],
+            "type": {
+              "option": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
+          },
+          {
+            "name": "walletBacked",
+            "docs": [
+              "wallet backed bids escrow nothing and are paid from the wallet account of the initializer when filled"
+            ],
+            "type": {
+              "option": "bool"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "bundle",
+      "docs": [
+        "bundle account - mints sold together by a bundle order or offered by a swap"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "docs": [
+              "bundle account version"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "order",
+            "docs": [
+              "order selling or swapping the bundle, its nft_mint is the bundle address"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "size",
+            "docs": [
+              "number of mints in the bundle"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "mints",
+            "docs": [
+              "mints in the bundle, default pubkey past size"
+            ],
+            "type": {
+              "array": [
+                "pubkey",
+                5
+              ]
+            }
+          },
+          {
+            "name": "reserve",
+            "docs": [
+              "reserved space for future changes"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                64
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "bundleFillData",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "itemAccounts",
+            "docs": [
+              "number of remaining accounts each item uses, in bundle order"
+            ],
+            "type": "bytes"
+          }
+        ]
+      }
+    },
+    {
+      "name": "bundleListData",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "nonce",
+            "type": "pubkey"
+          },
+          {
+            "name": "paymentMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "price",
+            "docs": [
+              "total price of the bundle"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "compressedFillOrderData",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "root",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "dataHash",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "creatorHash",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "index",
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "compressedOrderData",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "nonce",
+            "type": "pubkey"
+          },
+          {
+            "name": "paymentMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "expiry",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "root",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "dataHash",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "creatorHash",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "index",
+            "type": "u32"
+          }
+        ]
+      }
+    },
     {
-      "code": 6000,
-      "name": "wrongAccount",
-      "msg": "Account passed in incorrectly"
+      "name": "coreListData",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "nonce",
+            "type": "pubkey"
+          },
+          {
+            "name": "paymentMint",
+            "docs": [
+              "default pubkey for a listing in native SOL"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "expiry",
+            "type": {
+              "option": "i64"
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6001,
-      "name": "insufficientOrderSize",
-      "msg": "Order too small"
+      "name": "editOrderData",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "size",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "initMarketParams",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "feeRecipient",
+            "type": "pubkey"
+          },
+          {
+            "name": "feeBps",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "listData",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "nonce",
+            "type": "pubkey"
+          },
+          {
+            "name": "paymentMint",
+            "type": "pubkey"
+          },
+          {
+            "name": "price",
+            "type": "u64"
+          },
+          {
+            "name": "size",
+            "type": "u64"
+          },
+          {
+            "name": "expiry",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "endPrice",
+            "docs": [
+              "dutch auctions decline from price to end_price by end_time"
+            ],
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "endTime",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "auction",
+            "docs": [
+              "english auctions start at price as the reserve and are settled by settle_auction"
+            ],
+            "type": {
+              "option": {
+                "defined": {
+                  "name": "auctionData"
+                }
+              }
+            }
+          },
+          {
+            "name": "taker",
+            "docs": [
+              "private listings can only be filled by this wallet"
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "escrow",
+            "docs": [
+              "escrow listings move the nft into a token account of the order instead of delegating it"
+            ],
+            "type": {
+              "option": "bool"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "market",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "docs": [
+              "market account version, used to conditionally parse accounts if changes are made to the struct"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "marketIdentifier",
+            "docs": [
+              "identifying of the index to which the NFTs belong to (WNS Collection, Metaplex collection, separate hash)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "initializer",
+            "docs": [
+              "initializer of the market - can edit and close the market, admin key"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "state",
+            "docs": [
+              "state representing the market - open/closed"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "feeRecipient",
+            "docs": [
+              "address that should receive market fees"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "feeBps",
+            "docs": [
+              "fee basis points, charged to the seller when maker and taker fees are unset"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "makerFeeBps",
+            "docs": [
+              "maker fee basis points, negative for a maker rebate"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "takerFeeBps",
+            "docs": [
+              "taker fee basis points"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "paymentAllowlistOn",
+            "docs": [
+              "true if orders are limited to the allowlisted payment mints"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "nativeAllowed",
+            "docs": [
+              "true if native SOL is allowlisted"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "paymentMints",
+            "docs": [
+              "allowlisted payment mints, default pubkey for empty slots"
+            ],
+            "type": {
+              "array": [
+                "pubkey",
+                8
+              ]
+            }
+          },
+          {
+            "name": "keeperTipBps",
+            "docs": [
+              "basis points of the bid surplus of a match paid to the keeper, the rest is refunded to the bidder"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "openOrders",
+            "docs": [
+              "number of orders on the market that are not closed yet, the market can only be closed at 0",
+              "markets older than OPEN_ORDERS_MARKET_VERSION opened orders before it was counted"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserve",
+            "docs": [
+              "reserved space for future changes"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                222
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "marketEditEvent",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "editType",
+            "type": "u8"
+          },
+          {
+            "name": "address",
+            "type": "string"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "marketIdentifier",
+            "type": "string"
+          },
+          {
+            "name": "initializer",
+            "type": "string"
+          },
+          {
+            "name": "state",
+            "type": "u8"
+          },
+          {
+            "name": "feeRecipient",
+            "type": "string"
+          },
+          {
+            "name": "feeBps",
+            "type": "u64"
+          },
+          {
+            "name": "makerFeeBps",
+            "type": "i64"
+          },
+          {
+            "name": "takerFeeBps",
+            "type": "u64"
+          },
+          {
+            "name": "paymentAllowlistOn",
+            "type": "bool"
+          },
+          {
+            "name": "nativeAllowed",
+            "type": "bool"
+          },
+          {
+            "name": "paymentMints",
+            "type": {
+              "vec": "string"
+            }
+          },
+          {
+            "name": "keeperTipBps",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6002,
-      "name": "amountOverflow",
-      "msg": "Amount overflow"
+      "name": "marketState",
+      "repr": {
+        "kind": "rust"
+      },
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "open"
+          },
+          {
+            "name": "closed"
+          }
+        ]
+      }
     },
     {
-      "code": 6003,
-      "name": "amountUnderflow",
-      "msg": "Amount underflow"
+      "name": "mintVerification",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "verified",
+            "type": "u8"
+          }
+        ]
+      }
     },
     {
-      "code": 6004,
-      "name": "unsupportedNft",
-      "msg": "Unsupported NFT Type"
+      "name": "nonceBitmap",
+      "docs": [
+        "nonce bitmap account - replay protection for the signed listings of a user on a market",
+        "also the delegate of the token accounts listed by signature"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "docs": [
+              "nonce bitmap account version"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "market",
+            "docs": [
+              "market the signed listings belong to"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "docs": [
+              "signer of the listings"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "bitmap",
+            "docs": [
+              "one bit per nonce, set once the listing is filled or cancelled"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                1024
+              ]
+            }
+          },
+          {
+            "name": "reserve",
+            "docs": [
+              "reserved space for future changes"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                64
+              ]
+            }
+          }
+        ]
+      }
     },
     {
-      "code": 6005,
-      "name": "invalidNft",
-      "msg": "Invalid NFT for Market"
-    }
-  ],
-  "types": [
-    {
-      "name": "bidData",
+      "name": "order",
+      "docs": [
+        "order account - each listing has one order account"
+      ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "nonce",
+            "name": "version",
+            "docs": [
+              "order account version"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "nonce",
+            "docs": [
+              "nonce for uniqueness"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "market",
+            "docs": [
+              "market to which the order belongs to, must be init'd"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "docs": [
+              "owner of the order account"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "side",
+            "docs": [
+              "type of order - buy/sell/auction/swap"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "size",
+            "docs": [
+              "number of units the order is buying or selling",
+              "reduced by partial fills"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "price",
+            "docs": [
+              "bid amount in lamports",
+              "starting price of a dutch auction"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "state",
+            "docs": [
+              "order state - ready/partial/closed"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "initTime",
+            "docs": [
+              "order account creation time"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "lastEditTime",
+            "docs": [
+              "last time the order was edited"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "nftMint",
+            "docs": [
+              "nft mint in case order is a sell order",
+              "default pubkey for collection bids, which any verified mint of the market can fill",
+              "bundle address for bundle listings and swaps"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "paymentMint",
+            "docs": [
+              "mint for the payment, default pubkey if SOL"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "feesOn",
+            "docs": [
+              "fees on for this order"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "expiry",
+            "docs": [
+              "time after which the order can no longer be filled, 0 if the order never expires"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "merkleRoot",
+            "docs": [
+              "merkle root of the mints a trait bid accepts, zeroed for any other order"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "endPrice",
+            "docs": [
+              "price a dutch auction listing declines to"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "endTime",
+            "docs": [
+              "time a dutch auction listing reaches end_price or an english auction ends",
+              "0 for fixed price orders"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "highestBidder",
+            "docs": [
+              "current highest bidder of an english auction"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "highestBid",
+            "docs": [
+              "current highest bid of an english auction, escrowed in the order"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "minIncrement",
+            "docs": [
+              "amount a new bid must add to the highest bid"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timeExtension",
+            "docs": [
+              "bids placed this close to the end push end_time back to this far from the bid"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "taker",
+            "docs": [
+              "only wallet that can fill a private listing, default pubkey for public orders"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "swapMint",
+            "docs": [
+              "mint a swap wants in return, default pubkey for any verified mint of the market"
+            ],
             "type": "pubkey"
           },
           {
-            "name": "price",
-            "type": "u64"
+            "name": "makerPays",
+            "docs": [
+              "true if the maker of a swap adds price on top of their nfts, false if the taker does"
+            ],
+            "type": "bool"
           },
           {
-            "name": "size",
+            "name": "epoch",
+            "docs": [
+              "order epoch of the owner when the order was created, stale once the owner moves past it"
+            ],
             "type": "u64"
-          }
-        ]
-      }
-    },
-    {
-      "name": "initMarketParams",
-      "type": {
-        "kind": "struct",
-        "fields": [
+          },
           {
-            "name": "feeRecipient",
-            "type": "pubkey"
+            "name": "escrowed",
+            "docs": [
+              "true if the listed nft is held in a token account of the order instead of delegated to it"
+            ],
+            "type": "bool"
           },
           {
-            "name": "feeBps",
-            "type": "u64"
+            "name": "walletBacked",
+            "docs": [
+              "true if a bid is paid from the wallet account of the owner when filled instead of an escrow"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "makerFeeBps",
+            "docs": [
+              "maker fee of a bid or of a swap sweetener its maker pays, escrowed on top of the price",
+              "0 when the market charged no positive maker fee as the order was placed"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "reserve0",
+            "docs": [
+              "reserved space for future changes split up due to serialization constraints"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                67
+              ]
+            }
+          },
+          {
+            "name": "reserve1",
+            "docs": [
+              "reserved space for future changes"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                128
+              ]
+            }
+          },
+          {
+            "name": "reserve2",
+            "docs": [
+              "reserved space for future changes"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                64
+              ]
+            }
+          },
+          {
+            "name": "reserve3",
+            "docs": [
+              "reserved space for future changes"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                30
+              ]
+            }
+          },
+          {
+            "name": "reserve4",
+            "docs": [
+              "reserved space for future changes"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                30
+              ]
+            }
+          },
+          {
+            "name": "reserve5",
+            "docs": [
+              "reserved space for future changes"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                3
+              ]
+            }
           }
         ]
       }
     },
     {
-      "name": "listData",
+      "name": "orderEditEvent",
       "type": {
         "kind": "struct",
         "fields": [
+          {
+            "name": "editType",
+            "type": "u8"
+          },
+          {
+            "name": "address",
+            "type": "string"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
           {
             "name": "nonce",
-            "type": "pubkey"
+            "type": "string"
           },
           {
-            "name": "paymentMint",
-            "type": "pubkey"
+            "name": "market",
+            "type": "string"
+          },
+          {
+            "name": "owner",
+            "type": "string"
+          },
+          {
+            "name": "side",
+            "type": "u8"
+          },
+          {
+            "name": "size",
+            "type": "u64"
           },
           {
             "name": "price",
             "type": "u64"
           },
           {
-            "name": "size",
+            "name": "state",
+            "type": "u8"
+          },
+          {
+            "name": "initTime",
+            "type": "i64"
+          },
+          {
+            "name": "lastEditTime",
+            "type": "i64"
+          },
+          {
+            "name": "nftMint",
+            "type": "string"
+          },
+          {
+            "name": "paymentMint",
+            "type": "string"
+          },
+          {
+            "name": "marketIdentifier",
+            "type": "string"
+          },
+          {
+            "name": "expiry",
+            "type": "i64"
+          },
+          {
+            "name": "endPrice",
+            "type": "u64"
+          },
+          {
+            "name": "endTime",
+            "type": "i64"
+          },
+          {
+            "name": "highestBidder",
+            "type": "string"
+          },
+          {
+            "name": "highestBid",
+            "type": "u64"
+          },
+          {
+            "name": "isPrivate",
+            "type": "bool"
+          },
+          {
+            "name": "taker",
+            "type": "string"
+          },
+          {
+            "name": "swapMint",
+            "type": "string"
+          },
+          {
+            "name": "makerPays",
+            "type": "bool"
+          },
+          {
+            "name": "epoch",
             "type": "u64"
+          },
+          {
+            "name": "escrowed",
+            "type": "bool"
+          },
+          {
+            "name": "walletBacked",
+            "type": "bool"
+          },
+          {
+            "name": "makerFeeBps",
+            "type": "u16"
           }
         ]
       }
     },
     {
-      "name": "market",
+      "name": "orderEpoch",
+      "docs": [
+        "order epoch account - one per owner, orders created under an older epoch can no longer be filled"
+      ],
       "type": {
         "kind": "struct",
         "fields": [
           {
             "name": "version",
             "docs": [
-              "market account version, used to conditionally parse accounts if changes are made to the struct"
-            ],
-            "type": "u8"
-          },
-          {
-            "name": "marketIdentifier",
-            "docs": [
-              "identifying of the index to which the NFTs belong to (WNS Collection, Metaplex collection, separate hash)"
-            ],
-            "type": "pubkey"
-          },
-          {
-            "name": "initializer",
-            "docs": [
-              "initializer of the market - can edit and close the market, admin key"
-            ],
-            "type": "pubkey"
-          },
-          {
-            "name": "state",
-            "docs": [
-              "state representing the market - open/closed"
+              "order epoch account version"
             ],
             "type": "u8"
           },
           {
-            "name": "feeRecipient",
+            "name": "owner",
             "docs": [
-              "address that should receive market fees"
+              "owner of the orders"
             ],
             "type": "pubkey"
           },
           {
-            "name": "feeBps",
+            "name": "epoch",
             "docs": [
-              "fee basis points"
+              "epoch recorded in the new orders of the owner"
             ],
             "type": "u64"
           },
@@ -1390,7 +7809,7 @@ export type Marketplace = {
             "type": {
               "array": [
                 "u8",
-                512
+                64
               ]
             }
           }
@@ -1398,226 +7817,335 @@ export type Marketplace = {
       }
     },
     {
-      "name": "marketEditEvent",
+      "name": "orderEpochEvent",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "address",
+            "type": "string"
+          },
+          {
+            "name": "owner",
+            "type": "string"
+          },
+          {
+            "name": "epoch",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "orderFillEvent",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "editType",
-            "type": "u8"
+            "name": "address",
+            "type": "string"
+          },
+          {
+            "name": "marketIdentifier",
+            "type": "string"
+          },
+          {
+            "name": "maker",
+            "type": "string"
           },
           {
-            "name": "address",
+            "name": "taker",
             "type": "string"
           },
           {
-            "name": "version",
+            "name": "side",
             "type": "u8"
           },
           {
-            "name": "marketIdentifier",
+            "name": "nftMint",
             "type": "string"
           },
           {
-            "name": "initializer",
+            "name": "paymentMint",
             "type": "string"
           },
           {
-            "name": "state",
-            "type": "u8"
+            "name": "amount",
+            "type": "u64"
           },
           {
-            "name": "feeRecipient",
-            "type": "string"
+            "name": "price",
+            "type": "u64"
           },
           {
-            "name": "feeBps",
+            "name": "value",
             "type": "u64"
-          }
-        ]
-      }
-    },
-    {
-      "name": "mintVerification",
-      "type": {
-        "kind": "struct",
-        "fields": [
+          },
           {
-            "name": "verified",
-            "type": "u8"
+            "name": "makerFee",
+            "type": "i64"
+          },
+          {
+            "name": "takerFee",
+            "type": "u64"
+          },
+          {
+            "name": "royalties",
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "order",
+      "name": "signedListing",
       "docs": [
-        "order account - each listing has one order account"
+        "listing terms signed off-chain by the seller, the signed message is their borsh serialization"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "version",
-            "docs": [
-              "order account version"
-            ],
-            "type": "u8"
-          },
-          {
-            "name": "nonce",
-            "docs": [
-              "nonce for uniqueness"
-            ],
+            "name": "market",
             "type": "pubkey"
           },
           {
-            "name": "market",
-            "docs": [
-              "market to which the order belongs to, must be init'd"
-            ],
+            "name": "nftMint",
             "type": "pubkey"
           },
           {
-            "name": "owner",
-            "docs": [
-              "owner of the order account"
-            ],
+            "name": "paymentMint",
             "type": "pubkey"
           },
           {
-            "name": "side",
-            "docs": [
-              "type of order - buy/sell"
-            ],
-            "type": "u8"
+            "name": "price",
+            "type": "u64"
           },
           {
             "name": "size",
-            "docs": [
-              "number of bids order is making",
-              "always for 1 for sell"
-            ],
             "type": "u64"
           },
           {
-            "name": "price",
+            "name": "nonce",
             "docs": [
-              "bid amount in lamports"
+              "index in the nonce bitmap of the seller"
             ],
             "type": "u64"
           },
           {
-            "name": "state",
+            "name": "expiry",
             "docs": [
-              "order state - ready/partial/closed"
+              "time after which the listing can no longer be filled, 0 if it never expires"
             ],
-            "type": "u8"
+            "type": "i64"
           },
           {
-            "name": "initTime",
+            "name": "epoch",
             "docs": [
-              "order account creation time"
+              "order epoch of the seller when signing, stale once the seller moves past it"
             ],
-            "type": "i64"
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "swapData",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "nonce",
+            "type": "pubkey"
           },
           {
-            "name": "lastEditTime",
+            "name": "swapMint",
             "docs": [
-              "last time the order was edited"
+              "mint wanted in return, default pubkey for any verified mint of the market"
             ],
-            "type": "i64"
+            "type": "pubkey"
           },
           {
-            "name": "nftMint",
+            "name": "merkleRoot",
             "docs": [
-              "nft mint in case order is a sell order"
+              "narrows a swap for any mint down to the mints of a merkle root"
             ],
-            "type": "pubkey"
+            "type": {
+              "option": {
+                "array": [
+                  "u8",
+                  32
+                ]
+              }
+            }
           },
           {
             "name": "paymentMint",
             "docs": [
-              "mint for the payment, default pubkey if SOL"
+              "mint of the sweetener, default pubkey if SOL"
             ],
             "type": "pubkey"
           },
           {
-            "name": "feesOn",
+            "name": "sweetener",
             "docs": [
-              "fees on for this order"
+              "optional payment on top of the nfts, 0 for a straight swap"
             ],
-            "type": "bool"
+            "type": "u64"
           },
           {
-            "name": "reserve0",
+            "name": "makerPays",
             "docs": [
-              "reserved space for future changes split up due to serialization constraints"
+              "true if the maker pays the sweetener, which is escrowed in the order"
             ],
+            "type": "bool"
+          },
+          {
+            "name": "expiry",
             "type": {
-              "array": [
-                "u8",
-                256
-              ]
+              "option": "i64"
             }
-          },
+          }
+        ]
+      }
+    },
+    {
+      "name": "swapFillData",
+      "type": {
+        "kind": "struct",
+        "fields": [
           {
-            "name": "reserve1",
+            "name": "itemAccounts",
             "docs": [
-              "reserved space for future changes"
+              "number of remaining accounts each offered mint uses, in bundle order,",
+              "followed by the number the wanted mint uses"
             ],
+            "type": "bytes"
+          },
+          {
+            "name": "merkleProof",
             "type": {
-              "array": [
-                "u8",
-                128
-              ]
+              "option": {
+                "vec": {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                }
+              }
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "updateMarketParams",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "feeRecipient",
+            "type": {
+              "option": "pubkey"
             }
           },
           {
-            "name": "reserve2",
+            "name": "feeBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "makerFeeBps",
+            "type": {
+              "option": "i64"
+            }
+          },
+          {
+            "name": "takerFeeBps",
+            "type": {
+              "option": "u64"
+            }
+          },
+          {
+            "name": "state",
+            "type": {
+              "option": {
+                "defined": {
+                  "name": "marketState"
+                }
+              }
+            }
+          },
+          {
+            "name": "paymentAllowlistOn",
             "docs": [
-              "reserved space for future changes"
+              "limits orders to the allowlisted payment mints"
             ],
             "type": {
-              "array": [
-                "u8",
-                64
-              ]
+              "option": "bool"
             }
           },
           {
-            "name": "reserve3",
+            "name": "nativeAllowed",
+            "type": {
+              "option": "bool"
+            }
+          },
+          {
+            "name": "paymentMints",
             "docs": [
-              "reserved space for future changes"
+              "replaces the allowlisted payment mints, native SOL is set by native_allowed"
             ],
             "type": {
-              "array": [
-                "u8",
-                30
-              ]
+              "option": {
+                "vec": "pubkey"
+              }
             }
           },
           {
-            "name": "reserve4",
+            "name": "keeperTipBps",
             "docs": [
-              "reserved space for future changes"
+              "share of the bid surplus paid to keepers matching orders"
             ],
             "type": {
-              "array": [
-                "u8",
-                30
-              ]
+              "option": "u64"
             }
+          }
+        ]
+      }
+    },
+    {
+      "name": "wallet",
+      "docs": [
+        "wallet account - bidding balance of a user, drawn on by their wallet backed bids when filled",
+        "native balance is held in the lamports of the account, token balances in its token accounts"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "version",
+            "docs": [
+              "wallet account version"
+            ],
+            "type": "u8"
           },
           {
-            "name": "reserve5",
+            "name": "owner",
+            "docs": [
+              "owner of the balance"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "reserve",
             "docs": [
               "reserved space for future changes"
             ],
             "type": {
               "array": [
                 "u8",
-                3
+                64
               ]
             }
           }
@@ -1625,7 +8153,7 @@ export type Marketplace = {
       }
     },
     {
-      "name": "orderEditEvent",
+      "name": "walletEditEvent",
       "type": {
         "kind": "struct",
         "fields": [
@@ -1637,57 +8165,17 @@ export type Marketplace = {
             "name": "address",
             "type": "string"
           },
-          {
-            "name": "version",
-            "type": "u8"
-          },
-          {
-            "name": "nonce",
-            "type": "string"
-          },
-          {
-            "name": "market",
-            "type": "string"
-          },
           {
             "name": "owner",
             "type": "string"
           },
-          {
-            "name": "side",
-            "type": "u8"
-          },
-          {
-            "name": "size",
-            "type": "u64"
-          },
-          {
-            "name": "price",
-            "type": "u64"
-          },
-          {
-            "name": "state",
-            "type": "u8"
-          },
-          {
-            "name": "initTime",
-            "type": "i64"
-          },
-          {
-            "name": "lastEditTime",
-            "type": "i64"
-          },
-          {
-            "name": "nftMint",
-            "type": "string"
-          },
           {
             "name": "paymentMint",
             "type": "string"
           },
           {
-            "name": "marketIdentifier",
-            "type": "string"
+            "name": "amount",
+            "type": "u64"
           }
         ]
       }
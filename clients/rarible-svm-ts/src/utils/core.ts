@@ -225,6 +225,18 @@ export const getOrderAccount = (nonce: string, marketAddress: string, user: stri
 	return marketAccount;
 };
 
+export const getOrderEpochPda = (user: string) => {
+	const [orderEpochAccount] = PublicKey.findProgramAddressSync([utils.bytes.utf8.encode('epoch'), new PublicKey(user).toBuffer()], marketplaceProgramId);
+
+	return orderEpochAccount;
+};
+
+export const getWalletPda = (user: string) => {
+	const [walletAccount] = PublicKey.findProgramAddressSync([utils.bytes.utf8.encode('wallet'), new PublicKey(user).toBuffer()], marketplaceProgramId);
+
+	return walletAccount;
+};
+
 export const getEventAuthority = () => {
 	const [eventAuthority] = PublicKey.findProgramAddressSync([utils.bytes.utf8.encode('__event_authority')], marketplaceProgramId);
 
@@ -14,4 +14,50 @@ pub enum MarketError {
     UnsupportedNft,
     #[msg("Invalid NFT for Market")]
     InvalidNft,
+    #[msg("Fee basis points too high")]
+    InvalidFeeBps,
+    #[msg("Order cannot be edited this way")]
+    InvalidOrderEdit,
+    #[msg("Order has expired")]
+    OrderExpired,
+    #[msg("Order has not expired")]
+    OrderNotExpired,
+    #[msg("Merkle proof required")]
+    MerkleProofRequired,
+    #[msg("Invalid merkle proof")]
+    InvalidMerkleProof,
+    #[msg("Invalid auction parameters")]
+    InvalidAuctionParams,
+    #[msg("Auction has ended")]
+    AuctionEnded,
+    #[msg("Auction has not ended")]
+    AuctionNotEnded,
+    #[msg("Bid too low")]
+    BidTooLow,
+    #[msg("Order is reserved for another taker")]
+    InvalidTaker,
+    #[msg("Invalid swap parameters")]
+    InvalidSwapParams,
+    #[msg("Payment mint is not allowed by the market")]
+    PaymentMintNotAllowed,
+    #[msg("Invalid payment mint allowlist")]
+    InvalidPaymentMints,
+    #[msg("Bid does not cover the ask")]
+    OrdersNotCrossed,
+    #[msg("Listing signature is invalid")]
+    InvalidSignature,
+    #[msg("Nonce has already been used")]
+    NonceUsed,
+    #[msg("Order was cancelled by its owner's order epoch")]
+    StaleOrder,
+    #[msg("Order is not stale")]
+    OrderNotStale,
+    #[msg("Wallet balance is too low")]
+    InsufficientBalance,
+    #[msg("Market still has open orders")]
+    MarketHasOpenOrders,
+    #[msg("Listing delegate can only be revoked by its owner")]
+    OwnerMustRevoke,
+    #[msg("Market predates the open order count and cannot be closed")]
+    MarketOrdersUncounted,
 }
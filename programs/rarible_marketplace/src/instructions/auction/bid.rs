@@ -0,0 +1,228 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    errors::MarketError,
+    state::*,
+    utils::{create_ata, get_bump_in_seed_form, lamport_transfer, transfer_sol},
+};
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+#[event_cpi]
+pub struct BidAuction<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        constraint = Market::is_active(market.state),
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(
+        mut,
+        constraint = Order::is_active(order.state),
+        constraint = order.is_auction(),
+        constraint = order.market == market.key(),
+        seeds = [ORDER_SEED,
+        order.nonce.as_ref(),
+        order.market.as_ref(),
+        order.owner.as_ref()],
+        bump,
+    )]
+    pub order: Box<Account<'info, Order>>,
+    #[account(
+        seeds = [ORDER_EPOCH_SEED,
+        order.owner.as_ref()],
+        bump,
+    )]
+    /// CHECK: seeds constraint, empty until the order owner first cancels all their orders
+    pub order_epoch: UncheckedAccount<'info>,
+    /// left out for the first bid of the auction
+    #[account(mut, address = order.highest_bidder)]
+    /// CHECK: checked by address
+    pub previous_bidder: Option<UncheckedAccount<'info>>,
+    /// payment accounts are left out for auctions in native SOL
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub bidder_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub previous_bidder_payment_ta: Option<UncheckedAccount<'info>>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        associated_token::mint = payment_mint,
+        associated_token::authority = order,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub order_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(constraint = payment_mint.key() == order.payment_mint)]
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> BidAuction<'info> {
+    /// moves the new bid into the order escrow
+    fn escrow_bid(&self, amount: u64) -> Result<()> {
+        if self.order.is_native() {
+            return transfer_sol(
+                self.bidder.to_account_info(),
+                self.order.to_account_info(),
+                self.system_program.to_account_info(),
+                None,
+                amount,
+            );
+        }
+
+        let (
+            Some(bidder_payment_ta),
+            Some(order_payment_ta),
+            Some(payment_mint),
+            Some(payment_token_program),
+        ) = (
+            &self.bidder_payment_ta,
+            &self.order_payment_ta,
+            &self.payment_mint,
+            &self.payment_token_program,
+        )
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        let cpi_ctx = CpiContext::new(
+            payment_token_program.to_account_info(),
+            TransferChecked {
+                from: bidder_payment_ta.to_account_info(),
+                to: order_payment_ta.to_account_info(),
+                authority: self.bidder.to_account_info(),
+                mint: payment_mint.to_account_info(),
+            },
+        );
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)
+    }
+
+    /// hands the outbid amount back from the order escrow
+    fn refund_previous_bid(&self, signer_seeds: &[&[&[u8]]], amount: u64) -> Result<()> {
+        let Some(previous_bidder) = &self.previous_bidder else {
+            return Err(MarketError::WrongAccount.into());
+        };
+        if self.order.is_native() {
+            return lamport_transfer(
+                self.order.to_account_info(),
+                previous_bidder.to_account_info(),
+                amount,
+            );
+        }
+
+        let (
+            Some(previous_bidder_payment_ta),
+            Some(order_payment_ta),
+            Some(payment_mint),
+            Some(payment_token_program),
+        ) = (
+            &self.previous_bidder_payment_ta,
+            &self.order_payment_ta,
+            &self.payment_mint,
+            &self.payment_token_program,
+        )
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        create_ata(
+            &previous_bidder_payment_ta.to_account_info(),
+            &self.bidder.to_account_info(),
+            &payment_mint.to_account_info(),
+            &previous_bidder.to_account_info(),
+            &self.system_program.to_account_info(),
+            &payment_token_program.to_account_info(),
+        )?;
+        let cpi_ctx = CpiContext::new_with_signer(
+            payment_token_program.to_account_info(),
+            TransferChecked {
+                from: order_payment_ta.to_account_info(),
+                to: previous_bidder_payment_ta.to_account_info(),
+                authority: self.order.to_account_info(),
+                mint: payment_mint.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)
+    }
+}
+
+/// outbid the current highest bidder of an english auction, who is refunded
+/// the first bid must meet the reserve price, later ones must add the minimum increment
+#[inline(always)]
+pub fn handler(ctx: Context<BidAuction>, amount: u64) -> Result<()> {
+    msg!("Bid on auction: {}", ctx.accounts.order.key());
+
+    let clock = Clock::get()?;
+    if clock.unix_timestamp >= ctx.accounts.order.end_time {
+        return Err(MarketError::AuctionEnded.into());
+    }
+    // bids already placed can still be settled
+    if ctx
+        .accounts
+        .order
+        .is_stale(OrderEpoch::get_current(&ctx.accounts.order_epoch)?)
+    {
+        return Err(MarketError::StaleOrder.into());
+    }
+
+    let previous_bid = ctx.accounts.order.highest_bid;
+    let min_bid = if previous_bid == 0 {
+        ctx.accounts.order.price
+    } else {
+        previous_bid
+            .checked_add(ctx.accounts.order.min_increment)
+            .ok_or(MarketError::AmountOverflow)?
+    };
+    if amount < min_bid {
+        return Err(MarketError::BidTooLow.into());
+    }
+
+    let bump = &get_bump_in_seed_form(&ctx.bumps.order);
+    let signer_seeds: &[&[&[u8]]; 1] = &[&[
+        ORDER_SEED,
+        ctx.accounts.order.nonce.as_ref(),
+        ctx.accounts.order.market.as_ref(),
+        ctx.accounts.order.owner.as_ref(),
+        bump,
+    ][..]];
+
+    ctx.accounts.escrow_bid(amount)?;
+    if previous_bid > 0 {
+        ctx.accounts
+            .refund_previous_bid(signer_seeds, previous_bid)?;
+    }
+
+    let order = &mut ctx.accounts.order;
+    order.highest_bidder = ctx.accounts.bidder.key();
+    order.highest_bid = amount;
+    order.last_edit_time = clock.unix_timestamp;
+    // anti-sniping, late bids give the others time to respond
+    if order.end_time - clock.unix_timestamp < order.time_extension {
+        order.end_time = clock.unix_timestamp + order.time_extension;
+    }
+
+    emit_cpi!(Order::get_edit_event(
+        &mut ctx.accounts.order.clone(),
+        ctx.accounts.order.key(),
+        ctx.accounts.market.market_identifier,
+        OrderEditType::AuctionBid,
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct AuctionData {
+    pub end_time: i64,
+    pub min_increment: u64,
+    pub time_extension: i64,
+}
+
+pub mod bid;
+pub mod settle;
+
+pub use bid::*;
+pub use settle::*;
@@ -0,0 +1,716 @@
+use std::str::FromStr;
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program_option::COption, sysvar},
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::spl_token_2022::instruction::transfer_checked as transfer_2022,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+use spl_transfer_hook_interface::onchain::add_extra_accounts_for_execute_cpi;
+use wen_new_standard::{
+    cpi::{accounts::ApproveTransfer, approve_transfer},
+    utils::get_mint_metadata,
+    ROYALTY_BASIS_POINTS_FIELD,
+};
+
+use crate::{
+    errors::MarketError,
+    state::*,
+    utils::{
+        create_ata, get_amount_from_bp, get_bump_in_seed_form, get_editions_royalties,
+        get_has_royalty_field, get_verified_metadata, lamport_transfer,
+        metaplex::pnft::utils::get_is_pnft,
+        mplx_transfer::{
+            get_extra_transfer_params, transfer_metaplex_nft, MetaplexAdditionalTransferAccounts,
+            TransferMetaplexNft,
+        },
+        parse_remaining_accounts_pnft,
+        token_extensions::WnsApprovalAccounts,
+        verify_mint_verification, verify_wns_mint,
+    },
+};
+
+#[derive(Accounts)]
+#[instruction()]
+#[event_cpi]
+pub struct SettleAuction<'info> {
+    /// anyone can settle an auction once it has ended
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, constraint = maker.key() == order.owner)]
+    /// CHECK: constraint check
+    pub maker: UncheckedAccount<'info>,
+    #[account(mut, address = order.highest_bidder)]
+    /// CHECK: checked by address
+    pub winner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(
+        mut,
+        constraint = Order::is_active(order.state),
+        constraint = order.is_auction(),
+        constraint = order.market == market.key(),
+        seeds = [ORDER_SEED,
+        order.nonce.as_ref(),
+        order.market.as_ref(),
+        order.owner.as_ref()],
+        bump,
+        close = maker
+    )]
+    pub order: Box<Account<'info, Order>>,
+    #[account(mut, address = order.nft_mint)]
+    pub nft_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub seller_nft_ta: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub winner_nft_ta: UncheckedAccount<'info>,
+    #[account(mut, constraint = fee_recipient.key() == market.fee_recipient.key())]
+    /// CHECK: constraint check
+    pub fee_recipient: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub fee_recipient_ta: Option<UncheckedAccount<'info>>,
+    pub system_program: Program<'info, System>,
+    pub nft_token_program: Interface<'info, TokenInterface>,
+    /// CHECK: checked by constraint and in cpi
+    pub nft_program: UncheckedAccount<'info>,
+    /// payment token accounts, mint and program are left out for auctions in native SOL
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub seller_payment_ta: Option<UncheckedAccount<'info>>,
+    /// only needed to refund the winner when the seller no longer holds the nft
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub winner_payment_ta: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = order,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub order_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut, constraint = payment_mint.key() == order.payment_mint)]
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+    /// CHECK: checked by constraint and in cpi
+    #[account(address = sysvar::instructions::id())]
+    pub sysvar_instructions: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> SettleAuction<'info> {
+    /// pays the recipient out of the escrowed winning bid
+    #[inline(never)]
+    fn transfer_payment(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        recipient: AccountInfo<'info>,
+        recipient_ta: Option<AccountInfo<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        if self.order.is_native() {
+            return lamport_transfer(self.order.to_account_info(), recipient, amount);
+        }
+
+        let (
+            Some(order_payment_ta),
+            Some(recipient_ta),
+            Some(payment_mint),
+            Some(payment_token_program),
+        ) = (
+            &self.order_payment_ta,
+            recipient_ta,
+            &self.payment_mint,
+            &self.payment_token_program,
+        )
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            payment_token_program.to_account_info(),
+            TransferChecked {
+                from: order_payment_ta.to_account_info(),
+                to: recipient_ta,
+                authority: self.order.to_account_info(),
+                mint: payment_mint.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)
+    }
+
+    /// native auctions pay wallets directly and need no token accounts
+    #[inline(never)]
+    fn create_payment_ata(
+        &self,
+        ata: Option<AccountInfo<'info>>,
+        owner: &AccountInfo<'info>,
+    ) -> Result<()> {
+        if self.order.is_native() {
+            return Ok(());
+        }
+
+        let (Some(ata), Some(payment_mint), Some(payment_token_program)) =
+            (ata, &self.payment_mint, &self.payment_token_program)
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        create_ata(
+            &ata,
+            &self.payer.to_account_info(),
+            &payment_mint.to_account_info(),
+            owner,
+            &self.system_program.to_account_info(),
+            &payment_token_program.to_account_info(),
+        )
+    }
+
+    /// the emptied escrow account is closed along with the order
+    #[inline(never)]
+    fn close_order_payment_ta(&self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let (Some(order_payment_ta), Some(payment_token_program)) =
+            (&self.order_payment_ta, &self.payment_token_program)
+        else {
+            return Ok(());
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            payment_token_program.to_account_info(),
+            CloseAccount {
+                account: order_payment_ta.to_account_info(),
+                destination: self.maker.to_account_info(),
+                authority: self.order.to_account_info(),
+            },
+            signer_seeds,
+        );
+        close_account(cpi_ctx)
+    }
+
+    /// false once the seller has moved the nft away or revoked the order's delegation
+    fn is_nft_delegated(&self) -> Result<bool> {
+        let seller_nft_ta_info = self.seller_nft_ta.to_account_info();
+        let seller_nft_ta_data = seller_nft_ta_info.try_borrow_data()?;
+        let seller_nft_ta = TokenAccount::try_deserialize(&mut &seller_nft_ta_data[..])?;
+        if seller_nft_ta.mint != self.nft_mint.key() || seller_nft_ta.owner != self.maker.key() {
+            return Err(MarketError::WrongAccount.into());
+        }
+
+        Ok(seller_nft_ta.delegate == COption::Some(self.order.key())
+            && seller_nft_ta.delegated_amount >= self.order.size
+            && seller_nft_ta.amount >= self.order.size)
+    }
+
+    /*
+        Metaplex Transfer Instructions
+    */
+    #[inline(never)]
+    fn metaplex_nft_transfer(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        amount: u64,
+        is_pnft: bool,
+        transfer_params: MetaplexAdditionalTransferAccounts<'info>,
+    ) -> Result<()> {
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.nft_program.to_account_info(),
+            TransferMetaplexNft {
+                authority: self.order.to_account_info(),
+                payer: self.payer.to_account_info(),
+                source_owner: self.maker.to_account_info(),
+                source_ta: self.seller_nft_ta.to_account_info(),
+                destination_owner: self.winner.to_account_info(),
+                destination_ta: self.winner_nft_ta.to_account_info(),
+                mint: self.nft_mint.to_account_info(),
+                metadata: transfer_params.metadata,
+                edition: transfer_params.edition,
+                system_program: self.system_program.to_account_info(),
+                instructions: self.sysvar_instructions.to_account_info(),
+                token_program: self.nft_token_program.to_account_info(),
+                ata_program: self.associated_token_program.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        transfer_metaplex_nft(cpi_ctx, transfer_params.extra_accounts, amount, is_pnft)
+    }
+
+    /*
+        Token 22 Transfer Instructions
+    */
+
+    // WNS Pre-Transfer Approval
+    #[inline(never)]
+    fn approve_wns_transfer(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        buy_amount: u64,
+        wns_accounts: WnsApprovalAccounts<'info>,
+    ) -> Result<()> {
+        let cpi_program = self.nft_program.to_account_info();
+        if wns_accounts.payment_mint.key() != self.order.payment_mint {
+            return Err(MarketError::WrongAccount.into());
+        }
+        self.create_payment_ata(
+            Some(wns_accounts.distribution_token_account.to_account_info()),
+            &wns_accounts.distribution_account,
+        )?;
+
+        // native royalties are a system transfer the order account can't make,
+        // so the payer pays them after being reimbursed from the escrow
+        let is_native = self.order.is_native();
+        let authority = if is_native {
+            self.payer.to_account_info()
+        } else {
+            self.order.to_account_info()
+        };
+        let (distribution_token_account, authority_token_account, payment_token_program) =
+            if is_native {
+                (None, None, None)
+            } else {
+                (
+                    Some(wns_accounts.distribution_token_account.to_account_info()),
+                    self.order_payment_ta
+                        .as_ref()
+                        .map(|order_payment_ta| order_payment_ta.to_account_info()),
+                    self.payment_token_program
+                        .as_ref()
+                        .map(|payment_token_program| payment_token_program.to_account_info()),
+                )
+            };
+
+        let cpi_accounts = ApproveTransfer {
+            payer: self.payer.to_account_info(),
+            authority,
+            mint: self.nft_mint.to_account_info(),
+            approve_account: wns_accounts.approval_account.to_account_info(),
+            payment_mint: wns_accounts.payment_mint.to_account_info(),
+            distribution_token_account,
+            authority_token_account,
+            distribution_account: wns_accounts.distribution_account.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            distribution_program: wns_accounts.distribution_program.to_account_info(),
+            token_program: self.nft_token_program.to_account_info(),
+            payment_token_program,
+        };
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        approve_transfer(cpi_ctx, buy_amount)
+    }
+
+    // General Token22 Transfer
+    #[inline(never)]
+    fn token22_nft_transfer(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        amount: u64,
+        remaining_accounts: Vec<AccountInfo<'info>>,
+    ) -> Result<()> {
+        let authority_info = self.order.to_account_info();
+
+        let mut transfer_ix = transfer_2022(
+            self.nft_token_program.key,
+            self.seller_nft_ta.key,
+            self.nft_mint.to_account_info().key,
+            self.winner_nft_ta.key,
+            authority_info.key,
+            &[],
+            amount,
+            self.nft_mint.decimals,
+        )?;
+
+        let mut account_infos = vec![
+            self.seller_nft_ta.to_account_info(),
+            self.nft_mint.to_account_info(),
+            self.winner_nft_ta.to_account_info(),
+            authority_info.clone(),
+        ];
+
+        if let Some(hook_program) = remaining_accounts.last() {
+            // transfer hook
+            add_extra_accounts_for_execute_cpi(
+                &mut transfer_ix,
+                &mut account_infos,
+                hook_program.key,
+                self.seller_nft_ta.to_account_info(),
+                self.nft_mint.to_account_info(),
+                self.winner_nft_ta.to_account_info(),
+                authority_info,
+                amount,
+                &remaining_accounts,
+            )?;
+        }
+
+        anchor_lang::solana_program::program::invoke_signed(
+            &transfer_ix,
+            &account_infos,
+            signer_seeds,
+        )?;
+
+        Ok(())
+    }
+}
+
+/// hands the auctioned nft to the highest bidder and pays out the winning bid
+/// with market fees and royalties, then closes the order
+/// if the seller no longer holds the delegated nft the winning bid is refunded instead
+#[inline(never)]
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, SettleAuction<'info>>) -> Result<()> {
+    msg!("Settle auction: {}", ctx.accounts.order.key());
+
+    let clock = Clock::get()?;
+    if clock.unix_timestamp < ctx.accounts.order.end_time {
+        return Err(MarketError::AuctionNotEnded.into());
+    }
+    // auctions without bids are cancelled by the seller instead
+    let winning_bid = ctx.accounts.order.highest_bid;
+    if winning_bid == 0 {
+        return Err(MarketError::BidTooLow.into());
+    }
+
+    let nft_token_program_key = &ctx.accounts.nft_token_program.key.to_string().clone();
+    let nft_program_key = &ctx.accounts.nft_program.key.to_string().clone();
+    let remaining_accounts = ctx.remaining_accounts.to_vec();
+
+    let bump = &get_bump_in_seed_form(&ctx.bumps.order);
+    let signer_seeds: &[&[&[u8]]; 1] = &[&[
+        ORDER_SEED,
+        ctx.accounts.order.nonce.as_ref(),
+        ctx.accounts.order.market.as_ref(),
+        ctx.accounts.order.owner.as_ref(),
+        bump,
+    ][..]];
+
+    let amount = ctx.accounts.order.size;
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let nft_token_program = ctx.accounts.nft_token_program.to_account_info();
+    let payer = ctx.accounts.payer.to_account_info();
+
+    create_ata(
+        &ctx.accounts.seller_nft_ta.to_account_info(),
+        &payer,
+        &ctx.accounts.nft_mint.to_account_info(),
+        &ctx.accounts.maker.to_account_info(),
+        &system_program,
+        &nft_token_program,
+    )?;
+
+    // the seller can no longer deliver the nft, so the winning bid goes back to the winner
+    if !ctx.accounts.is_nft_delegated()? {
+        let winner = ctx.accounts.winner.to_account_info();
+        let winner_payment_ta = ctx
+            .accounts
+            .winner_payment_ta
+            .as_ref()
+            .map(|winner_payment_ta| winner_payment_ta.to_account_info());
+        ctx.accounts
+            .create_payment_ata(winner_payment_ta.clone(), &winner)?;
+        ctx.accounts
+            .transfer_payment(signer_seeds, winner, winner_payment_ta, winning_bid)?;
+        ctx.accounts.close_order_payment_ta(signer_seeds)?;
+
+        ctx.accounts.market.remove_open_order();
+        ctx.accounts.order.state = OrderState::Closed.into();
+        emit_cpi!(Order::get_edit_event(
+            &mut ctx.accounts.order.clone(),
+            ctx.accounts.order.key(),
+            ctx.accounts.market.market_identifier,
+            OrderEditType::Close,
+        ));
+        return Ok(());
+    }
+
+    // the winner's fee comes out of the escrowed bid, the seller keeps the rest
+    let fill_fees = if ctx.accounts.order.fees_on {
+        ctx.accounts.market.get_fill_fees(winning_bid, false)?
+    } else {
+        FillFees::default()
+    };
+    let fee_amount = u64::try_from(
+        fill_fees
+            .maker_fee
+            .saturating_add_unsigned(fill_fees.taker_fee),
+    )
+    .map_err(|_| MarketError::AmountUnderflow)?;
+    let mut seller_received_amount = winning_bid
+        .checked_sub(fee_amount)
+        .ok_or(MarketError::AmountUnderflow)?;
+    let mut royalties_paid: u64 = 0;
+
+    create_ata(
+        &ctx.accounts.winner_nft_ta.to_account_info(),
+        &payer,
+        &ctx.accounts.nft_mint.to_account_info(),
+        &ctx.accounts.winner.to_account_info(),
+        &system_program,
+        &nft_token_program,
+    )?;
+
+    let maker = ctx.accounts.maker.to_account_info();
+    let seller_payment_ta = ctx
+        .accounts
+        .seller_payment_ta
+        .as_ref()
+        .map(|seller_payment_ta| seller_payment_ta.to_account_info());
+    ctx.accounts
+        .create_payment_ata(seller_payment_ta.clone(), &maker)?;
+
+    let fee_reciever = ctx.accounts.fee_recipient.to_account_info();
+    let fee_recipient_ta = ctx
+        .accounts
+        .fee_recipient_ta
+        .as_ref()
+        .map(|fee_recipient_ta| fee_recipient_ta.to_account_info());
+    ctx.accounts
+        .create_payment_ata(fee_recipient_ta.clone(), &fee_reciever)?;
+
+    // Transfer NFT
+    if *nft_token_program_key == TOKEN_PID && *nft_program_key == METAPLEX_PID {
+        // Remaining Accounts 0-2 for metadata, edition and manual verification
+        let nft_metadata = remaining_accounts
+            .first()
+            .ok_or(MarketError::WrongAccount)?;
+        let nft_edition = remaining_accounts.get(1).ok_or(MarketError::WrongAccount)?;
+        let verification_account = remaining_accounts.get(2).ok_or(MarketError::WrongAccount)?;
+
+        verify_mint_verification(
+            verification_account,
+            ctx.accounts.nft_mint.key(),
+            ctx.accounts.market.key(),
+        )?;
+
+        let parsed_metadata = get_verified_metadata(nft_metadata, &ctx.accounts.nft_mint.key())?;
+        let is_pnft = get_is_pnft(&parsed_metadata);
+
+        // Remaining Accounts after the pNFT accounts are a creator and creator payment ta per creator
+        let creator_accounts = remaining_accounts
+            .get(if is_pnft { 7 } else { 3 }..)
+            .ok_or(MarketError::WrongAccount)?;
+        let royalties =
+            get_amount_from_bp(winning_bid, parsed_metadata.seller_fee_basis_points.into())?;
+        for (i, creator) in parsed_metadata.creators.iter().flatten().enumerate() {
+            let creator_account = creator_accounts
+                .get(2 * i)
+                .ok_or(MarketError::WrongAccount)?;
+            let creator_payment_ta = creator_accounts
+                .get(1 + 2 * i)
+                .ok_or(MarketError::WrongAccount)?;
+            if *creator_account.key != creator.address {
+                return Err(MarketError::WrongAccount.into());
+            }
+
+            let creator_amount = get_amount_from_bp(royalties, u128::from(creator.share) * 100)?;
+            if creator_amount == 0 {
+                continue;
+            }
+            ctx.accounts
+                .create_payment_ata(Some(creator_payment_ta.to_account_info()), creator_account)?;
+            ctx.accounts.transfer_payment(
+                signer_seeds,
+                creator_account.to_account_info(),
+                Some(creator_payment_ta.to_account_info()),
+                creator_amount,
+            )?;
+            royalties_paid = royalties_paid
+                .checked_add(creator_amount)
+                .ok_or(MarketError::AmountOverflow)?;
+        }
+        seller_received_amount = seller_received_amount
+            .checked_sub(royalties_paid)
+            .ok_or(MarketError::AmountUnderflow)?;
+
+        // Remaining Accounts 3-6 for pNFT token records and rule set
+        let extra_accounts = if is_pnft {
+            let (_, extra_remaining_accounts) = remaining_accounts.split_at(3);
+            parse_remaining_accounts_pnft(extra_remaining_accounts.to_vec(), false, None)
+                .pnft_params
+                .into()
+        } else {
+            get_extra_transfer_params(Vec::new(), None, 0)
+        };
+
+        let transfer_params = MetaplexAdditionalTransferAccounts {
+            metadata: nft_metadata.to_account_info(),
+            edition: nft_edition.to_account_info(),
+            extra_accounts,
+        };
+
+        ctx.accounts
+            .metaplex_nft_transfer(signer_seeds, amount, is_pnft, transfer_params)?;
+    } else if *nft_token_program_key == TOKEN_EXT_PID {
+        let mut token22_ra = remaining_accounts.clone();
+        if *nft_program_key == WNS_PID {
+            // Remaining Accounts 0-5 for approval
+            let approval_account = remaining_accounts
+                .first()
+                .ok_or(MarketError::WrongAccount)?;
+            let distribution_account =
+                remaining_accounts.get(1).ok_or(MarketError::WrongAccount)?;
+            let distribution_token_account =
+                remaining_accounts.get(2).ok_or(MarketError::WrongAccount)?;
+            let distribution_program =
+                remaining_accounts.get(3).ok_or(MarketError::WrongAccount)?;
+            let group_member_account =
+                remaining_accounts.get(4).ok_or(MarketError::WrongAccount)?;
+            let payment_mint = remaining_accounts.get(5).ok_or(MarketError::WrongAccount)?;
+
+            verify_wns_mint(
+                ctx.accounts.nft_mint.to_account_info(),
+                group_member_account.to_account_info(),
+                ctx.accounts.market.market_identifier,
+            )?;
+
+            token22_ra = remaining_accounts.split_at(6).1.to_vec();
+
+            let wns_accounts = WnsApprovalAccounts {
+                approval_account: approval_account.to_account_info(),
+                distribution_account: distribution_account.to_account_info(),
+                distribution_token_account: distribution_token_account.to_account_info(),
+                distribution_program: distribution_program.to_account_info(),
+                payment_mint: payment_mint.to_account_info(),
+            };
+
+            let mint_metadata = get_mint_metadata(&mut ctx.accounts.nft_mint.to_account_info())?;
+            let royalty_basis_points = mint_metadata
+                .additional_metadata
+                .iter()
+                .find(|(key, _)| key == ROYALTY_BASIS_POINTS_FIELD)
+                .map(|(_, value)| value)
+                .map(|value| u64::from_str(value).unwrap())
+                .unwrap_or(0);
+
+            let royalties = get_amount_from_bp(winning_bid, royalty_basis_points.into())?;
+            seller_received_amount = seller_received_amount
+                .checked_sub(royalties)
+                .ok_or(MarketError::AmountUnderflow)?;
+            royalties_paid = royalties;
+
+            if ctx.accounts.order.is_native() {
+                lamport_transfer(
+                    ctx.accounts.order.to_account_info(),
+                    payer.clone(),
+                    royalties,
+                )?;
+            }
+
+            // Handles royalties
+            ctx.accounts
+                .approve_wns_transfer(signer_seeds, winning_bid, wns_accounts)?;
+        } else if *nft_program_key == EDITIONS_PID {
+            // Remaining Account 0 for the editions deployment, then a creator and creator payment ta per creator
+            let editions_deployment = remaining_accounts
+                .first()
+                .ok_or(MarketError::WrongAccount)?;
+            let editions_royalties = get_editions_royalties(
+                &mut ctx.accounts.nft_mint.to_account_info(),
+                editions_deployment,
+            )?;
+            let royalties =
+                get_amount_from_bp(winning_bid, editions_royalties.royalty_basis_points.into())?;
+
+            for (i, creator) in editions_royalties.creators.iter().enumerate() {
+                let creator_account = remaining_accounts
+                    .get(1 + 2 * i)
+                    .ok_or(MarketError::WrongAccount)?;
+                let creator_payment_ta = remaining_accounts
+                    .get(2 + 2 * i)
+                    .ok_or(MarketError::WrongAccount)?;
+                if *creator_account.key != creator.address {
+                    return Err(MarketError::WrongAccount.into());
+                }
+
+                let creator_amount =
+                    get_amount_from_bp(royalties, u128::from(creator.share) * 100)?;
+                if creator_amount == 0 {
+                    continue;
+                }
+                ctx.accounts.create_payment_ata(
+                    Some(creator_payment_ta.to_account_info()),
+                    creator_account,
+                )?;
+                ctx.accounts.transfer_payment(
+                    signer_seeds,
+                    creator_account.to_account_info(),
+                    Some(creator_payment_ta.to_account_info()),
+                    creator_amount,
+                )?;
+                royalties_paid = royalties_paid
+                    .checked_add(creator_amount)
+                    .ok_or(MarketError::AmountOverflow)?;
+            }
+            seller_received_amount = seller_received_amount
+                .checked_sub(royalties_paid)
+                .ok_or(MarketError::AmountUnderflow)?;
+
+            token22_ra = remaining_accounts
+                .split_at(1 + 2 * editions_royalties.creators.len())
+                .1
+                .to_vec();
+        } else if get_has_royalty_field(&mut ctx.accounts.nft_mint.to_account_info()) {
+            // mints carrying royalties must go through their royalty path
+            return Err(MarketError::UnsupportedNft.into());
+        }
+        // Any remaining accounts left are for potential transfer hook (Empty if not expecting hook)
+        ctx.accounts
+            .token22_nft_transfer(signer_seeds, amount, token22_ra)?;
+    } else {
+        return Err(MarketError::UnsupportedNft.into());
+    }
+
+    // Transfer payment
+    ctx.accounts.transfer_payment(
+        signer_seeds,
+        maker,
+        seller_payment_ta,
+        seller_received_amount,
+    )?;
+    ctx.accounts
+        .transfer_payment(signer_seeds, fee_reciever, fee_recipient_ta, fee_amount)?;
+    ctx.accounts.close_order_payment_ta(signer_seeds)?;
+
+    emit_cpi!(ctx.accounts.order.get_fill_event(
+        ctx.accounts.order.key(),
+        ctx.accounts.market.market_identifier,
+        ctx.accounts.winner.key(),
+        ctx.accounts.nft_mint.key(),
+        amount,
+        winning_bid,
+        winning_bid,
+        &fill_fees,
+        royalties_paid,
+    ));
+
+    // close order account
+    let price = ctx.accounts.order.price;
+    let payment_mint = ctx.accounts.order.payment_mint;
+    Order::edit_order(
+        &mut ctx.accounts.order,
+        price,
+        payment_mint,
+        0,
+        clock.unix_timestamp,
+    );
+    ctx.accounts.market.remove_open_order();
+    ctx.accounts.order.state = OrderState::Closed.into();
+    emit_cpi!(Order::get_edit_event(
+        &mut ctx.accounts.order.clone(),
+        ctx.accounts.order.key(),
+        ctx.accounts.market.market_identifier,
+        OrderEditType::FillAndClose,
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{revoke, Revoke, TokenAccount, TokenInterface};
+
+use crate::{errors::MarketError, state::*};
+
+#[derive(Accounts)]
+#[instruction()]
+#[event_cpi]
+pub struct CancelBundle<'info> {
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = order.owner == initializer.key(),
+        constraint = order.market == market.key(),
+        constraint = Order::is_active(order.state),
+        constraint = order.side == <OrderSide as Into<u8>>::into(OrderSide::Sell),
+        constraint = order.nft_mint == bundle.key(),
+        seeds = [ORDER_SEED,
+        order.nonce.as_ref(),
+        order.market.as_ref(),
+        initializer.key().as_ref()],
+        bump,
+        close = initializer,
+    )]
+    pub order: Box<Account<'info, Order>>,
+    #[account(
+        mut,
+        seeds = [BUNDLE_SEED,
+        order.key().as_ref()],
+        bump,
+        close = initializer,
+    )]
+    pub bundle: Box<Account<'info, Bundle>>,
+    #[account(
+        mut,
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+    pub nft_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CancelBundle<'info> {
+    fn nft_revoke(&self, initializer_nft_ta: AccountInfo<'info>) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            self.nft_token_program.to_account_info(),
+            Revoke {
+                authority: self.initializer.to_account_info(),
+                source: initializer_nft_ta,
+            },
+        );
+        revoke(cpi_ctx)
+    }
+}
+
+/// cancel a bundle listing and close its accounts
+/// remaining accounts are the initializer nft ta of each item, in bundle order
+#[inline(always)]
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CancelBundle<'info>>) -> Result<()> {
+    msg!("Close bundle order account: {}", ctx.accounts.order.key());
+
+    let mints = ctx.accounts.bundle.get_mints();
+    if ctx.remaining_accounts.len() != mints.len() {
+        return Err(MarketError::WrongAccount.into());
+    }
+
+    for (initializer_nft_ta_info, mint) in ctx.remaining_accounts.iter().zip(mints) {
+        let initializer_nft_ta =
+            InterfaceAccount::<TokenAccount>::try_from(initializer_nft_ta_info)?;
+        if initializer_nft_ta.owner != ctx.accounts.initializer.key()
+            || initializer_nft_ta.mint != *mint
+        {
+            return Err(MarketError::WrongAccount.into());
+        }
+        // items moved or delegated elsewhere since listing are left alone
+        if initializer_nft_ta
+            .delegate
+            .contains(&ctx.accounts.order.key())
+        {
+            ctx.accounts
+                .nft_revoke(initializer_nft_ta_info.to_account_info())?;
+        }
+    }
+
+    let clock = Clock::get()?;
+    let price = ctx.accounts.order.price;
+    let payment_mint = ctx.accounts.order.payment_mint;
+    Order::edit_order(
+        &mut ctx.accounts.order,
+        price,
+        payment_mint,
+        0,
+        clock.unix_timestamp,
+    );
+    ctx.accounts.market.remove_open_order();
+    ctx.accounts.order.state = OrderState::Closed.into();
+    emit_cpi!(Order::get_edit_event(
+        &mut ctx.accounts.order.clone(),
+        ctx.accounts.order.key(),
+        ctx.accounts.market.market_identifier,
+        OrderEditType::Close,
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,594 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::spl_token_2022::instruction::transfer_checked as transfer_2022,
+    token_interface::{transfer_checked, Mint, TokenInterface, TransferChecked},
+};
+use spl_transfer_hook_interface::onchain::add_extra_accounts_for_execute_cpi;
+use wen_new_standard::{
+    cpi::{accounts::ApproveTransfer, approve_transfer},
+    utils::get_mint_metadata,
+    ROYALTY_BASIS_POINTS_FIELD,
+};
+
+use crate::{
+    errors::MarketError,
+    instructions::bundle::BundleFillData,
+    state::*,
+    utils::{
+        create_ata, get_amount_from_bp, get_bump_in_seed_form, get_editions_royalties,
+        get_has_royalty_field, get_verified_metadata, metaplex::pnft::utils::get_is_pnft,
+        token_extensions::WnsApprovalAccounts, transfer_sol,
+    },
+};
+
+#[derive(Accounts)]
+#[instruction()]
+#[event_cpi]
+pub struct FillBundle<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut, constraint = maker.key() == order.owner)]
+    /// CHECK: constraint check
+    pub maker: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = Market::is_active(market.state),
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(
+        mut,
+        constraint = Order::is_active(order.state),
+        constraint = order.market == market.key(),
+        constraint = order.side == <OrderSide as Into<u8>>::into(OrderSide::Sell),
+        constraint = order.nft_mint == bundle.key(),
+        seeds = [ORDER_SEED,
+        order.nonce.as_ref(),
+        order.market.as_ref(),
+        order.owner.as_ref()],
+        bump,
+        close = maker
+    )]
+    pub order: Box<Account<'info, Order>>,
+    #[account(
+        seeds = [ORDER_EPOCH_SEED,
+        order.owner.as_ref()],
+        bump,
+    )]
+    /// CHECK: seeds constraint, empty until the order owner first cancels all their orders
+    pub order_epoch: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [BUNDLE_SEED,
+        order.key().as_ref()],
+        bump,
+        close = maker
+    )]
+    pub bundle: Box<Account<'info, Bundle>>,
+    #[account(mut, constraint = fee_recipient.key() == market.fee_recipient.key())]
+    /// CHECK: constraint check
+    pub fee_recipient: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub fee_recipient_ta: Option<UncheckedAccount<'info>>,
+    /// payment token accounts, mint and program are left out for bundles in native SOL
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub seller_payment_ta: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub buyer_payment_ta: Option<UncheckedAccount<'info>>,
+    #[account(mut, constraint = payment_mint.key() == order.payment_mint)]
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+    pub nft_token_program: Interface<'info, TokenInterface>,
+    /// CHECK: checked by constraint and in cpi
+    pub nft_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> FillBundle<'info> {
+    /// pays the recipient from the taker, in lamports when the bundle is priced in native SOL
+    #[inline(never)]
+    fn transfer_payment(
+        &self,
+        recipient: AccountInfo<'info>,
+        recipient_ta: Option<AccountInfo<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        if self.order.is_native() {
+            return transfer_sol(
+                self.taker.to_account_info(),
+                recipient,
+                self.system_program.to_account_info(),
+                None,
+                amount,
+            );
+        }
+
+        let (
+            Some(buyer_payment_ta),
+            Some(recipient_ta),
+            Some(payment_mint),
+            Some(payment_token_program),
+        ) = (
+            &self.buyer_payment_ta,
+            recipient_ta,
+            &self.payment_mint,
+            &self.payment_token_program,
+        )
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        let cpi_ctx = CpiContext::new(
+            payment_token_program.to_account_info(),
+            TransferChecked {
+                from: buyer_payment_ta.to_account_info(),
+                to: recipient_ta,
+                authority: self.taker.to_account_info(),
+                mint: payment_mint.to_account_info(),
+            },
+        );
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)
+    }
+
+    /// native bundles pay wallets directly and need no token accounts
+    #[inline(never)]
+    fn create_payment_ata(
+        &self,
+        ata: Option<AccountInfo<'info>>,
+        owner: &AccountInfo<'info>,
+    ) -> Result<()> {
+        if self.order.is_native() {
+            return Ok(());
+        }
+
+        let (Some(ata), Some(payment_mint), Some(payment_token_program)) =
+            (ata, &self.payment_mint, &self.payment_token_program)
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        create_ata(
+            &ata,
+            &self.taker.to_account_info(),
+            &payment_mint.to_account_info(),
+            owner,
+            &self.system_program.to_account_info(),
+            &payment_token_program.to_account_info(),
+        )
+    }
+
+    // WNS Pre-Transfer Approval
+    #[inline(never)]
+    fn approve_wns_transfer(
+        &self,
+        nft_mint: AccountInfo<'info>,
+        item_value: u64,
+        wns_accounts: WnsApprovalAccounts<'info>,
+    ) -> Result<()> {
+        if wns_accounts.payment_mint.key() != self.order.payment_mint {
+            return Err(MarketError::WrongAccount.into());
+        }
+        self.create_payment_ata(
+            Some(wns_accounts.distribution_token_account.to_account_info()),
+            &wns_accounts.distribution_account,
+        )?;
+
+        let (distribution_token_account, authority_token_account, payment_token_program) =
+            if self.order.is_native() {
+                (None, None, None)
+            } else {
+                (
+                    Some(wns_accounts.distribution_token_account.to_account_info()),
+                    self.buyer_payment_ta
+                        .as_ref()
+                        .map(|buyer_payment_ta| buyer_payment_ta.to_account_info()),
+                    self.payment_token_program
+                        .as_ref()
+                        .map(|payment_token_program| payment_token_program.to_account_info()),
+                )
+            };
+
+        let cpi_accounts = ApproveTransfer {
+            payer: self.taker.to_account_info(),
+            authority: self.taker.to_account_info(),
+            mint: nft_mint,
+            approve_account: wns_accounts.approval_account.to_account_info(),
+            payment_mint: wns_accounts.payment_mint.to_account_info(),
+            distribution_token_account,
+            authority_token_account,
+            distribution_account: wns_accounts.distribution_account.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            distribution_program: wns_accounts.distribution_program.to_account_info(),
+            token_program: self.nft_token_program.to_account_info(),
+            payment_token_program,
+        };
+        let cpi_ctx = CpiContext::new(self.nft_program.to_account_info(), cpi_accounts);
+        approve_transfer(cpi_ctx, item_value)
+    }
+
+    /// the order is the delegate of every listed token account
+    /// any hook accounts are forwarded to the token program
+    #[inline(never)]
+    fn nft_transfer(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        nft_mint: &InterfaceAccount<'info, Mint>,
+        seller_nft_ta: AccountInfo<'info>,
+        buyer_nft_ta: AccountInfo<'info>,
+        hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let authority_info = self.order.to_account_info();
+
+        let mut transfer_ix = transfer_2022(
+            self.nft_token_program.key,
+            seller_nft_ta.key,
+            nft_mint.to_account_info().key,
+            buyer_nft_ta.key,
+            authority_info.key,
+            &[],
+            1,
+            nft_mint.decimals,
+        )?;
+
+        let mut account_infos = vec![
+            seller_nft_ta.clone(),
+            nft_mint.to_account_info(),
+            buyer_nft_ta.clone(),
+            authority_info.clone(),
+        ];
+
+        if let Some(hook_program) = hook_accounts.last() {
+            // transfer hook
+            add_extra_accounts_for_execute_cpi(
+                &mut transfer_ix,
+                &mut account_infos,
+                hook_program.key,
+                seller_nft_ta,
+                nft_mint.to_account_info(),
+                buyer_nft_ta,
+                authority_info,
+                1,
+                hook_accounts,
+            )?;
+        }
+
+        anchor_lang::solana_program::program::invoke_signed(
+            &transfer_ix,
+            &account_infos,
+            signer_seeds,
+        )?;
+
+        Ok(())
+    }
+}
+
+/// buy every mint of a bundle in one go
+/// remaining accounts per item are the mint, seller nft ta and buyer nft ta,
+/// then the royalty and transfer hook accounts fill_order takes for that mint
+/// royalties are paid per item on an even split of the bundle price
+#[inline(never)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FillBundle<'info>>,
+    data: BundleFillData,
+) -> Result<()> {
+    let nft_token_program_key = &ctx.accounts.nft_token_program.key.to_string().clone();
+    let nft_program_key = &ctx.accounts.nft_program.key.to_string().clone();
+
+    let mints = ctx.accounts.bundle.get_mints().to_vec();
+    let total_item_accounts = data
+        .item_accounts
+        .iter()
+        .map(|item_accounts| *item_accounts as usize)
+        .sum::<usize>();
+    if data.item_accounts.len() != mints.len()
+        || data
+            .item_accounts
+            .iter()
+            .any(|item_accounts| *item_accounts < 3)
+        || total_item_accounts != ctx.remaining_accounts.len()
+    {
+        return Err(MarketError::WrongAccount.into());
+    }
+
+    let clock = Clock::get()?;
+    if ctx
+        .accounts
+        .order
+        .is_stale(OrderEpoch::get_current(&ctx.accounts.order_epoch)?)
+    {
+        return Err(MarketError::StaleOrder.into());
+    }
+    if !ctx
+        .accounts
+        .market
+        .is_payment_mint_allowed(&ctx.accounts.order.payment_mint)
+    {
+        return Err(MarketError::PaymentMintNotAllowed.into());
+    }
+    let bump = &get_bump_in_seed_form(&ctx.bumps.order);
+    let signer_seeds: &[&[&[u8]]; 1] = &[&[
+        ORDER_SEED,
+        ctx.accounts.order.nonce.as_ref(),
+        ctx.accounts.order.market.as_ref(),
+        ctx.accounts.order.owner.as_ref(),
+        bump,
+    ][..]];
+
+    let bundle_value = ctx.accounts.order.price;
+    let fill_fees = if ctx.accounts.order.fees_on {
+        ctx.accounts.market.get_fill_fees(bundle_value, false)?
+    } else {
+        FillFees::default()
+    };
+    // maker rebates are paid out of the taker fee
+    let fee_amount = u64::try_from(
+        fill_fees
+            .maker_fee
+            .saturating_add_unsigned(fill_fees.taker_fee),
+    )
+    .map_err(|_| MarketError::AmountUnderflow)?;
+    // the taker pays their fee on top of the price
+    let mut seller_received_amount = bundle_value
+        .checked_add_signed(-fill_fees.maker_fee)
+        .ok_or(MarketError::AmountUnderflow)?;
+    let mut royalties_paid: u64 = 0;
+
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let nft_token_program = ctx.accounts.nft_token_program.to_account_info();
+    let taker = ctx.accounts.taker.to_account_info();
+
+    let buyer_payment_ta = ctx
+        .accounts
+        .buyer_payment_ta
+        .as_ref()
+        .map(|buyer_payment_ta| buyer_payment_ta.to_account_info());
+    ctx.accounts.create_payment_ata(buyer_payment_ta, &taker)?;
+
+    let maker = ctx.accounts.maker.to_account_info();
+    let seller_payment_ta = ctx
+        .accounts
+        .seller_payment_ta
+        .as_ref()
+        .map(|seller_payment_ta| seller_payment_ta.to_account_info());
+    ctx.accounts
+        .create_payment_ata(seller_payment_ta.clone(), &maker)?;
+
+    let fee_reciever = ctx.accounts.fee_recipient.to_account_info();
+    let fee_recipient_ta = ctx
+        .accounts
+        .fee_recipient_ta
+        .as_ref()
+        .map(|fee_recipient_ta| fee_recipient_ta.to_account_info());
+    ctx.accounts
+        .create_payment_ata(fee_recipient_ta.clone(), &fee_reciever)?;
+
+    // even split of the price, the last item takes the rounding
+    let item_count = mints.len() as u64;
+    let base_item_value = bundle_value / item_count;
+
+    let mut item_start = 0;
+    for (i, item_accounts) in data.item_accounts.iter().enumerate() {
+        let item_end = item_start + *item_accounts as usize;
+        let item = &ctx.remaining_accounts[item_start..item_end];
+        item_start = item_end;
+
+        let nft_mint = InterfaceAccount::<Mint>::try_from(&item[0])?;
+        if nft_mint.key() != mints[i] {
+            return Err(MarketError::InvalidNft.into());
+        }
+        let seller_nft_ta = item[1].to_account_info();
+        let buyer_nft_ta = item[2].to_account_info();
+        let extra_accounts = &item[3..];
+
+        let item_value = if i == mints.len() - 1 {
+            bundle_value - base_item_value * (item_count - 1)
+        } else {
+            base_item_value
+        };
+
+        create_ata(
+            &seller_nft_ta,
+            &taker,
+            &item[0],
+            &maker,
+            &system_program,
+            &nft_token_program,
+        )?;
+        create_ata(
+            &buyer_nft_ta,
+            &taker,
+            &item[0],
+            &taker,
+            &system_program,
+            &nft_token_program,
+        )?;
+
+        let mut hook_accounts = extra_accounts;
+        if *nft_token_program_key == TOKEN_PID && *nft_program_key == METAPLEX_PID {
+            // Extra Account 0 for the metadata, then a creator and creator payment ta per creator
+            let parsed_metadata = get_verified_metadata(
+                extra_accounts.first().ok_or(MarketError::WrongAccount)?,
+                &nft_mint.key(),
+            )?;
+            // legacy metaplex nfts move with a plain token transfer, pNFTs can't
+            if get_is_pnft(&parsed_metadata) {
+                return Err(MarketError::UnsupportedNft.into());
+            }
+            let royalties =
+                get_amount_from_bp(item_value, parsed_metadata.seller_fee_basis_points.into())?;
+
+            for (j, creator) in parsed_metadata.creators.iter().flatten().enumerate() {
+                let creator_account = extra_accounts
+                    .get(1 + 2 * j)
+                    .ok_or(MarketError::WrongAccount)?;
+                let creator_payment_ta = extra_accounts
+                    .get(2 + 2 * j)
+                    .ok_or(MarketError::WrongAccount)?;
+                if *creator_account.key != creator.address {
+                    return Err(MarketError::WrongAccount.into());
+                }
+
+                let creator_amount =
+                    get_amount_from_bp(royalties, u128::from(creator.share) * 100)?;
+                if creator_amount == 0 {
+                    continue;
+                }
+                ctx.accounts.create_payment_ata(
+                    Some(creator_payment_ta.to_account_info()),
+                    creator_account,
+                )?;
+                ctx.accounts.transfer_payment(
+                    creator_account.to_account_info(),
+                    Some(creator_payment_ta.to_account_info()),
+                    creator_amount,
+                )?;
+                royalties_paid = royalties_paid
+                    .checked_add(creator_amount)
+                    .ok_or(MarketError::AmountOverflow)?;
+            }
+            hook_accounts = &[];
+        } else if *nft_token_program_key == TOKEN_EXT_PID {
+            if *nft_program_key == WNS_PID {
+                // Extra Accounts 0-4 for approval
+                if extra_accounts.len() < 5 {
+                    return Err(MarketError::WrongAccount.into());
+                }
+                let wns_accounts = WnsApprovalAccounts {
+                    approval_account: extra_accounts[0].to_account_info(),
+                    distribution_account: extra_accounts[1].to_account_info(),
+                    distribution_token_account: extra_accounts[2].to_account_info(),
+                    distribution_program: extra_accounts[3].to_account_info(),
+                    payment_mint: extra_accounts[4].to_account_info(),
+                };
+                hook_accounts = &extra_accounts[5..];
+
+                let mint_metadata = get_mint_metadata(&mut item[0].to_account_info())?;
+                let royalty_basis_points = mint_metadata
+                    .additional_metadata
+                    .iter()
+                    .find(|(key, _)| key == ROYALTY_BASIS_POINTS_FIELD)
+                    .map(|(_, value)| value)
+                    .map(|value| u64::from_str(value).unwrap())
+                    .unwrap_or(0);
+                let royalties = get_amount_from_bp(item_value, royalty_basis_points.into())?;
+                royalties_paid = royalties_paid
+                    .checked_add(royalties)
+                    .ok_or(MarketError::AmountOverflow)?;
+
+                // Handles royalties
+                ctx.accounts.approve_wns_transfer(
+                    item[0].to_account_info(),
+                    item_value,
+                    wns_accounts,
+                )?;
+            } else if *nft_program_key == EDITIONS_PID {
+                // Extra Account 0 for the editions deployment, then a creator and creator payment ta per creator
+                let editions_royalties = get_editions_royalties(
+                    &mut item[0].to_account_info(),
+                    extra_accounts.first().ok_or(MarketError::WrongAccount)?,
+                )?;
+                let royalties =
+                    get_amount_from_bp(item_value, editions_royalties.royalty_basis_points.into())?;
+
+                for (j, creator) in editions_royalties.creators.iter().enumerate() {
+                    let creator_account = extra_accounts
+                        .get(1 + 2 * j)
+                        .ok_or(MarketError::WrongAccount)?;
+                    let creator_payment_ta = extra_accounts
+                        .get(2 + 2 * j)
+                        .ok_or(MarketError::WrongAccount)?;
+                    if *creator_account.key != creator.address {
+                        return Err(MarketError::WrongAccount.into());
+                    }
+
+                    let creator_amount =
+                        get_amount_from_bp(royalties, u128::from(creator.share) * 100)?;
+                    if creator_amount == 0 {
+                        continue;
+                    }
+                    ctx.accounts.create_payment_ata(
+                        Some(creator_payment_ta.to_account_info()),
+                        creator_account,
+                    )?;
+                    ctx.accounts.transfer_payment(
+                        creator_account.to_account_info(),
+                        Some(creator_payment_ta.to_account_info()),
+                        creator_amount,
+                    )?;
+                    royalties_paid = royalties_paid
+                        .checked_add(creator_amount)
+                        .ok_or(MarketError::AmountOverflow)?;
+                }
+                hook_accounts = extra_accounts
+                    .get(1 + 2 * editions_royalties.creators.len()..)
+                    .ok_or(MarketError::WrongAccount)?;
+            } else if get_has_royalty_field(&mut item[0].to_account_info()) {
+                // mints carrying royalties must go through their royalty path
+                return Err(MarketError::UnsupportedNft.into());
+            }
+        } else {
+            return Err(MarketError::UnsupportedNft.into());
+        }
+
+        ctx.accounts.nft_transfer(
+            signer_seeds,
+            &nft_mint,
+            seller_nft_ta,
+            buyer_nft_ta,
+            hook_accounts,
+        )?;
+    }
+
+    // royalties were paid by the taker on the seller's behalf
+    seller_received_amount = seller_received_amount
+        .checked_sub(royalties_paid)
+        .ok_or(MarketError::AmountUnderflow)?;
+
+    // Transfer payment
+    ctx.accounts
+        .transfer_payment(maker, seller_payment_ta, seller_received_amount)?;
+    ctx.accounts
+        .transfer_payment(fee_reciever, fee_recipient_ta, fee_amount)?;
+
+    emit_cpi!(ctx.accounts.order.get_fill_event(
+        ctx.accounts.order.key(),
+        ctx.accounts.market.market_identifier,
+        ctx.accounts.taker.key(),
+        ctx.accounts.bundle.key(),
+        1,
+        bundle_value,
+        bundle_value,
+        &fill_fees,
+        royalties_paid,
+    ));
+
+    // close order account
+    let price = ctx.accounts.order.price;
+    let payment_mint = ctx.accounts.order.payment_mint;
+    Order::edit_order(
+        &mut ctx.accounts.order,
+        price,
+        payment_mint,
+        0,
+        clock.unix_timestamp,
+    );
+    ctx.accounts.market.remove_open_order();
+    ctx.accounts.order.state = OrderState::Closed.into();
+    emit_cpi!(Order::get_edit_event(
+        &mut ctx.accounts.order.clone(),
+        ctx.accounts.order.key(),
+        ctx.accounts.market.market_identifier,
+        OrderEditType::FillAndClose,
+    ));
+
+    Ok(())
+}
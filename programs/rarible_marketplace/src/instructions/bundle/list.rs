@@ -0,0 +1,181 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_interface::{approve, Approve, Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    errors::MarketError,
+    instructions::bundle::BundleListData,
+    state::*,
+    utils::{verify_mint_verification, verify_wns_mint},
+};
+
+#[derive(Accounts)]
+#[instruction(data: BundleListData)]
+#[event_cpi]
+pub struct ListBundle<'info> {
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = Market::is_active(market.state),
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(
+        constraint = data.price > 0,
+        init,
+        seeds = [ORDER_SEED,
+        data.nonce.as_ref(),
+        market.key().as_ref(),
+        initializer.key().as_ref()],
+        bump,
+        payer = initializer,
+        space = 8 + std::mem::size_of::<Order>()
+    )]
+    pub order: Box<Account<'info, Order>>,
+    #[account(
+        seeds = [ORDER_EPOCH_SEED,
+        initializer.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: seeds constraint, empty until the initializer first cancels all their orders
+    pub order_epoch: UncheckedAccount<'info>,
+    #[account(
+        init,
+        seeds = [BUNDLE_SEED,
+        order.key().as_ref()],
+        bump,
+        payer = initializer,
+        space = 8 + std::mem::size_of::<Bundle>()
+    )]
+    pub bundle: Box<Account<'info, Bundle>>,
+    pub system_program: Program<'info, System>,
+    pub nft_token_program: Interface<'info, TokenInterface>,
+    /// CHECK: checked by constraint and in cpi
+    pub nft_program: UncheckedAccount<'info>,
+}
+
+impl<'info> ListBundle<'info> {
+    fn nft_delegate(&self, initializer_nft_ta: AccountInfo<'info>) -> Result<()> {
+        let delegate_cpi = CpiContext::new(
+            self.nft_token_program.to_account_info(),
+            Approve {
+                to: initializer_nft_ta,
+                authority: self.initializer.to_account_info(),
+                delegate: self.order.to_account_info(),
+            },
+        );
+
+        approve(delegate_cpi, 1)
+    }
+}
+
+/// list several mints of the market for one total price
+/// remaining accounts per item are the mint, the initializer nft ata and,
+/// for metaplex and wns mints, the mint verification or wns group member account
+#[inline(always)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ListBundle<'info>>,
+    data: BundleListData,
+) -> Result<()> {
+    msg!(
+        "Initialize a new bundle order: {}",
+        ctx.accounts.order.key()
+    );
+
+    if !ctx
+        .accounts
+        .market
+        .is_payment_mint_allowed(&data.payment_mint)
+    {
+        return Err(MarketError::PaymentMintNotAllowed.into());
+    }
+
+    let nft_token_program_key = &ctx.accounts.nft_token_program.key.to_string().clone();
+    let nft_program_key = &ctx.accounts.nft_program.key.to_string().clone();
+
+    let needs_membership_account = if *nft_token_program_key == TOKEN_PID {
+        if *nft_program_key != METAPLEX_PID {
+            return Err(MarketError::UnsupportedNft.into());
+        }
+        true
+    } else if *nft_token_program_key == TOKEN_EXT_PID {
+        *nft_program_key == WNS_PID
+    } else {
+        return Err(MarketError::UnsupportedNft.into());
+    };
+    let item_accounts_len = if needs_membership_account { 3 } else { 2 };
+
+    let items = ctx.remaining_accounts.chunks_exact(item_accounts_len);
+    if items.len() < 2 || items.len() > MAX_BUNDLE_SIZE || !items.remainder().is_empty() {
+        return Err(MarketError::WrongAccount.into());
+    }
+
+    let mut mints: Vec<Pubkey> = Vec::with_capacity(items.len());
+    for item in items {
+        let nft_mint = InterfaceAccount::<Mint>::try_from(&item[0])?;
+        let initializer_nft_ta = InterfaceAccount::<TokenAccount>::try_from(&item[1])?;
+        // fill_bundle sells out of the associated token account of the initializer
+        if *item[0].owner != ctx.accounts.nft_token_program.key()
+            || initializer_nft_ta.mint != nft_mint.key()
+            || initializer_nft_ta.owner != ctx.accounts.initializer.key()
+            || *item[1].key
+                != get_associated_token_address_with_program_id(
+                    ctx.accounts.initializer.key,
+                    &nft_mint.key(),
+                    ctx.accounts.nft_token_program.key,
+                )
+            || mints.contains(&nft_mint.key())
+        {
+            return Err(MarketError::WrongAccount.into());
+        }
+
+        if *nft_program_key == METAPLEX_PID {
+            verify_mint_verification(&item[2], nft_mint.key(), ctx.accounts.market.key())?;
+        } else if *nft_program_key == WNS_PID {
+            verify_wns_mint(
+                item[0].to_account_info(),
+                item[2].to_account_info(),
+                ctx.accounts.market.market_identifier,
+            )?;
+        }
+
+        ctx.accounts.nft_delegate(item[1].to_account_info())?;
+        mints.push(nft_mint.key());
+    }
+
+    let clock = Clock::get()?;
+    // the order sells the bundle as a single unit
+    Order::init(
+        &mut ctx.accounts.order,
+        ctx.accounts.market.key(),
+        ctx.accounts.initializer.key(),
+        data.nonce,
+        ctx.accounts.bundle.key(),
+        data.payment_mint,
+        clock.unix_timestamp,
+        OrderSide::Sell.into(),
+        1,
+        data.price,
+        OrderState::Ready.into(),
+        true,
+        0,
+    );
+    ctx.accounts.order.epoch = OrderEpoch::get_current(&ctx.accounts.order_epoch)?;
+    ctx.accounts.market.add_open_order();
+    let order_key = ctx.accounts.order.key();
+    ctx.accounts.bundle.init(order_key, &mints);
+
+    emit_cpi!(Order::get_edit_event(
+        &mut ctx.accounts.order.clone(),
+        ctx.accounts.order.key(),
+        ctx.accounts.market.market_identifier,
+        OrderEditType::Init,
+    ));
+
+    Ok(())
+}
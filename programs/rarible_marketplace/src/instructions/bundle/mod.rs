@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct BundleListData {
+    pub nonce: Pubkey,
+    pub payment_mint: Pubkey,
+    /// total price of the bundle
+    pub price: u64,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct BundleFillData {
+    /// number of remaining accounts each item uses, in bundle order
+    pub item_accounts: Vec<u8>,
+}
+
+pub mod cancel;
+pub mod fill;
+pub mod list;
+
+pub use cancel::*;
+pub use fill::*;
+pub use list::*;
@@ -2,9 +2,10 @@ use anchor_lang::prelude::*;
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone)]
 pub struct CompressedOrderData {
-    pub order_nonce: Pubkey,
-    pub mint_id: Pubkey,
+    pub nonce: Pubkey,
+    pub payment_mint: Pubkey,
     pub price: u64,
+    pub expiry: Option<i64>,
     pub root: [u8; 32],
     pub data_hash: [u8; 32],
     pub creator_hash: [u8; 32],
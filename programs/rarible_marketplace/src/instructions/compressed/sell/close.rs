@@ -1,7 +1,18 @@
 use anchor_lang::prelude::*;
-use mpl_bubblegum::instructions::{TransferCpiAccounts, TransferInstructionArgs};
+use mpl_bubblegum::{
+    instructions::{TransferCpiAccounts, TransferInstructionArgs},
+    utils::get_asset_id,
+};
 
-use crate::{instructions::compressed_draft::CompressedOrderData, state::*};
+use crate::{
+    errors::MarketError,
+    instructions::compressed::CompressedFillOrderData,
+    state::*,
+    utils::{
+        get_bump_in_seed_form,
+        metaplex::bubblegum::{compressed_transfer, get_proof_accounts},
+    },
+};
 
 #[derive(Accounts)]
 #[instruction()]
@@ -23,20 +34,14 @@ pub struct CompressedCloseSellOrder<'info> {
     )]
     pub order: Box<Account<'info, Order>>,
     #[account(
-        constraint = Market::is_active(market.state),
+        mut,
         seeds = [MARKET_SEED,
         market.market_identifier.as_ref()],
         bump,
     )]
     pub market: Box<Account<'info, Market>>,
-    #[account(
-        mut,
-        seeds = [WALLET_SEED,
-        order.owner.as_ref()],
-        bump,
-    )]
-    pub wallet: Box<Account<'info, Wallet>>,
     /// CHECK: checked in cpi
+    #[account(mut)]
     pub tree_config: UncheckedAccount<'info>,
     /// CHECK: checked in cpi
     #[account(mut)]
@@ -45,7 +50,8 @@ pub struct CompressedCloseSellOrder<'info> {
     pub log_wrapper: UncheckedAccount<'info>,
     /// CHECK: checked in cpi
     pub compression_program: UncheckedAccount<'info>,
-    /// CHECK: checked in cpi
+    /// CHECK: checked by address
+    #[account(address = mpl_bubblegum::ID)]
     pub mpl_bubblegum: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
@@ -53,42 +59,44 @@ pub struct CompressedCloseSellOrder<'info> {
 impl<'info> CompressedCloseSellOrder<'info> {
     pub fn transfer_compressed_nft(
         &self,
-        ra: Vec<AccountInfo<'info>>,
+        ra: &[AccountInfo<'info>],
         signer_seeds: &[&[&[u8]]],
         root: [u8; 32],
         data_hash: [u8; 32],
         creator_hash: [u8; 32],
         index: u32,
     ) -> Result<()> {
+        let tree_config = self.tree_config.to_account_info();
+        let leaf_owner = self.order.to_account_info();
+        let new_leaf_owner = self.initializer.to_account_info();
+        let merkle_tree = self.merkle_tree.to_account_info();
+        let log_wrapper = self.log_wrapper.to_account_info();
+        let compression_program = self.compression_program.to_account_info();
+        let system_program = self.system_program.to_account_info();
+
         let cpi_accounts = TransferCpiAccounts {
-            tree_config: &self.tree_config.to_account_info(),
-            leaf_owner: (&self.initializer.to_account_info(), true),
-            leaf_delegate: (&self.initializer.to_account_info(), false),
-            new_leaf_owner: &self.wallet.to_account_info(),
-            merkle_tree: &self.merkle_tree.to_account_info(),
-            log_wrapper: &self.log_wrapper.to_account_info(),
-            compression_program: &self.compression_program.to_account_info(),
-            system_program: &self.system_program.to_account_info(),
+            tree_config: &tree_config,
+            leaf_owner: (&leaf_owner, true),
+            leaf_delegate: (&leaf_owner, false),
+            new_leaf_owner: &new_leaf_owner,
+            merkle_tree: &merkle_tree,
+            log_wrapper: &log_wrapper,
+            compression_program: &compression_program,
+            system_program: &system_program,
         };
 
         let args = TransferInstructionArgs {
             root,
             data_hash,
             creator_hash,
-            index,
             nonce: index as u64,
+            index,
         };
 
-        let transformed: Vec<(&AccountInfo, bool, bool)> = ra
-            .iter()
-            .map(|account| (account, account.is_signer, account.is_writable))
-            .collect();
-
-        let transformed_slice: &[(&AccountInfo, bool, bool)] = &transformed;
         compressed_transfer(
             self.mpl_bubblegum.to_account_info(),
             cpi_accounts,
-            transformed_slice,
+            &get_proof_accounts(ra),
             signer_seeds,
             args,
         )
@@ -98,16 +106,26 @@ impl<'info> CompressedCloseSellOrder<'info> {
 #[inline(always)]
 pub fn handler<'info>(
     ctx: Context<'_, '_, '_, 'info, CompressedCloseSellOrder<'info>>,
-    data: CompressedOrderData,
+    data: CompressedFillOrderData,
 ) -> Result<()> {
-    msg!("Close sell order account: {}", ctx.accounts.order.key());
+    msg!("Close compressed sell order account: {}", ctx.accounts.order.key());
 
-    let bump = &get_bump_in_seed_form(&ctx.bumps.wallet);
+    let asset_id = get_asset_id(&ctx.accounts.merkle_tree.key(), data.index as u64);
+    if asset_id != ctx.accounts.order.nft_mint {
+        return Err(MarketError::InvalidNft.into());
+    }
 
-    let signer_seeds = &[&[WALLET_SEED, ctx.accounts.order.owner.as_ref(), bump][..]];
+    let bump = &get_bump_in_seed_form(&ctx.bumps.order);
+    let signer_seeds: &[&[&[u8]]; 1] = &[&[
+        ORDER_SEED,
+        ctx.accounts.order.nonce.as_ref(),
+        ctx.accounts.order.market.as_ref(),
+        ctx.accounts.order.owner.as_ref(),
+        bump,
+    ][..]];
 
     ctx.accounts.transfer_compressed_nft(
-        ctx.remaining_accounts.to_vec(),
+        ctx.remaining_accounts,
         signer_seeds,
         data.root,
         data.data_hash,
@@ -115,6 +133,7 @@ pub fn handler<'info>(
         data.index,
     )?;
 
+    ctx.accounts.market.remove_open_order();
     ctx.accounts.order.state = OrderState::Closed.into();
 
     emit_cpi!(Order::get_edit_event(
@@ -0,0 +1,277 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use mpl_bubblegum::{
+    instructions::{TransferCpiAccounts, TransferInstructionArgs},
+    utils::get_asset_id,
+};
+
+use crate::{
+    errors::MarketError,
+    instructions::compressed::CompressedFillOrderData,
+    state::*,
+    utils::{
+        create_ata, get_bump_in_seed_form,
+        metaplex::bubblegum::{compressed_transfer, get_proof_accounts},
+    },
+};
+
+#[derive(Accounts)]
+#[instruction()]
+#[event_cpi]
+pub struct CompressedFillSellOrder<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut, constraint = maker.key() == order.owner)]
+    /// CHECK: constraint check
+    pub maker: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = Market::is_active(market.state),
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(
+        mut,
+        constraint = Order::is_active(order.state),
+        constraint = order.market == market.key(),
+        constraint = order.side == <OrderSide as Into<u8>>::into(OrderSide::Sell),
+        seeds = [ORDER_SEED,
+        order.nonce.as_ref(),
+        order.market.as_ref(),
+        order.owner.as_ref()],
+        bump,
+        close = maker
+    )]
+    pub order: Box<Account<'info, Order>>,
+    #[account(
+        seeds = [ORDER_EPOCH_SEED,
+        order.owner.as_ref()],
+        bump,
+    )]
+    /// CHECK: seeds constraint, empty until the order owner first cancels all their orders
+    pub order_epoch: UncheckedAccount<'info>,
+    /// CHECK: checked in cpi
+    #[account(mut)]
+    pub tree_config: UncheckedAccount<'info>,
+    /// CHECK: checked in cpi
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: checked in cpi
+    pub log_wrapper: UncheckedAccount<'info>,
+    /// CHECK: checked in cpi
+    pub compression_program: UncheckedAccount<'info>,
+    /// CHECK: checked by address
+    #[account(address = mpl_bubblegum::ID)]
+    pub mpl_bubblegum: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = taker,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub buyer_payment_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub seller_payment_ta: UncheckedAccount<'info>,
+    #[account(mut, constraint = fee_recipient.key() == market.fee_recipient.key())]
+    /// CHECK: constraint check
+    pub fee_recipient: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub fee_recipient_ta: UncheckedAccount<'info>,
+    #[account(constraint = payment_mint.key() == order.payment_mint)]
+    pub payment_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub payment_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CompressedFillSellOrder<'info> {
+    pub fn transfer_compressed_nft(
+        &self,
+        ra: &[AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        index: u32,
+    ) -> Result<()> {
+        let tree_config = self.tree_config.to_account_info();
+        let leaf_owner = self.order.to_account_info();
+        let new_leaf_owner = self.taker.to_account_info();
+        let merkle_tree = self.merkle_tree.to_account_info();
+        let log_wrapper = self.log_wrapper.to_account_info();
+        let compression_program = self.compression_program.to_account_info();
+        let system_program = self.system_program.to_account_info();
+
+        let cpi_accounts = TransferCpiAccounts {
+            tree_config: &tree_config,
+            leaf_owner: (&leaf_owner, true),
+            leaf_delegate: (&leaf_owner, false),
+            new_leaf_owner: &new_leaf_owner,
+            merkle_tree: &merkle_tree,
+            log_wrapper: &log_wrapper,
+            compression_program: &compression_program,
+            system_program: &system_program,
+        };
+
+        let args = TransferInstructionArgs {
+            root,
+            data_hash,
+            creator_hash,
+            nonce: index as u64,
+            index,
+        };
+
+        compressed_transfer(
+            self.mpl_bubblegum.to_account_info(),
+            cpi_accounts,
+            &get_proof_accounts(ra),
+            signer_seeds,
+            args,
+        )
+    }
+
+    fn transfer_payment(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            self.payment_token_program.to_account_info(),
+            TransferChecked {
+                from: self.buyer_payment_ta.to_account_info(),
+                to,
+                authority: self.taker.to_account_info(),
+                mint: self.payment_mint.to_account_info(),
+            },
+        );
+        transfer_checked(cpi_ctx, amount, self.payment_mint.decimals)
+    }
+}
+
+/// taker is buying the compressed nft held by the order account
+#[inline(always)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CompressedFillSellOrder<'info>>,
+    data: CompressedFillOrderData,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    if ctx.accounts.order.is_expired(clock.unix_timestamp) {
+        return Err(MarketError::OrderExpired.into());
+    }
+    if ctx
+        .accounts
+        .order
+        .is_stale(OrderEpoch::get_current(&ctx.accounts.order_epoch)?)
+    {
+        return Err(MarketError::StaleOrder.into());
+    }
+    if !ctx
+        .accounts
+        .market
+        .is_payment_mint_allowed(&ctx.accounts.order.payment_mint)
+    {
+        return Err(MarketError::PaymentMintNotAllowed.into());
+    }
+
+    let asset_id = get_asset_id(&ctx.accounts.merkle_tree.key(), data.index as u64);
+    if asset_id != ctx.accounts.order.nft_mint {
+        return Err(MarketError::InvalidNft.into());
+    }
+
+    let bump = &get_bump_in_seed_form(&ctx.bumps.order);
+    let signer_seeds: &[&[&[u8]]; 1] = &[&[
+        ORDER_SEED,
+        ctx.accounts.order.nonce.as_ref(),
+        ctx.accounts.order.market.as_ref(),
+        ctx.accounts.order.owner.as_ref(),
+        bump,
+    ][..]];
+
+    ctx.accounts.transfer_compressed_nft(
+        ctx.remaining_accounts,
+        signer_seeds,
+        data.root,
+        data.data_hash,
+        data.creator_hash,
+        data.index,
+    )?;
+
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let payment_token_program = ctx.accounts.payment_token_program.to_account_info();
+    create_ata(
+        &ctx.accounts.seller_payment_ta.to_account_info(),
+        &ctx.accounts.taker.to_account_info(),
+        &ctx.accounts.payment_mint.to_account_info(),
+        &ctx.accounts.maker.to_account_info(),
+        &system_program,
+        &payment_token_program,
+    )?;
+    create_ata(
+        &ctx.accounts.fee_recipient_ta.to_account_info(),
+        &ctx.accounts.taker.to_account_info(),
+        &ctx.accounts.payment_mint.to_account_info(),
+        &ctx.accounts.fee_recipient.to_account_info(),
+        &system_program,
+        &payment_token_program,
+    )?;
+
+    let price = ctx.accounts.order.price;
+    let fill_fees = if ctx.accounts.order.fees_on {
+        ctx.accounts.market.get_fill_fees(price, false)?
+    } else {
+        FillFees::default()
+    };
+    // maker rebates are paid out of the taker fee
+    let fee_amount = u64::try_from(
+        fill_fees
+            .maker_fee
+            .saturating_add_unsigned(fill_fees.taker_fee),
+    )
+    .map_err(|_| MarketError::AmountUnderflow)?;
+    // the taker pays their fee on top of the price
+    let seller_received_amount = price
+        .checked_add_signed(-fill_fees.maker_fee)
+        .ok_or(MarketError::AmountUnderflow)?;
+
+    ctx.accounts.transfer_payment(
+        ctx.accounts.seller_payment_ta.to_account_info(),
+        seller_received_amount,
+    )?;
+    ctx.accounts
+        .transfer_payment(ctx.accounts.fee_recipient_ta.to_account_info(), fee_amount)?;
+
+    emit_cpi!(ctx.accounts.order.get_fill_event(
+        ctx.accounts.order.key(),
+        ctx.accounts.market.market_identifier,
+        ctx.accounts.taker.key(),
+        ctx.accounts.order.nft_mint,
+        1,
+        price,
+        price,
+        &fill_fees,
+        0,
+    ));
+
+    // close order account
+    msg!("Close compressed sell order account: {}", ctx.accounts.order.key());
+    Order::edit_order(
+        &mut ctx.accounts.order,
+        price,
+        ctx.accounts.payment_mint.key(),
+        0,
+        clock.unix_timestamp,
+    );
+    ctx.accounts.market.remove_open_order();
+    ctx.accounts.order.state = OrderState::Closed.into();
+    emit_cpi!(Order::get_edit_event(
+        &mut ctx.accounts.order.clone(),
+        ctx.accounts.order.key(),
+        ctx.accounts.market.market_identifier,
+        OrderEditType::FillAndClose,
+    ));
+
+    Ok(())
+}
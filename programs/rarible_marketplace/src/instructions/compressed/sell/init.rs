@@ -1,8 +1,18 @@
 use anchor_lang::prelude::*;
+use mpl_bubblegum::{
+    instructions::{TransferCpiAccounts, TransferInstructionArgs},
+    utils::get_asset_id,
+};
 
-use mpl_bubblegum::instructions::{TransferCpiAccounts, TransferInstructionArgs};
-
-use crate::{instructions::compressed_draft::CompressedOrderData, state::*};
+use crate::{
+    errors::MarketError,
+    instructions::compressed::CompressedOrderData,
+    state::*,
+    utils::{
+        metaplex::bubblegum::{compressed_transfer, get_proof_accounts},
+        verify_mint_verification,
+    },
+};
 
 #[derive(Accounts)]
 #[instruction(data: CompressedOrderData)]
@@ -12,12 +22,6 @@ pub struct CompressedInitSellOrder<'info> {
     pub initializer: Signer<'info>,
     #[account(
         mut,
-        seeds = [WALLET_SEED,
-        initializer.key().as_ref()],
-        bump,
-    )]
-    pub wallet: Box<Account<'info, Wallet>>,
-    #[account(
         constraint = Market::is_active(market.state),
         seeds = [MARKET_SEED,
         market.market_identifier.as_ref()],
@@ -28,7 +32,7 @@ pub struct CompressedInitSellOrder<'info> {
         constraint = data.price > 0,
         init,
         seeds = [ORDER_SEED,
-        data.order_nonce.as_ref(),
+        data.nonce.as_ref(),
         market.key().as_ref(),
         initializer.key().as_ref()],
         bump,
@@ -37,12 +41,18 @@ pub struct CompressedInitSellOrder<'info> {
     )]
     pub order: Box<Account<'info, Order>>,
     #[account(
-        seeds = [APPRAISAL_SEED, market.market_identifier.as_ref(), data.mint_id.as_ref()],
+        seeds = [ORDER_EPOCH_SEED,
+        initializer.key().as_ref()],
         bump,
-        seeds::program = vault::ID,
     )]
-    pub appraisal: Box<Account<'info, Appraisal>>,
+    /// CHECK: seeds constraint, empty until the initializer first cancels all their orders
+    pub order_epoch: UncheckedAccount<'info>,
+    /// CHECK: checked in verify_mint_verification
+    pub verification: UncheckedAccount<'info>,
     /// CHECK: checked in cpi
+    pub leaf_delegate: UncheckedAccount<'info>,
+    /// CHECK: checked in cpi
+    #[account(mut)]
     pub tree_config: UncheckedAccount<'info>,
     /// CHECK: checked in cpi
     #[account(mut)]
@@ -51,7 +61,8 @@ pub struct CompressedInitSellOrder<'info> {
     pub log_wrapper: UncheckedAccount<'info>,
     /// CHECK: checked in cpi
     pub compression_program: UncheckedAccount<'info>,
-    /// CHECK: checked in cpi
+    /// CHECK: checked by address
+    #[account(address = mpl_bubblegum::ID)]
     pub mpl_bubblegum: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
@@ -59,78 +70,97 @@ pub struct CompressedInitSellOrder<'info> {
 impl<'info> CompressedInitSellOrder<'info> {
     pub fn transfer_compressed_nft(
         &self,
-        ra: Vec<AccountInfo<'info>>,
+        ra: &[AccountInfo<'info>],
         root: [u8; 32],
         data_hash: [u8; 32],
         creator_hash: [u8; 32],
-        nonce: u64,
         index: u32,
     ) -> Result<()> {
+        let tree_config = self.tree_config.to_account_info();
+        let leaf_owner = self.initializer.to_account_info();
+        let leaf_delegate = self.leaf_delegate.to_account_info();
+        let new_leaf_owner = self.order.to_account_info();
+        let merkle_tree = self.merkle_tree.to_account_info();
+        let log_wrapper = self.log_wrapper.to_account_info();
+        let compression_program = self.compression_program.to_account_info();
+        let system_program = self.system_program.to_account_info();
+
         let cpi_accounts = TransferCpiAccounts {
-            tree_config: &self.tree_config.to_account_info(),
-            leaf_owner: (&self.initializer.to_account_info(), true),
-            leaf_delegate: (&self.initializer.to_account_info(), false),
-            new_leaf_owner: &self.wallet.to_account_info(),
-            merkle_tree: &self.merkle_tree.to_account_info(),
-            log_wrapper: &self.log_wrapper.to_account_info(),
-            compression_program: &self.compression_program.to_account_info(),
-            system_program: &self.system_program.to_account_info(),
+            tree_config: &tree_config,
+            leaf_owner: (&leaf_owner, true),
+            leaf_delegate: (&leaf_delegate, false),
+            new_leaf_owner: &new_leaf_owner,
+            merkle_tree: &merkle_tree,
+            log_wrapper: &log_wrapper,
+            compression_program: &compression_program,
+            system_program: &system_program,
         };
 
         let args = TransferInstructionArgs {
             root,
             data_hash,
             creator_hash,
-            nonce,
+            nonce: index as u64,
             index,
         };
 
-        let transformed: Vec<(&AccountInfo, bool, bool)> = ra
-            .iter()
-            .map(|account| (account, account.is_signer, account.is_writable))
-            .collect();
-
-        let transformed_slice: &[(&AccountInfo, bool, bool)] = &transformed;
         compressed_transfer(
             self.mpl_bubblegum.to_account_info(),
             cpi_accounts,
-            transformed_slice,
+            &get_proof_accounts(ra),
             &[],
             args,
         )
     }
 }
 
+/// compressed nft is transferred to the order account until the order is filled or closed
 #[inline(always)]
 pub fn handler<'info>(
     ctx: Context<'_, '_, '_, 'info, CompressedInitSellOrder<'info>>,
     data: CompressedOrderData,
 ) -> Result<()> {
-    msg!("Initialize a new sell order: {}", ctx.accounts.order.key());
+    msg!("Initialize a new compressed sell order: {}", ctx.accounts.order.key());
+
+    let asset_id = get_asset_id(&ctx.accounts.merkle_tree.key(), data.index as u64);
+    verify_mint_verification(
+        &ctx.accounts.verification,
+        asset_id,
+        ctx.accounts.market.key(),
+    )?;
 
     let clock = Clock::get()?;
+    let expiry = data.expiry.unwrap_or(0);
+    if expiry != 0 && expiry <= clock.unix_timestamp {
+        return Err(MarketError::OrderExpired.into());
+    }
+    if !ctx.accounts.market.is_payment_mint_allowed(&data.payment_mint) {
+        return Err(MarketError::PaymentMintNotAllowed.into());
+    }
     // create a new order with size 1
     Order::init(
         &mut ctx.accounts.order,
         ctx.accounts.market.key(),
         ctx.accounts.initializer.key(),
-        ctx.accounts.wallet.key(),
-        data.order_nonce,
-        data.mint_id,
+        data.nonce,
+        asset_id,
+        data.payment_mint,
         clock.unix_timestamp,
         OrderSide::Sell.into(),
         1, // always 1
         data.price,
         OrderState::Ready.into(),
         true,
+        expiry,
     );
+    ctx.accounts.order.epoch = OrderEpoch::get_current(&ctx.accounts.order_epoch)?;
+    ctx.accounts.market.add_open_order();
 
     ctx.accounts.transfer_compressed_nft(
-        ctx.remaining_accounts.to_vec(),
+        ctx.remaining_accounts,
         data.root,
         data.data_hash,
         data.creator_hash,
-        data.index as u64,
         data.index,
     )?;
 
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    state::*,
+    utils::metaplex::core_asset::{get_verified_core_asset, revoke_core_asset, DelegateCoreAsset},
+};
+
+#[derive(Accounts)]
+#[instruction()]
+#[event_cpi]
+pub struct CancelCoreListing<'info> {
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = order.owner == initializer.key(),
+        constraint = order.market == market.key(),
+        constraint = Order::is_active(order.state),
+        seeds = [ORDER_SEED,
+        order.nonce.as_ref(),
+        order.market.as_ref(),
+        initializer.key().as_ref()],
+        bump,
+        close = initializer,
+    )]
+    pub order: Box<Account<'info, Order>>,
+    #[account(
+        mut,
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(mut, address = order.nft_mint)]
+    /// CHECK: checked by address and in cpi
+    pub asset: UncheckedAccount<'info>,
+    #[account(mut, address = market.market_identifier)]
+    /// CHECK: checked by address
+    pub collection: UncheckedAccount<'info>,
+    /// CHECK: checked by address
+    #[account(address = mpl_core::ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CancelCoreListing<'info> {
+    fn core_asset_revoke(&self) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            self.core_program.to_account_info(),
+            DelegateCoreAsset {
+                asset: self.asset.to_account_info(),
+                collection: self.collection.to_account_info(),
+                delegate: self.order.to_account_info(),
+                authority: self.initializer.to_account_info(),
+                payer: self.initializer.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            },
+        );
+        revoke_core_asset(cpi_ctx)
+    }
+}
+
+#[inline(never)]
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CancelCoreListing<'info>>) -> Result<()> {
+    msg!(
+        "Close core asset sell order account: {}",
+        ctx.accounts.order.key()
+    );
+
+    // core already dropped the delegate if the asset was transferred elsewhere
+    let asset =
+        get_verified_core_asset(&ctx.accounts.asset, ctx.accounts.market.market_identifier)?;
+    if asset.owner == ctx.accounts.initializer.key() {
+        ctx.accounts.core_asset_revoke()?;
+    }
+
+    ctx.accounts.market.remove_open_order();
+    ctx.accounts.order.state = OrderState::Closed.into();
+
+    emit_cpi!(Order::get_edit_event(
+        &mut ctx.accounts.order.clone(),
+        ctx.accounts.order.key(),
+        ctx.accounts.market.market_identifier,
+        OrderEditType::Close,
+    ));
+    Ok(())
+}
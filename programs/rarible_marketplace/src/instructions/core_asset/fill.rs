@@ -0,0 +1,397 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    errors::MarketError,
+    state::*,
+    utils::{
+        create_ata, get_bump_in_seed_form, lamport_transfer,
+        metaplex::core_asset::{get_verified_core_asset, transfer_core_asset, TransferCoreAsset},
+        transfer_sol, verify_merkle_mint,
+    },
+};
+
+#[derive(Accounts)]
+#[instruction()]
+#[event_cpi]
+pub struct FillCoreOrder<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut, constraint = maker.key() == order.owner)]
+    /// CHECK: constraint check
+    pub maker: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = Market::is_active(market.state),
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(
+        mut,
+        constraint = Order::is_active(order.state),
+        constraint = order.market == market.key(),
+        constraint = !order.wallet_backed,
+        seeds = [ORDER_SEED,
+        order.nonce.as_ref(),
+        order.market.as_ref(),
+        order.owner.as_ref()],
+        bump,
+    )]
+    pub order: Box<Account<'info, Order>>,
+    #[account(
+        seeds = [ORDER_EPOCH_SEED,
+        order.owner.as_ref()],
+        bump,
+    )]
+    /// CHECK: seeds constraint, empty until the order owner first cancels all their orders
+    pub order_epoch: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: checked against the order and in get_verified_core_asset
+    pub asset: UncheckedAccount<'info>,
+    #[account(mut, address = market.market_identifier)]
+    /// CHECK: checked by address
+    pub collection: UncheckedAccount<'info>,
+    /// CHECK: checked by address
+    #[account(address = mpl_core::ID)]
+    pub core_program: UncheckedAccount<'info>,
+    /// payment accounts are left out for orders in native SOL
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub seller_payment_ta: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub buyer_payment_ta: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function, only needed for maker rebates on bids
+    pub maker_payment_ta: Option<UncheckedAccount<'info>>,
+    #[account(mut, constraint = fee_recipient.key() == market.fee_recipient.key())]
+    /// CHECK: constraint check
+    pub fee_recipient: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub fee_recipient_ta: Option<UncheckedAccount<'info>>,
+    #[account(constraint = payment_mint.key() == order.payment_mint)]
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FillCoreOrder<'info> {
+    fn core_asset_transfer(&self, signer_seeds: &[&[&[u8]]], is_buy: bool) -> Result<()> {
+        // the order is the transfer delegate of a listed asset
+        let (authority, new_owner) = if is_buy {
+            (self.taker.to_account_info(), self.maker.to_account_info())
+        } else {
+            (self.order.to_account_info(), self.taker.to_account_info())
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.core_program.to_account_info(),
+            TransferCoreAsset {
+                asset: self.asset.to_account_info(),
+                collection: self.collection.to_account_info(),
+                authority,
+                payer: self.taker.to_account_info(),
+                new_owner,
+                system_program: self.system_program.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_core_asset(cpi_ctx)
+    }
+
+    /// pays the recipient from the buyer, in lamports when the order is priced in native SOL
+    fn transfer_payment(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        is_buy: bool,
+        recipient: AccountInfo<'info>,
+        recipient_ta: Option<AccountInfo<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        if self.order.is_native() {
+            // the order account holds the escrowed lamports of a bid
+            return if is_buy {
+                lamport_transfer(self.order.to_account_info(), recipient, amount)
+            } else {
+                transfer_sol(
+                    self.taker.to_account_info(),
+                    recipient,
+                    self.system_program.to_account_info(),
+                    None,
+                    amount,
+                )
+            };
+        }
+
+        let (
+            Some(buyer_payment_ta),
+            Some(recipient_ta),
+            Some(payment_mint),
+            Some(payment_token_program),
+        ) = (
+            &self.buyer_payment_ta,
+            recipient_ta,
+            &self.payment_mint,
+            &self.payment_token_program,
+        )
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        let authority = if is_buy {
+            self.order.to_account_info()
+        } else {
+            self.taker.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            payment_token_program.to_account_info(),
+            TransferChecked {
+                from: buyer_payment_ta.to_account_info(),
+                to: recipient_ta,
+                authority,
+                mint: payment_mint.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)
+    }
+
+    /// native orders pay wallets directly and need no token accounts
+    fn create_payment_ata(
+        &self,
+        ata: Option<AccountInfo<'info>>,
+        owner: &AccountInfo<'info>,
+    ) -> Result<()> {
+        if self.order.is_native() {
+            return Ok(());
+        }
+
+        let (Some(ata), Some(payment_mint), Some(payment_token_program)) =
+            (ata, &self.payment_mint, &self.payment_token_program)
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        create_ata(
+            &ata,
+            &self.taker.to_account_info(),
+            &payment_mint.to_account_info(),
+            owner,
+            &self.system_program.to_account_info(),
+            &payment_token_program.to_account_info(),
+        )
+    }
+}
+
+/// fills a core asset listing or bid, one asset at a time
+/// core bids are made with bid, on a single asset or on any asset of the collection
+#[inline(never)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, FillCoreOrder<'info>>,
+    merkle_proof: Option<Vec<[u8; 32]>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    if ctx.accounts.order.is_expired(clock.unix_timestamp) {
+        return Err(MarketError::OrderExpired.into());
+    }
+    if ctx
+        .accounts
+        .order
+        .is_stale(OrderEpoch::get_current(&ctx.accounts.order_epoch)?)
+    {
+        return Err(MarketError::StaleOrder.into());
+    }
+    if ctx.accounts.order.is_private() && ctx.accounts.order.taker != ctx.accounts.taker.key() {
+        return Err(MarketError::InvalidTaker.into());
+    }
+    if !ctx
+        .accounts
+        .market
+        .is_payment_mint_allowed(&ctx.accounts.order.payment_mint)
+    {
+        return Err(MarketError::PaymentMintNotAllowed.into());
+    }
+
+    let is_buy = ctx.accounts.order.side == <OrderSide as Into<u8>>::into(OrderSide::Buy);
+
+    // collection bids take any asset of the collection, checked below
+    let asset_key = ctx.accounts.asset.key();
+    if !ctx.accounts.order.is_collection_bid() && asset_key != ctx.accounts.order.nft_mint {
+        return Err(MarketError::InvalidNft.into());
+    }
+    // trait bids narrow the collection down to the assets of their merkle root
+    if ctx.accounts.order.is_trait_bid() {
+        verify_merkle_mint(ctx.accounts.order.merkle_root, merkle_proof, asset_key)?;
+    }
+
+    let asset =
+        get_verified_core_asset(&ctx.accounts.asset, ctx.accounts.market.market_identifier)?;
+    let seller = if is_buy {
+        ctx.accounts.taker.key()
+    } else {
+        ctx.accounts.maker.key()
+    };
+    if asset.owner != seller {
+        return Err(MarketError::WrongAccount.into());
+    }
+
+    let bump = &get_bump_in_seed_form(&ctx.bumps.order);
+    let signer_seeds: &[&[&[u8]]; 1] = &[&[
+        ORDER_SEED,
+        ctx.accounts.order.nonce.as_ref(),
+        ctx.accounts.order.market.as_ref(),
+        ctx.accounts.order.owner.as_ref(),
+        bump,
+    ][..]];
+
+    ctx.accounts.core_asset_transfer(signer_seeds, is_buy)?;
+
+    let payment_funder = if is_buy {
+        ctx.accounts.order.to_account_info()
+    } else {
+        ctx.accounts.taker.to_account_info()
+    };
+    let buyer_payment_ta = ctx
+        .accounts
+        .buyer_payment_ta
+        .as_ref()
+        .map(|buyer_payment_ta| buyer_payment_ta.to_account_info());
+    ctx.accounts
+        .create_payment_ata(buyer_payment_ta, &payment_funder)?;
+
+    let payment_receiver = if is_buy {
+        ctx.accounts.taker.to_account_info()
+    } else {
+        ctx.accounts.maker.to_account_info()
+    };
+    let seller_payment_ta = ctx
+        .accounts
+        .seller_payment_ta
+        .as_ref()
+        .map(|seller_payment_ta| seller_payment_ta.to_account_info());
+    ctx.accounts
+        .create_payment_ata(seller_payment_ta.clone(), &payment_receiver)?;
+
+    let fee_reciever = ctx.accounts.fee_recipient.to_account_info();
+    let fee_recipient_ta = ctx
+        .accounts
+        .fee_recipient_ta
+        .as_ref()
+        .map(|fee_recipient_ta| fee_recipient_ta.to_account_info());
+    ctx.accounts
+        .create_payment_ata(fee_recipient_ta.clone(), &fee_reciever)?;
+
+    let price = ctx.accounts.order.price;
+    // a bid maker pays their fee out of the escrow on top of the price
+    let fill_fees = if is_buy {
+        ctx.accounts
+            .order
+            .get_bid_fill_fees(&ctx.accounts.market, 1)?
+    } else if ctx.accounts.order.fees_on {
+        ctx.accounts.market.get_fill_fees(price, is_buy)?
+    } else {
+        FillFees::default()
+    };
+    // maker rebates are paid out of the taker fee
+    let fee_amount = u64::try_from(
+        fill_fees
+            .maker_fee
+            .saturating_add_unsigned(fill_fees.taker_fee),
+    )
+    .map_err(|_| MarketError::AmountUnderflow)?;
+    // a listing taker pays their fee on top of the price,
+    // a bid taker pays theirs out of the proceeds and a maker rebate comes out of the escrow
+    let (seller_received_amount, maker_rebate) = if is_buy {
+        (
+            price.checked_sub(fill_fees.taker_fee),
+            fill_fees.maker_fee.min(0).unsigned_abs(),
+        )
+    } else {
+        (price.checked_add_signed(-fill_fees.maker_fee), 0)
+    };
+    let seller_received_amount = seller_received_amount.ok_or(MarketError::AmountUnderflow)?;
+
+    ctx.accounts.transfer_payment(
+        signer_seeds,
+        is_buy,
+        payment_receiver,
+        seller_payment_ta,
+        seller_received_amount,
+    )?;
+    ctx.accounts.transfer_payment(
+        signer_seeds,
+        is_buy,
+        fee_reciever,
+        fee_recipient_ta,
+        fee_amount,
+    )?;
+    if maker_rebate > 0 {
+        let maker = ctx.accounts.maker.to_account_info();
+        let maker_payment_ta = ctx
+            .accounts
+            .maker_payment_ta
+            .as_ref()
+            .map(|maker_payment_ta| maker_payment_ta.to_account_info());
+        ctx.accounts
+            .create_payment_ata(maker_payment_ta.clone(), &maker)?;
+        ctx.accounts.transfer_payment(
+            signer_seeds,
+            is_buy,
+            maker,
+            maker_payment_ta,
+            maker_rebate,
+        )?;
+    }
+
+    emit_cpi!(ctx.accounts.order.get_fill_event(
+        ctx.accounts.order.key(),
+        ctx.accounts.market.market_identifier,
+        ctx.accounts.taker.key(),
+        asset_key,
+        1,
+        price,
+        price,
+        &fill_fees,
+        0,
+    ));
+
+    // close order account once the last unit is filled
+    let new_size = ctx.accounts.order.size - 1;
+    let payment_mint = ctx.accounts.order.payment_mint;
+    Order::edit_order(
+        &mut ctx.accounts.order,
+        price,
+        payment_mint,
+        new_size,
+        clock.unix_timestamp,
+    );
+    if new_size == 0 {
+        ctx.accounts.market.remove_open_order();
+        ctx.accounts.order.state = OrderState::Closed.into();
+        emit_cpi!(Order::get_edit_event(
+            &mut ctx.accounts.order.clone(),
+            ctx.accounts.order.key(),
+            ctx.accounts.market.market_identifier,
+            OrderEditType::FillAndClose,
+        ));
+        ctx.accounts
+            .order
+            .close(ctx.accounts.maker.to_account_info())?;
+    } else {
+        emit_cpi!(Order::get_edit_event(
+            &mut ctx.accounts.order.clone(),
+            ctx.accounts.order.key(),
+            ctx.accounts.market.market_identifier,
+            OrderEditType::Fill,
+        ));
+    }
+
+    Ok(())
+}
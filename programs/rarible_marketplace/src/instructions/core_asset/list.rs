@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::MarketError,
+    state::*,
+    utils::metaplex::core_asset::{
+        delegate_core_asset, get_verified_core_asset, DelegateCoreAsset,
+    },
+};
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct CoreListData {
+    pub nonce: Pubkey,
+    /// default pubkey for a listing in native SOL
+    pub payment_mint: Pubkey,
+    pub price: u64,
+    pub expiry: Option<i64>,
+}
+
+#[derive(Accounts)]
+#[instruction(data: CoreListData)]
+#[event_cpi]
+pub struct ListCoreAsset<'info> {
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = Market::is_active(market.state),
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(
+        constraint = data.price > 0,
+        init,
+        seeds = [ORDER_SEED,
+        data.nonce.as_ref(),
+        market.key().as_ref(),
+        initializer.key().as_ref()],
+        bump,
+        payer = initializer,
+        space = 8 + std::mem::size_of::<Order>()
+    )]
+    pub order: Box<Account<'info, Order>>,
+    #[account(
+        seeds = [ORDER_EPOCH_SEED,
+        initializer.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: seeds constraint, empty until the initializer first cancels all their orders
+    pub order_epoch: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: checked in get_verified_core_asset and in cpi
+    pub asset: UncheckedAccount<'info>,
+    #[account(mut, address = market.market_identifier)]
+    /// CHECK: checked by address
+    pub collection: UncheckedAccount<'info>,
+    /// CHECK: checked by address
+    #[account(address = mpl_core::ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ListCoreAsset<'info> {
+    fn core_asset_delegate(&self) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            self.core_program.to_account_info(),
+            DelegateCoreAsset {
+                asset: self.asset.to_account_info(),
+                collection: self.collection.to_account_info(),
+                delegate: self.order.to_account_info(),
+                authority: self.initializer.to_account_info(),
+                payer: self.initializer.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            },
+        );
+        delegate_core_asset(cpi_ctx)
+    }
+}
+
+/// core asset stays in the owner's wallet with the order as its transfer delegate
+#[inline(never)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ListCoreAsset<'info>>,
+    data: CoreListData,
+) -> Result<()> {
+    msg!(
+        "Initialize a new core asset sell order: {}",
+        ctx.accounts.order.key()
+    );
+
+    let asset =
+        get_verified_core_asset(&ctx.accounts.asset, ctx.accounts.market.market_identifier)?;
+    if asset.owner != ctx.accounts.initializer.key() {
+        return Err(MarketError::WrongAccount.into());
+    }
+
+    if !ctx
+        .accounts
+        .market
+        .is_payment_mint_allowed(&data.payment_mint)
+    {
+        return Err(MarketError::PaymentMintNotAllowed.into());
+    }
+
+    let clock = Clock::get()?;
+    let expiry = data.expiry.unwrap_or(0);
+    if expiry != 0 && expiry <= clock.unix_timestamp {
+        return Err(MarketError::OrderExpired.into());
+    }
+    // create a new order with size 1
+    Order::init(
+        &mut ctx.accounts.order,
+        ctx.accounts.market.key(),
+        ctx.accounts.initializer.key(),
+        data.nonce,
+        ctx.accounts.asset.key(),
+        data.payment_mint,
+        clock.unix_timestamp,
+        OrderSide::Sell.into(),
+        1, // always 1
+        data.price,
+        OrderState::Ready.into(),
+        true,
+        expiry,
+    );
+    ctx.accounts.order.epoch = OrderEpoch::get_current(&ctx.accounts.order_epoch)?;
+    ctx.accounts.market.add_open_order();
+
+    ctx.accounts.core_asset_delegate()?;
+
+    emit_cpi!(Order::get_edit_event(
+        &mut ctx.accounts.order.clone(),
+        ctx.accounts.order.key(),
+        ctx.accounts.market.market_identifier,
+        OrderEditType::Init,
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,7 @@
+pub mod cancel_list;
+pub mod fill;
+pub mod list;
+
+pub use cancel_list::*;
+pub use fill::*;
+pub use list::*;
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::MarketError, state::*};
+
+#[derive(Accounts)]
+#[instruction()]
+#[event_cpi]
+pub struct CloseMarket<'info> {
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = market.initializer == initializer.key(),
+        constraint = !Market::is_active(market.state),
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+        close = initializer,
+    )]
+    pub market: Box<Account<'info, Market>>,
+}
+
+/// market must be set to closed before the account can be closed
+/// every order must be closed first, their cancel instructions need the market account
+/// markets created before orders were counted cannot be closed
+#[inline(always)]
+pub fn handler(ctx: Context<CloseMarket>) -> Result<()> {
+    msg!("Close market account: {}", ctx.accounts.market.key());
+
+    if !ctx.accounts.market.counts_open_orders() {
+        return Err(MarketError::MarketOrdersUncounted.into());
+    }
+    if ctx.accounts.market.open_orders != 0 {
+        return Err(MarketError::MarketHasOpenOrders.into());
+    }
+
+    emit_cpi!(Market::get_edit_event(
+        &mut ctx.accounts.market.clone(),
+        ctx.accounts.market.key(),
+        MarketEditType::Close
+    ));
+    Ok(())
+}
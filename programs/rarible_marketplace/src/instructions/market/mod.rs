@@ -1,5 +1,9 @@
+pub mod close;
 pub mod init;
+pub mod update;
 pub mod verify_mint;
 
+pub use close::*;
 pub use init::*;
+pub use update::*;
 pub use verify_mint::*;
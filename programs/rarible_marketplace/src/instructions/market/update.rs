@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::MarketError, state::*};
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct UpdateMarketParams {
+    pub fee_recipient: Option<Pubkey>,
+    pub fee_bps: Option<u64>,
+    pub maker_fee_bps: Option<i64>,
+    pub taker_fee_bps: Option<u64>,
+    pub state: Option<MarketState>,
+    /// limits orders to the allowlisted payment mints
+    pub payment_allowlist_on: Option<bool>,
+    pub native_allowed: Option<bool>,
+    /// replaces the allowlisted payment mints, native SOL is set by native_allowed
+    pub payment_mints: Option<Vec<Pubkey>>,
+    /// share of the bid surplus paid to keepers matching orders
+    pub keeper_tip_bps: Option<u64>,
+}
+
+#[derive(Accounts)]
+#[instruction()]
+#[event_cpi]
+pub struct UpdateMarket<'info> {
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = market.initializer == initializer.key(),
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+}
+
+#[inline(always)]
+pub fn handler(ctx: Context<UpdateMarket>, params: UpdateMarketParams) -> Result<()> {
+    msg!("Updating market: {}", ctx.accounts.market.key());
+
+    if let Some(fee_bps) = params.fee_bps {
+        if fee_bps > MAX_FEE_BPS {
+            return Err(MarketError::InvalidFeeBps.into());
+        }
+    }
+    if let Some(keeper_tip_bps) = params.keeper_tip_bps {
+        if keeper_tip_bps > MAX_FEE_BPS {
+            return Err(MarketError::InvalidFeeBps.into());
+        }
+        ctx.accounts.market.keeper_tip_bps = keeper_tip_bps;
+    }
+
+    let maker_fee_bps = params
+        .maker_fee_bps
+        .unwrap_or(ctx.accounts.market.maker_fee_bps);
+    let taker_fee_bps = params
+        .taker_fee_bps
+        .unwrap_or(ctx.accounts.market.taker_fee_bps);
+    if !Market::is_valid_fee_split(maker_fee_bps, taker_fee_bps) {
+        return Err(MarketError::InvalidFeeBps.into());
+    }
+    if let Some(payment_mints) = &params.payment_mints {
+        if payment_mints.len() > MAX_PAYMENT_MINTS || payment_mints.contains(&Pubkey::default()) {
+            return Err(MarketError::InvalidPaymentMints.into());
+        }
+    }
+
+    Market::edit(
+        &mut ctx.accounts.market,
+        params.fee_recipient,
+        params.fee_bps,
+        params.maker_fee_bps,
+        params.taker_fee_bps,
+        params.state.map(|state| state.into()),
+    );
+    Market::edit_payment_allowlist(
+        &mut ctx.accounts.market,
+        params.payment_allowlist_on,
+        params.native_allowed,
+        params.payment_mints.as_deref(),
+    );
+
+    emit_cpi!(Market::get_edit_event(
+        &mut ctx.accounts.market.clone(),
+        ctx.accounts.market.key(),
+        MarketEditType::Edit
+    ));
+    Ok(())
+}
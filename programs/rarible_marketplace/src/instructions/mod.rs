@@ -1,5 +1,19 @@
+pub mod auction;
+pub mod bundle;
+pub mod compressed;
+pub mod core_asset;
 pub mod market;
 pub mod order;
+pub mod signed;
+pub mod swap;
+pub mod wallet;
 
+pub use auction::*;
+pub use bundle::*;
+pub use compressed::*;
+pub use core_asset::*;
 pub use market::*;
 pub use order::*;
+pub use signed::*;
+pub use swap::*;
+pub use wallet::*;
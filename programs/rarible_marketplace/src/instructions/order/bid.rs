@@ -2,13 +2,18 @@ use anchor_lang::prelude::*;
 use anchor_spl::{associated_token::AssociatedToken, token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked}};
 
 
-use crate::state::*;
+use crate::{errors::MarketError, state::*, utils::transfer_sol};
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone)]
 pub struct BidData {
     pub nonce: Pubkey,
     pub price: u64,
     pub size: u64,
+    pub expiry: Option<i64>,
+    /// root of the eligible mints for a trait bid, nft_mint is left out
+    pub merkle_root: Option<[u8; 32]>,
+    /// wallet backed bids escrow nothing and are paid from the wallet account of the initializer when filled
+    pub wallet_backed: Option<bool>,
 }
 
 #[derive(Accounts)]
@@ -18,6 +23,7 @@ pub struct BidNft<'info> {
     #[account(mut)]
     pub initializer: Signer<'info>,
     #[account(
+        mut,
         constraint = Market::is_active(market.state),
         seeds = [MARKET_SEED,
         market.market_identifier.as_ref()],
@@ -36,13 +42,21 @@ pub struct BidNft<'info> {
         space = 8 + std::mem::size_of::<Order>()
     )]
     pub order: Box<Account<'info, Order>>,
+    #[account(
+        seeds = [ORDER_EPOCH_SEED,
+        initializer.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: seeds constraint, empty until the initializer first cancels all their orders
+    pub order_epoch: UncheckedAccount<'info>,
+    /// payment accounts are left out for bids in native SOL
     #[account(
         mut,
         associated_token::mint = payment_mint,
         associated_token::authority = initializer,
         associated_token::token_program = payment_token_program,
     )]
-    pub initializer_payment_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub initializer_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     #[account(
         init_if_needed,
         payer = initializer,
@@ -50,28 +64,54 @@ pub struct BidNft<'info> {
         associated_token::authority = order,
         associated_token::token_program = payment_token_program,
     )]
-    pub order_payment_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub order_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     #[account(mut)]
-    pub payment_mint: Box<InterfaceAccount<'info, Mint>>,
-    pub payment_token_program: Interface<'info, TokenInterface>,
-    /// CHECK: can be anything
-    pub nft_mint: UncheckedAccount<'info>,
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+    /// CHECK: can be anything, left out for collection bids
+    pub nft_mint: Option<UncheckedAccount<'info>>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 impl<'info> BidNft<'info> {
     fn transfer_payment(&self, amount: u64) -> Result<()> {
+        // native bids escrow lamports in the order account
+        if self.payment_mint.is_none() {
+            return transfer_sol(
+                self.initializer.to_account_info(),
+                self.order.to_account_info(),
+                self.system_program.to_account_info(),
+                None,
+                amount,
+            );
+        }
+
+        let (
+            Some(initializer_payment_ta),
+            Some(order_payment_ta),
+            Some(payment_mint),
+            Some(payment_token_program),
+        ) = (
+            &self.initializer_payment_ta,
+            &self.order_payment_ta,
+            &self.payment_mint,
+            &self.payment_token_program,
+        )
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
         let cpi_ctx = CpiContext::new(
-            self.payment_token_program.to_account_info(),
+            payment_token_program.to_account_info(),
             TransferChecked {
-                from: self.initializer_payment_ta.to_account_info(),
-                to: self.order_payment_ta.to_account_info(),
+                from: initializer_payment_ta.to_account_info(),
+                to: order_payment_ta.to_account_info(),
                 authority: self.initializer.to_account_info(),
-                mint: self.payment_mint.to_account_info(),
+                mint: payment_mint.to_account_info(),
             }
         );
-        transfer_checked(cpi_ctx, amount, self.payment_mint.decimals)
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)
     }
 }
 
@@ -80,25 +120,55 @@ pub fn handler(ctx: Context<BidNft>, data: BidData) -> Result<()> {
     msg!("Initialize a new buy order: {}", ctx.accounts.order.key());
 
     let clock = Clock::get()?;
-    let bid_value = data.size.checked_mul(data.price).unwrap();
-
-    // Transfer bid funds TODO;
-    ctx.accounts.transfer_payment(bid_value)?;
+    let expiry = data.expiry.unwrap_or(0);
+    if expiry != 0 && expiry <= clock.unix_timestamp {
+        return Err(MarketError::OrderExpired.into());
+    }
+    let payment_mint = ctx
+        .accounts
+        .payment_mint
+        .as_ref()
+        .map(|payment_mint| payment_mint.key())
+        .unwrap_or_default();
+    if !ctx.accounts.market.is_payment_mint_allowed(&payment_mint) {
+        return Err(MarketError::PaymentMintNotAllowed.into());
+    }
+    if data.merkle_root.is_some() && ctx.accounts.nft_mint.is_some() {
+        return Err(MarketError::WrongAccount.into());
+    }
+    let wallet_backed = data.wallet_backed.unwrap_or(false);
     // create a new order with size 1
     Order::init(
         &mut ctx.accounts.order,
         ctx.accounts.market.key(),
         ctx.accounts.initializer.key(),
         data.nonce,
-        ctx.accounts.nft_mint.key(),
-        ctx.accounts.payment_mint.key(),
+        ctx.accounts
+            .nft_mint
+            .as_ref()
+            .map(|nft_mint| nft_mint.key())
+            .unwrap_or_default(),
+        payment_mint,
         clock.unix_timestamp,
         OrderSide::Buy.into(),
         data.size,
         data.price,
         OrderState::Ready.into(),
         true,
+        expiry,
     );
+    ctx.accounts.order.epoch = OrderEpoch::get_current(&ctx.accounts.order_epoch)?;
+    ctx.accounts.market.add_open_order();
+    ctx.accounts.order.merkle_root = data.merkle_root.unwrap_or_default();
+    ctx.accounts.order.wallet_backed = wallet_backed;
+    ctx.accounts.order.maker_fee_bps = ctx.accounts.market.get_bid_maker_fee_bps();
+
+    // the escrow covers the maker fee on top of the price,
+    // wallet backed bids are paid from the wallet balance on fill
+    let bid_escrow = ctx.accounts.order.get_bid_escrow(data.size)?;
+    if !wallet_backed {
+        ctx.accounts.transfer_payment(bid_escrow)?;
+    }
 
     emit_cpi!(Order::get_edit_event(
         &mut ctx.accounts.order.clone(),
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction()]
+#[event_cpi]
+pub struct CancelAllOrders<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        init_if_needed,
+        seeds = [ORDER_EPOCH_SEED,
+        owner.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + std::mem::size_of::<OrderEpoch>()
+    )]
+    pub order_epoch: Box<Account<'info, OrderEpoch>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// move the owner to the next order epoch, every order and signed listing they created before can no longer be filled
+/// escrows and rent of the cancelled orders are reclaimed with close_stale_orders
+#[inline(always)]
+pub fn handler(ctx: Context<CancelAllOrders>) -> Result<()> {
+    msg!("Cancel all orders of: {}", ctx.accounts.owner.key());
+
+    if ctx.accounts.order_epoch.version == 0 {
+        let owner = ctx.accounts.owner.key();
+        ctx.accounts.order_epoch.init(owner);
+    }
+    ctx.accounts.order_epoch.epoch += 1;
+
+    emit_cpi!(ctx
+        .accounts
+        .order_epoch
+        .get_edit_event(ctx.accounts.order_epoch.key()));
+
+    Ok(())
+}
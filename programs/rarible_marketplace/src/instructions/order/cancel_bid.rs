@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{associated_token::AssociatedToken, token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked}};
 
-use crate::{state::*, utils::get_bump_in_seed_form};
+use crate::{errors::MarketError, state::*, utils::get_bump_in_seed_form};
 
 #[derive(Accounts)]
 #[instruction()]
@@ -13,6 +13,7 @@ pub struct CancelBid<'info> {
         mut,
         constraint = order.owner == initializer.key(),
         constraint = Order::is_active(order.state),
+        constraint = order.side == <OrderSide as Into<u8>>::into(OrderSide::Buy),
         seeds = [ORDER_SEED,
         order.nonce.as_ref(),
         order.market.as_ref(),
@@ -22,19 +23,21 @@ pub struct CancelBid<'info> {
     )]
     pub order: Box<Account<'info, Order>>,
     #[account(
+        mut,
         constraint = market.key() == order.market,
         seeds = [MARKET_SEED,
         market.market_identifier.as_ref()],
         bump,
     )]
     pub market: Box<Account<'info, Market>>,
+    /// payment accounts are left out for bids in native SOL
     #[account(
         mut,
         associated_token::mint = payment_mint,
         associated_token::authority = initializer,
         associated_token::token_program = payment_token_program,
     )]
-    pub initializer_payment_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub initializer_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     #[account(
         init_if_needed,
         payer = initializer,
@@ -42,42 +45,60 @@ pub struct CancelBid<'info> {
         associated_token::authority = order,
         associated_token::token_program = payment_token_program,
     )]
-    pub order_payment_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub order_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     #[account(mut, constraint = payment_mint.key() == order.payment_mint)]
-    pub payment_mint: Box<InterfaceAccount<'info, Mint>>,
-    pub payment_token_program: Interface<'info, TokenInterface>,
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 impl<'info> CancelBid<'info> {
     fn transfer_payment(&self, signer_seeds: &[&[&[u8]]], amount: u64) -> Result<()> {
-        msg!("{:?} -- {:?}", amount,  self.order_payment_ta.key().to_string());
+        let (
+            Some(initializer_payment_ta),
+            Some(order_payment_ta),
+            Some(payment_mint),
+            Some(payment_token_program),
+        ) = (
+            &self.initializer_payment_ta,
+            &self.order_payment_ta,
+            &self.payment_mint,
+            &self.payment_token_program,
+        )
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        msg!("{:?} -- {:?}", amount,  order_payment_ta.key().to_string());
         let cpi_ctx = CpiContext::new_with_signer(
-            self.payment_token_program.to_account_info(),
+            payment_token_program.to_account_info(),
             TransferChecked {
-                from: self.order_payment_ta.to_account_info(),
-                to: self.initializer_payment_ta.to_account_info(),
+                from: order_payment_ta.to_account_info(),
+                to: initializer_payment_ta.to_account_info(),
                 authority: self.order.to_account_info(),
-                mint: self.payment_mint.to_account_info(),
+                mint: payment_mint.to_account_info(),
             },
             signer_seeds
         );
-        transfer_checked(cpi_ctx, amount, self.payment_mint.decimals)
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)
     }
 }
 
 #[inline(always)]
 pub fn handler(ctx: Context<CancelBid>) -> Result<()> {
     msg!("Close buy order account: {}", ctx.accounts.order.key());
+    ctx.accounts.market.remove_open_order();
     ctx.accounts.order.state = OrderState::Closed.into();
     let bump = &get_bump_in_seed_form(&ctx.bumps.order);
 
     let signer_seeds: &[&[&[u8]]; 1] = &[&[ORDER_SEED, ctx.accounts.order.nonce.as_ref(), ctx.accounts.order.market.as_ref(), ctx.accounts.order.owner.as_ref(), bump][..]];
 
-    let bid_value = ctx.accounts.order.size.checked_mul(ctx.accounts.order.price).unwrap();
-    // TODO Transfer funds out
-    ctx.accounts.transfer_payment(signer_seeds, bid_value)?;
+    let bid_escrow = ctx.accounts.order.get_bid_escrow(ctx.accounts.order.size)?;
+    // native escrow is refunded when the order account is closed, wallet backed bids hold no escrow
+    if !ctx.accounts.order.is_native() && !ctx.accounts.order.wallet_backed {
+        ctx.accounts.transfer_payment(signer_seeds, bid_escrow)?;
+    }
     emit_cpi!(Order::get_edit_event(
         &mut ctx.accounts.order.clone(),
         ctx.accounts.order.key(),
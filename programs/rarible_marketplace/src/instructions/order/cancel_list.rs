@@ -1,17 +1,19 @@
 use anchor_lang::prelude::*;
 use anchor_lang::{solana_program::sysvar, Key};
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token_interface::{revoke, Mint, Revoke, TokenAccount, TokenInterface};
-use mpl_token_metadata::accounts::Metadata;
+use anchor_spl::token_interface::{
+    close_account, revoke, CloseAccount, Mint, Revoke, TokenAccount, TokenInterface,
+};
+use mpl_token_metadata::types::RevokeArgs;
 
 use crate::errors::MarketError;
 use crate::state::*;
-use crate::utils::metaplex::pnft::utils::get_is_pnft;
-use crate::utils::mplx_transfer::{
-    transfer_metaplex_nft, ExtraTransferParams, MetaplexAdditionalTransferAccounts,
-    TransferMetaplexNft,
+use crate::utils::metaplex::pnft::delegate::{revoke_pnft, DelegateMetaplexNft};
+use crate::utils::metaplex::pnft::utils::{get_is_pnft, PnftParams};
+use crate::utils::{
+    get_bump_in_seed_form, get_verified_metadata, parse_remaining_accounts_pnft,
+    transfer_nft_with_hook,
 };
-use crate::utils::{get_bump_in_seed_form, parse_remaining_accounts_pnft};
 
 #[derive(Accounts)]
 #[instruction()]
@@ -24,6 +26,9 @@ pub struct CancelListing<'info> {
         constraint = order.owner == initializer.key(),
         constraint = order.market == market.key(),
         constraint = Order::is_active(order.state),
+        constraint = order.side == <OrderSide as Into<u8>>::into(OrderSide::Sell) || order.is_auction(),
+        constraint = order.nft_mint == nft_mint.key(),
+        constraint = order.highest_bid == 0,
         seeds = [ORDER_SEED,
         order.nonce.as_ref(),
         order.market.as_ref(),
@@ -33,7 +38,7 @@ pub struct CancelListing<'info> {
     )]
     pub order: Box<Account<'info, Order>>,
     #[account(
-        constraint = Market::is_active(market.state),
+        mut,
         seeds = [MARKET_SEED,
         market.market_identifier.as_ref()],
         bump,
@@ -47,6 +52,14 @@ pub struct CancelListing<'info> {
         constraint = initializer_nft_ta.mint == nft_mint.key(),
     )]
     pub initializer_nft_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// only needed for escrow listings
+    #[account(
+        mut,
+        token::mint = nft_mint,
+        token::authority = order,
+        token::token_program = nft_token_program,
+    )]
+    pub order_nft_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     pub system_program: Program<'info, System>,
     /// CHECK: checked by constraint and in cpi
     #[account(address = sysvar::instructions::id())]
@@ -62,6 +75,41 @@ impl<'info> CancelListing<'info> {
     /*
         Metaplex Transfer Instructions
     */
+    // legacy token program only lets the owner revoke
+    fn metaplex_nft_revoke(&self) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            self.nft_token_program.to_account_info(),
+            Revoke {
+                authority: self.initializer.to_account_info(),
+                source: self.initializer_nft_ta.to_account_info(),
+            },
+        );
+        revoke(cpi_ctx)
+    }
+
+    fn metaplex_pnft_revoke(
+        &self,
+        metadata: AccountInfo<'info>,
+        edition: AccountInfo<'info>,
+        pnft_params: PnftParams<'info>,
+    ) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            self.nft_program.to_account_info(),
+            DelegateMetaplexNft {
+                delegate: self.order.to_account_info(),
+                authority: self.initializer.to_account_info(),
+                payer: self.initializer.to_account_info(),
+                token: self.initializer_nft_ta.to_account_info(),
+                mint: self.nft_mint.to_account_info(),
+                metadata,
+                edition,
+                system_program: self.system_program.to_account_info(),
+                instructions: self.sysvar_instructions.to_account_info(),
+                token_program: self.nft_token_program.to_account_info(),
+            },
+        );
+        revoke_pnft(cpi_ctx, pnft_params.into(), RevokeArgs::SaleV1)
+    }
 
     /*
         Compressed Transfer Instructions
@@ -85,6 +133,44 @@ impl<'info> CancelListing<'info> {
         );
         revoke(cpi_ctx.with_remaining_accounts(remaining_accounts))
     }
+
+    /*
+        Escrow Instructions
+    */
+    // returns the nft and closes the escrow
+    fn withdraw_escrow(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let order_nft_ta = self
+            .order_nft_ta
+            .as_ref()
+            .ok_or(MarketError::WrongAccount)?;
+
+        transfer_nft_with_hook(
+            &self.nft_token_program.to_account_info(),
+            &order_nft_ta.to_account_info(),
+            &self.nft_mint.to_account_info(),
+            &self.initializer_nft_ta.to_account_info(),
+            &self.order.to_account_info(),
+            order_nft_ta.amount,
+            self.nft_mint.decimals,
+            hook_accounts,
+            signer_seeds,
+        )?;
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.nft_token_program.to_account_info(),
+            CloseAccount {
+                account: order_nft_ta.to_account_info(),
+                destination: self.initializer.to_account_info(),
+                authority: self.order.to_account_info(),
+            },
+            signer_seeds,
+        );
+        close_account(cpi_ctx)
+    }
 }
 
 #[inline(always)]
@@ -104,11 +190,38 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CancelListing<'info>>) ->
     ][..]];
 
     // NFT Transfer
-    if *nft_token_program_key == TOKEN_PID {
+    if ctx.accounts.order.escrowed {
+        // Any remaining accounts are for potential transfer hook
+        let hook_accounts = if *nft_token_program_key == TOKEN_EXT_PID {
+            remaining_accounts
+        } else {
+            Vec::new()
+        };
+        ctx.accounts
+            .withdraw_escrow(signer_seeds, &hook_accounts)?;
+    } else if *nft_token_program_key == TOKEN_PID {
         // Check if its metaplex or not
         if *nft_program_key == METAPLEX_PID {
-            // TODO
-            return Err(MarketError::UnsupportedNft.into());
+            let nft_metadata = remaining_accounts.first().unwrap();
+            let parsed_metadata =
+                get_verified_metadata(nft_metadata, &ctx.accounts.nft_mint.key())?;
+            if get_is_pnft(&parsed_metadata) {
+                let nft_edition = remaining_accounts.get(1).unwrap();
+
+                // Remaining Accounts 2-5 for pNFT token record and rule set
+                let (_, extra_remaining_accounts) = remaining_accounts.split_at(2);
+                let pnft_params =
+                    parse_remaining_accounts_pnft(extra_remaining_accounts.to_vec(), false, None)
+                        .pnft_params;
+
+                ctx.accounts.metaplex_pnft_revoke(
+                    nft_metadata.to_account_info(),
+                    nft_edition.to_account_info(),
+                    pnft_params,
+                )?;
+            } else {
+                ctx.accounts.metaplex_nft_revoke()?;
+            }
         } else {
             // Transfer compressed NFT
             // TODO
@@ -119,14 +232,14 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CancelListing<'info>>) ->
         ctx.accounts
             .token22_nft_revoke(signer_seeds, token22_ra)?;
     } else if *nft_token_program_key == BUBBLEGUM_PID {
-        // Transfer compressed NFT
-        // TODO
+        // Compressed NFTs are handled by cancel_compressed_listing
         return Err(MarketError::UnsupportedNft.into());
     } else {
         // ERROR
         return Err(MarketError::UnsupportedNft.into());
     }
 
+    ctx.accounts.market.remove_open_order();
     ctx.accounts.order.state = OrderState::Closed.into();
 
     emit_cpi!(Order::get_edit_event(
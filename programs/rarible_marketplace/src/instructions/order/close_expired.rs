@@ -0,0 +1,228 @@
+use anchor_lang::{prelude::*, solana_program::program_option::COption};
+use anchor_spl::token_interface::{
+    close_account, revoke, transfer_checked, CloseAccount, Mint, Revoke, TokenAccount,
+    TokenInterface, TransferChecked,
+};
+
+use crate::{
+    errors::MarketError,
+    state::*,
+    utils::{get_bump_in_seed_form, transfer_nft_with_hook},
+};
+
+#[derive(Accounts)]
+#[instruction()]
+#[event_cpi]
+pub struct CloseExpiredOrder<'info> {
+    /// anyone can close an expired order
+    pub payer: Signer<'info>,
+    #[account(mut, constraint = owner.key() == order.owner)]
+    /// CHECK: constraint check
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(
+        mut,
+        constraint = order.market == market.key(),
+        constraint = Order::is_active(order.state),
+        seeds = [ORDER_SEED,
+        order.nonce.as_ref(),
+        order.market.as_ref(),
+        order.owner.as_ref()],
+        bump,
+        close = owner,
+    )]
+    pub order: Box<Account<'info, Order>>,
+    /// nft accounts are only needed when closing a sell order
+    #[account(
+        mut,
+        associated_token::mint = order.nft_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = nft_token_program,
+    )]
+    pub owner_nft_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// escrow accounts are only needed when closing an escrow listing
+    #[account(
+        mut,
+        token::mint = order.nft_mint,
+        token::authority = order,
+        token::token_program = nft_token_program,
+    )]
+    pub order_nft_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(constraint = nft_mint.key() == order.nft_mint)]
+    pub nft_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub nft_token_program: Option<Interface<'info, TokenInterface>>,
+    /// payment accounts are only needed when closing a buy order
+    #[account(
+        mut,
+        token::mint = order.payment_mint,
+        token::authority = owner,
+        token::token_program = payment_token_program,
+    )]
+    pub owner_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        token::mint = order.payment_mint,
+        token::authority = order,
+        token::token_program = payment_token_program,
+    )]
+    pub order_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(constraint = payment_mint.key() == order.payment_mint)]
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+impl<'info> CloseExpiredOrder<'info> {
+    fn refund_payment(&self, signer_seeds: &[&[&[u8]]], amount: u64) -> Result<()> {
+        let (
+            Some(owner_payment_ta),
+            Some(order_payment_ta),
+            Some(payment_mint),
+            Some(payment_token_program),
+        ) = (
+            &self.owner_payment_ta,
+            &self.order_payment_ta,
+            &self.payment_mint,
+            &self.payment_token_program,
+        )
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            payment_token_program.to_account_info(),
+            TransferChecked {
+                from: order_payment_ta.to_account_info(),
+                to: owner_payment_ta.to_account_info(),
+                authority: self.order.to_account_info(),
+                mint: payment_mint.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)
+    }
+
+    /// only token accounts still delegated to the order need a revoke
+    /// token 2022 lets the order revoke itself, legacy and programmable listings
+    /// and frozen accounts have to be cancelled by their owner
+    fn revoke_delegate(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        remaining_accounts: Vec<AccountInfo<'info>>,
+    ) -> Result<()> {
+        let (Some(owner_nft_ta), Some(nft_token_program)) =
+            (&self.owner_nft_ta, &self.nft_token_program)
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        if owner_nft_ta.delegate != COption::Some(self.order.key()) {
+            return Ok(());
+        }
+        if nft_token_program.key.to_string() != TOKEN_EXT_PID || owner_nft_ta.is_frozen() {
+            return Err(MarketError::OwnerMustRevoke.into());
+        }
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            nft_token_program.to_account_info(),
+            Revoke {
+                authority: self.order.to_account_info(),
+                source: owner_nft_ta.to_account_info(),
+            },
+            signer_seeds,
+        );
+        revoke(cpi_ctx.with_remaining_accounts(remaining_accounts))
+    }
+
+    /// returns the nft of an escrow listing and closes the escrow
+    fn withdraw_escrow(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        hook_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let (Some(owner_nft_ta), Some(order_nft_ta), Some(nft_mint), Some(nft_token_program)) = (
+            &self.owner_nft_ta,
+            &self.order_nft_ta,
+            &self.nft_mint,
+            &self.nft_token_program,
+        ) else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        transfer_nft_with_hook(
+            &nft_token_program.to_account_info(),
+            &order_nft_ta.to_account_info(),
+            &nft_mint.to_account_info(),
+            &owner_nft_ta.to_account_info(),
+            &self.order.to_account_info(),
+            order_nft_ta.amount,
+            nft_mint.decimals,
+            hook_accounts,
+            signer_seeds,
+        )?;
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            nft_token_program.to_account_info(),
+            CloseAccount {
+                account: order_nft_ta.to_account_info(),
+                destination: self.owner.to_account_info(),
+                authority: self.order.to_account_info(),
+            },
+            signer_seeds,
+        );
+        close_account(cpi_ctx)
+    }
+}
+
+/// permissionless crank to close an order past its expiry
+/// buy orders have their escrow refunded, sell orders have their delegate revoked
+/// or their nft returned from escrow
+#[inline(always)]
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CloseExpiredOrder<'info>>) -> Result<()> {
+    msg!("Close expired order account: {}", ctx.accounts.order.key());
+
+    let clock = Clock::get()?;
+    if !ctx.accounts.order.is_expired(clock.unix_timestamp) {
+        return Err(MarketError::OrderNotExpired.into());
+    }
+
+    let bump = &get_bump_in_seed_form(&ctx.bumps.order);
+    let signer_seeds: &[&[&[u8]]; 1] = &[&[
+        ORDER_SEED,
+        ctx.accounts.order.nonce.as_ref(),
+        ctx.accounts.order.market.as_ref(),
+        ctx.accounts.order.owner.as_ref(),
+        bump,
+    ][..]];
+
+    if ctx.accounts.order.side == <OrderSide as Into<u8>>::into(OrderSide::Buy) {
+        let bid_escrow = ctx.accounts.order.get_bid_escrow(ctx.accounts.order.size)?;
+        // native escrow is refunded when the order account is closed, wallet backed bids hold no escrow
+        if !ctx.accounts.order.is_native() && !ctx.accounts.order.wallet_backed {
+            ctx.accounts.refund_payment(signer_seeds, bid_escrow)?;
+        }
+    } else if ctx.accounts.order.escrowed {
+        // Any remaining accounts are for potential transfer hook
+        ctx.accounts
+            .withdraw_escrow(signer_seeds, ctx.remaining_accounts)?;
+    } else {
+        ctx.accounts
+            .revoke_delegate(signer_seeds, ctx.remaining_accounts.to_vec())?;
+    }
+
+    ctx.accounts.market.remove_open_order();
+    ctx.accounts.order.state = OrderState::Closed.into();
+
+    emit_cpi!(Order::get_edit_event(
+        &mut ctx.accounts.order.clone(),
+        ctx.accounts.order.key(),
+        ctx.accounts.market.market_identifier,
+        OrderEditType::Close,
+    ));
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use anchor_lang::{prelude::*, solana_program::program_option::COption};
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token::Token,
+    token_2022::Token2022,
+    token_interface::{revoke, transfer_checked, Mint, Revoke, TokenAccount, TransferChecked},
+};
+
+use crate::{errors::MarketError, state::*, utils::get_bump_in_seed_form};
+
+/// remaining accounts per closed order:
+/// order, order payment ta for bids or owner nft ta for listings, owner payment ta, payment mint
+/// slots an order does not use can hold any account
+pub const CLOSE_STALE_ACCOUNTS_PER_ORDER: usize = 4;
+
+#[derive(Accounts)]
+#[instruction()]
+#[event_cpi]
+pub struct CloseStaleOrders<'info> {
+    /// anyone can close the stale orders of an owner
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    /// CHECK: orders are checked against it in the handler
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        seeds = [ORDER_EPOCH_SEED,
+        owner.key().as_ref()],
+        bump,
+    )]
+    pub order_epoch: Box<Account<'info, OrderEpoch>>,
+    #[account(
+        mut,
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+    /// token programs are only needed by the orders holding accounts of that program
+    pub token_program: Option<Program<'info, Token>>,
+    pub token_2022_program: Option<Program<'info, Token2022>>,
+}
+
+impl<'info> CloseStaleOrders<'info> {
+    /// token program owning the account
+    fn get_token_program(&self, token_account: &AccountInfo<'info>) -> Result<AccountInfo<'info>> {
+        let token_program = if *token_account.owner == Token::id() {
+            self.token_program
+                .as_ref()
+                .map(|token_program| token_program.to_account_info())
+        } else if *token_account.owner == Token2022::id() {
+            self.token_2022_program
+                .as_ref()
+                .map(|token_2022_program| token_2022_program.to_account_info())
+        } else {
+            None
+        };
+        token_program.ok_or(MarketError::WrongAccount.into())
+    }
+
+    fn refund_payment(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        order: &Account<'info, Order>,
+        order_payment_ta_info: &'info AccountInfo<'info>,
+        owner_payment_ta_info: &'info AccountInfo<'info>,
+        payment_mint_info: &'info AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        let order_payment_ta = InterfaceAccount::<TokenAccount>::try_from(order_payment_ta_info)?;
+        let owner_payment_ta = InterfaceAccount::<TokenAccount>::try_from(owner_payment_ta_info)?;
+        let payment_mint = InterfaceAccount::<Mint>::try_from(payment_mint_info)?;
+        if payment_mint.key() != order.payment_mint
+            || order_payment_ta.mint != order.payment_mint
+            || order_payment_ta.owner != order.key()
+            || owner_payment_ta.mint != order.payment_mint
+            || owner_payment_ta.owner != order.owner
+        {
+            return Err(MarketError::WrongAccount.into());
+        }
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.get_token_program(order_payment_ta_info)?,
+            TransferChecked {
+                from: order_payment_ta_info.to_account_info(),
+                to: owner_payment_ta_info.to_account_info(),
+                authority: order.to_account_info(),
+                mint: payment_mint_info.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)
+    }
+
+    /// only token accounts still delegated to the order need a revoke
+    /// token 2022 lets the order revoke itself, legacy and programmable listings
+    /// and frozen accounts have to be cancelled by their owner
+    fn revoke_delegate(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        order: &Account<'info, Order>,
+        owner_nft_ta_info: &'info AccountInfo<'info>,
+    ) -> Result<()> {
+        let owner_nft_ta = InterfaceAccount::<TokenAccount>::try_from(owner_nft_ta_info)?;
+        // listings are delegated from the associated token account of the owner
+        if owner_nft_ta.mint != order.nft_mint
+            || owner_nft_ta.owner != order.owner
+            || *owner_nft_ta_info.key
+                != get_associated_token_address_with_program_id(
+                    &order.owner,
+                    &order.nft_mint,
+                    owner_nft_ta_info.owner,
+                )
+        {
+            return Err(MarketError::WrongAccount.into());
+        }
+
+        if owner_nft_ta.delegate != COption::Some(order.key()) {
+            return Ok(());
+        }
+        if *owner_nft_ta_info.owner != Token2022::id() || owner_nft_ta.is_frozen() {
+            return Err(MarketError::OwnerMustRevoke.into());
+        }
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.get_token_program(owner_nft_ta_info)?,
+            Revoke {
+                authority: order.to_account_info(),
+                source: owner_nft_ta_info.to_account_info(),
+            },
+            signer_seeds,
+        );
+        revoke(cpi_ctx)
+    }
+}
+
+/// permissionless crank to close orders cancelled by an order epoch increase
+/// bids have their escrow refunded, listings have their delegate revoked
+/// auctions, bundles, swaps and listings held by the order are closed with their own cancel instruction
+#[inline(always)]
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CloseStaleOrders<'info>>) -> Result<()> {
+    msg!("Close stale orders of: {}", ctx.accounts.owner.key());
+
+    let orders_accounts = ctx
+        .remaining_accounts
+        .chunks_exact(CLOSE_STALE_ACCOUNTS_PER_ORDER);
+    if orders_accounts.len() == 0 || !orders_accounts.remainder().is_empty() {
+        return Err(MarketError::WrongAccount.into());
+    }
+
+    let current_epoch = ctx.accounts.order_epoch.epoch;
+    let market_key = ctx.accounts.market.key();
+    let owner = ctx.accounts.owner.to_account_info();
+
+    for order_accounts in orders_accounts {
+        let order_info = &order_accounts[0];
+        let mut order = Account::<Order>::try_from(order_info)?;
+        if order.market != market_key || order.owner != owner.key() {
+            return Err(MarketError::WrongAccount.into());
+        }
+        if !Order::is_active(order.state) || !order.is_stale(current_epoch) {
+            return Err(MarketError::OrderNotStale.into());
+        }
+
+        let bump = Pubkey::find_program_address(
+            &[
+                ORDER_SEED,
+                order.nonce.as_ref(),
+                order.market.as_ref(),
+                order.owner.as_ref(),
+            ],
+            &crate::ID,
+        )
+        .1;
+        let bump = &get_bump_in_seed_form(&bump);
+        let signer_seeds: &[&[&[u8]]; 1] = &[&[
+            ORDER_SEED,
+            order.nonce.as_ref(),
+            order.market.as_ref(),
+            order.owner.as_ref(),
+            bump,
+        ][..]];
+
+        if order.side == <OrderSide as Into<u8>>::into(OrderSide::Buy) {
+            let bid_escrow = order.get_bid_escrow(order.size)?;
+            // native escrow is refunded when the order account is closed, wallet backed bids hold no escrow
+            if !order.is_native() && !order.wallet_backed {
+                ctx.accounts.refund_payment(
+                    signer_seeds,
+                    &order,
+                    &order_accounts[1],
+                    &order_accounts[2],
+                    &order_accounts[3],
+                    bid_escrow,
+                )?;
+            }
+        } else if order.side == <OrderSide as Into<u8>>::into(OrderSide::Sell) && !order.escrowed {
+            ctx.accounts
+                .revoke_delegate(signer_seeds, &order, &order_accounts[1])?;
+        } else {
+            return Err(MarketError::WrongAccount.into());
+        }
+
+        ctx.accounts.market.remove_open_order();
+        order.state = OrderState::Closed.into();
+        emit_cpi!(Order::get_edit_event(
+            &mut order.clone(),
+            order.key(),
+            ctx.accounts.market.market_identifier,
+            OrderEditType::Close,
+        ));
+        order.close(owner.to_account_info())?;
+    }
+
+    Ok(())
+}
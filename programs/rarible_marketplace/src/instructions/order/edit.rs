@@ -0,0 +1,194 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::{
+    errors::MarketError,
+    state::*,
+    utils::{get_bump_in_seed_form, lamport_transfer, transfer_sol},
+};
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct EditOrderData {
+    pub price: u64,
+    pub size: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(data: EditOrderData)]
+#[event_cpi]
+pub struct EditOrder<'info> {
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    #[account(
+        constraint = Market::is_active(market.state),
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(
+        mut,
+        constraint = data.price > 0 && data.size > 0,
+        constraint = order.owner == initializer.key(),
+        constraint = order.market == market.key(),
+        constraint = Order::is_active(order.state),
+        seeds = [ORDER_SEED,
+        order.nonce.as_ref(),
+        order.market.as_ref(),
+        initializer.key().as_ref()],
+        bump,
+    )]
+    pub order: Box<Account<'info, Order>>,
+    /// payment accounts are only needed when editing a buy order priced in a token
+    #[account(
+        mut,
+        token::mint = order.payment_mint,
+        token::authority = initializer,
+        token::token_program = payment_token_program,
+    )]
+    pub initializer_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        token::mint = order.payment_mint,
+        token::authority = order,
+        token::token_program = payment_token_program,
+    )]
+    pub order_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(constraint = payment_mint.key() == order.payment_mint)]
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> EditOrder<'info> {
+    fn transfer_payment(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        is_top_up: bool,
+        amount: u64,
+    ) -> Result<()> {
+        if self.order.is_native() {
+            return if is_top_up {
+                transfer_sol(
+                    self.initializer.to_account_info(),
+                    self.order.to_account_info(),
+                    self.system_program.to_account_info(),
+                    None,
+                    amount,
+                )
+            } else {
+                lamport_transfer(
+                    self.order.to_account_info(),
+                    self.initializer.to_account_info(),
+                    amount,
+                )
+            };
+        }
+
+        let (
+            Some(initializer_payment_ta),
+            Some(order_payment_ta),
+            Some(payment_mint),
+            Some(payment_token_program),
+        ) = (
+            &self.initializer_payment_ta,
+            &self.order_payment_ta,
+            &self.payment_mint,
+            &self.payment_token_program,
+        )
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        let cpi_ctx = if is_top_up {
+            CpiContext::new(
+                payment_token_program.to_account_info(),
+                TransferChecked {
+                    from: initializer_payment_ta.to_account_info(),
+                    to: order_payment_ta.to_account_info(),
+                    authority: self.initializer.to_account_info(),
+                    mint: payment_mint.to_account_info(),
+                },
+            )
+        } else {
+            CpiContext::new_with_signer(
+                payment_token_program.to_account_info(),
+                TransferChecked {
+                    from: order_payment_ta.to_account_info(),
+                    to: initializer_payment_ta.to_account_info(),
+                    authority: self.order.to_account_info(),
+                    mint: payment_mint.to_account_info(),
+                },
+                signer_seeds,
+            )
+        };
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)
+    }
+}
+
+/// sell orders can only be repriced, resizing would require a new delegation
+/// buy orders can be repriced and resized, the escrow is topped up or refunded by the difference
+#[inline(always)]
+pub fn handler(ctx: Context<EditOrder>, data: EditOrderData) -> Result<()> {
+    msg!("Edit order account: {}", ctx.accounts.order.key());
+
+    let is_buy = ctx.accounts.order.side == <OrderSide as Into<u8>>::into(OrderSide::Buy);
+
+    if !is_buy
+        && (data.size != ctx.accounts.order.size
+            || ctx.accounts.order.is_auction()
+            || ctx.accounts.order.is_swap()
+            || (ctx.accounts.order.is_dutch_auction()
+                && data.price <= ctx.accounts.order.end_price))
+    {
+        return Err(MarketError::InvalidOrderEdit.into());
+    }
+
+    // the escrow covers the maker fee of a bid on top of the price
+    let old_escrow = if is_buy {
+        ctx.accounts.order.get_bid_escrow(ctx.accounts.order.size)?
+    } else {
+        0
+    };
+
+    let payment_mint = ctx.accounts.order.payment_mint;
+    let clock = Clock::get()?;
+    Order::edit_order(
+        &mut ctx.accounts.order,
+        data.price,
+        payment_mint,
+        data.size,
+        clock.unix_timestamp,
+    );
+
+    // wallet backed bids hold no escrow, the wallet balance is checked on fill
+    if is_buy && !ctx.accounts.order.wallet_backed {
+        let bump = &get_bump_in_seed_form(&ctx.bumps.order);
+        let signer_seeds: &[&[&[u8]]; 1] = &[&[
+            ORDER_SEED,
+            ctx.accounts.order.nonce.as_ref(),
+            ctx.accounts.order.market.as_ref(),
+            ctx.accounts.order.owner.as_ref(),
+            bump,
+        ][..]];
+
+        let new_escrow = ctx.accounts.order.get_bid_escrow(data.size)?;
+        if new_escrow > old_escrow {
+            ctx.accounts
+                .transfer_payment(signer_seeds, true, new_escrow - old_escrow)?;
+        } else if new_escrow < old_escrow {
+            ctx.accounts
+                .transfer_payment(signer_seeds, false, old_escrow - new_escrow)?;
+        }
+    }
+
+    emit_cpi!(Order::get_edit_event(
+        &mut ctx.accounts.order.clone(),
+        ctx.accounts.order.key(),
+        ctx.accounts.market.market_identifier,
+        OrderEditType::Edit,
+    ));
+    Ok(())
+}
@@ -5,7 +5,8 @@ use anchor_spl::{
     associated_token::AssociatedToken,
     token_2022::spl_token_2022::instruction::transfer_checked as transfer_2022,
     token_interface::{
-        transfer_checked, Mint, TokenInterface, TransferChecked,
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
     },
 };
 use spl_transfer_hook_interface::onchain::add_extra_accounts_for_execute_cpi;
@@ -20,8 +21,17 @@ use crate::{
     errors::MarketError,
     state::*,
     utils::{
-        create_ata, get_amount_from_bp, get_bump_in_seed_form, get_fee_amount,
-        token_extensions::WnsApprovalAccounts, verify_wns_mint,
+        create_ata, get_amount_from_bp, get_bump_in_seed_form, get_editions_royalties,
+        get_has_royalty_field, get_verified_metadata, lamport_transfer,
+        metaplex::pnft::utils::get_is_pnft,
+        mplx_transfer::{
+            get_extra_transfer_params, transfer_metaplex_nft, MetaplexAdditionalTransferAccounts,
+            TransferMetaplexNft,
+        },
+        parse_remaining_accounts_pnft,
+        token_extensions::WnsApprovalAccounts,
+        transfer_sol,
+        verify_merkle_mint, verify_mint_verification, verify_wns_mint,
     },
 };
 
@@ -35,6 +45,7 @@ pub struct FillOrder<'info> {
     /// CHECK: constraint check
     pub maker: UncheckedAccount<'info>,
     #[account(
+        mut,
         constraint = Market::is_active(market.state),
         seeds = [MARKET_SEED,
         market.market_identifier.as_ref()],
@@ -44,6 +55,8 @@ pub struct FillOrder<'info> {
     #[account(
         mut,
         constraint = Order::is_active(order.state),
+        constraint = !order.is_auction(),
+        constraint = !order.is_swap(),
         constraint = order.market == market.key(),
         constraint = order.owner == maker.key(),
         seeds = [ORDER_SEED,
@@ -51,9 +64,23 @@ pub struct FillOrder<'info> {
         order.market.as_ref(),
         order.owner.as_ref()],
         bump,
-        close = maker
     )]
     pub order: Box<Account<'info, Order>>,
+    #[account(
+        seeds = [ORDER_EPOCH_SEED,
+        order.owner.as_ref()],
+        bump,
+    )]
+    /// CHECK: seeds constraint, empty until the order owner first cancels all their orders
+    pub order_epoch: UncheckedAccount<'info>,
+    /// wallet of the bidder, only needed to fill wallet backed bids
+    #[account(
+        mut,
+        seeds = [WALLET_SEED,
+        order.owner.as_ref()],
+        bump,
+    )]
+    pub wallet: Option<Box<Account<'info, Wallet>>>,
     #[account(mut)]
     pub nft_mint: Box<InterfaceAccount<'info, Mint>>,
     #[account(mut)]
@@ -67,20 +94,24 @@ pub struct FillOrder<'info> {
     pub fee_recipient: UncheckedAccount<'info>,
     #[account(mut)]
     /// CHECK: checked by create_ata function
-    pub fee_recipient_ta: UncheckedAccount<'info>,
+    pub fee_recipient_ta: Option<UncheckedAccount<'info>>,
     pub system_program: Program<'info, System>,
     pub nft_token_program: Interface<'info, TokenInterface>,
     /// CHECK: checked by constraint and in cpi
     pub nft_program: UncheckedAccount<'info>,
+    /// payment token accounts, mint and program are left out for orders in native SOL
     #[account(mut)]
     /// CHECK: checked by create_ata function
-    pub seller_payment_ta: UncheckedAccount<'info>,
+    pub seller_payment_ta: Option<UncheckedAccount<'info>>,
     #[account(mut)]
     /// CHECK: checked by create_ata function
-    pub buyer_payment_ta: UncheckedAccount<'info>,
+    pub buyer_payment_ta: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function, only needed for maker rebates on bids
+    pub maker_payment_ta: Option<UncheckedAccount<'info>>,
     #[account(mut, constraint = payment_mint.key() == order.payment_mint)]
-    pub payment_mint: Box<InterfaceAccount<'info, Mint>>,
-    pub payment_token_program: Interface<'info, TokenInterface>,
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
     /// CHECK: checked by constraint and in cpi
     #[account(address = sysvar::instructions::id())]
     pub sysvar_instructions: UncheckedAccount<'info>,
@@ -88,31 +119,195 @@ pub struct FillOrder<'info> {
 }
 
 impl<'info> FillOrder<'info> {
+    /// pays the recipient from the buyer, in lamports when the order is priced in native SOL
     #[inline(never)]
-    fn transfer_payment(&self, signer_seeds: &[&[&[u8]]], is_buy: bool, amount: u64) -> Result<()> {
-        let cpi_ctx = if is_buy {
-            CpiContext::new_with_signer(
-                self.payment_token_program.to_account_info(),
-                TransferChecked {
-                    from: self.buyer_payment_ta.to_account_info(),
-                    to: self.seller_payment_ta.to_account_info(),
-                    authority: self.order.to_account_info(),
-                    mint: self.payment_mint.to_account_info(),
-                },
-                signer_seeds,
+    fn transfer_payment(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        is_buy: bool,
+        recipient: AccountInfo<'info>,
+        recipient_ta: Option<AccountInfo<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        if self.order.is_native() {
+            // the order account holds the escrowed lamports of a bid
+            return if is_buy {
+                lamport_transfer(self.bid_payer()?, recipient, amount)
+            } else {
+                transfer_sol(
+                    self.taker.to_account_info(),
+                    recipient,
+                    self.system_program.to_account_info(),
+                    None,
+                    amount,
+                )
+            };
+        }
+
+        let (
+            Some(buyer_payment_ta),
+            Some(recipient_ta),
+            Some(payment_mint),
+            Some(payment_token_program),
+        ) = (
+            &self.buyer_payment_ta,
+            recipient_ta,
+            &self.payment_mint,
+            &self.payment_token_program,
+        )
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        let authority = if is_buy {
+            self.bid_payer()?
+        } else {
+            self.taker.to_account_info()
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            payment_token_program.to_account_info(),
+            TransferChecked {
+                from: buyer_payment_ta.to_account_info(),
+                to: recipient_ta,
+                authority,
+                mint: payment_mint.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)
+    }
+
+    /// account bids are paid from, the wallet of the bidder for wallet backed bids
+    fn bid_payer(&self) -> Result<AccountInfo<'info>> {
+        if !self.order.wallet_backed {
+            return Ok(self.order.to_account_info());
+        }
+        self.wallet
+            .as_ref()
+            .map(|wallet| wallet.to_account_info())
+            .ok_or(MarketError::WrongAccount.into())
+    }
+
+    /// wallet backed bids fail to fill once the balance of the bidder no longer covers them
+    fn check_wallet_balance(&self, amount: u64) -> Result<()> {
+        let balance = if self.order.is_native() {
+            Wallet::get_native_balance(&self.bid_payer()?)?
+        } else {
+            let buyer_payment_ta = self
+                .buyer_payment_ta
+                .as_ref()
+                .ok_or(MarketError::WrongAccount)?;
+            if buyer_payment_ta.data_is_empty() {
+                0
+            } else {
+                TokenAccount::try_deserialize(&mut &buyer_payment_ta.data.borrow()[..])?.amount
+            }
+        };
+
+        if balance < amount {
+            return Err(MarketError::InsufficientBalance.into());
+        }
+        Ok(())
+    }
+
+    /// native orders pay wallets directly and need no token accounts
+    #[inline(never)]
+    fn create_payment_ata(
+        &self,
+        ata: Option<AccountInfo<'info>>,
+        owner: &AccountInfo<'info>,
+    ) -> Result<()> {
+        if self.order.is_native() {
+            return Ok(());
+        }
+
+        let (Some(ata), Some(payment_mint), Some(payment_token_program)) =
+            (ata, &self.payment_mint, &self.payment_token_program)
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        create_ata(
+            &ata,
+            &self.taker.to_account_info(),
+            &payment_mint.to_account_info(),
+            owner,
+            &self.system_program.to_account_info(),
+            &payment_token_program.to_account_info(),
+        )
+    }
+
+    /*
+        Metaplex Transfer Instructions
+    */
+    #[inline(never)]
+    fn metaplex_nft_transfer(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        is_buy: bool,
+        amount: u64,
+        is_pnft: bool,
+        transfer_params: MetaplexAdditionalTransferAccounts<'info>,
+    ) -> Result<()> {
+        let (authority, source_owner, destination_owner) = if is_buy {
+            (
+                self.taker.to_account_info(),
+                self.taker.to_account_info(),
+                self.maker.to_account_info(),
             )
         } else {
-            CpiContext::new(
-                self.payment_token_program.to_account_info(),
-                TransferChecked {
-                    from: self.buyer_payment_ta.to_account_info(),
-                    to: self.seller_payment_ta.to_account_info(),
-                    authority: self.taker.to_account_info(),
-                    mint: self.payment_mint.to_account_info(),
-                },
+            (
+                self.order.to_account_info(),
+                self.nft_holder(),
+                self.taker.to_account_info(),
             )
         };
-        transfer_checked(cpi_ctx, amount, self.payment_mint.decimals)
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.nft_program.to_account_info(),
+            TransferMetaplexNft {
+                authority,
+                payer: self.taker.to_account_info(),
+                source_owner,
+                source_ta: self.seller_nft_ta.to_account_info(),
+                destination_owner,
+                destination_ta: self.buyer_nft_ta.to_account_info(),
+                mint: self.nft_mint.to_account_info(),
+                metadata: transfer_params.metadata,
+                edition: transfer_params.edition,
+                system_program: self.system_program.to_account_info(),
+                instructions: self.sysvar_instructions.to_account_info(),
+                token_program: self.nft_token_program.to_account_info(),
+                ata_program: self.associated_token_program.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        transfer_metaplex_nft(cpi_ctx, transfer_params.extra_accounts, amount, is_pnft)
+    }
+
+    /// owner of the nft of a listing, the order itself for escrow listings
+    fn nft_holder(&self) -> AccountInfo<'info> {
+        if self.order.escrowed {
+            self.order.to_account_info()
+        } else {
+            self.maker.to_account_info()
+        }
+    }
+
+    /// closes the emptied escrow of a listing, rent goes back to the maker
+    #[inline(never)]
+    fn close_escrow(&self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.nft_token_program.to_account_info(),
+            CloseAccount {
+                account: self.seller_nft_ta.to_account_info(),
+                destination: self.maker.to_account_info(),
+                authority: self.order.to_account_info(),
+            },
+            signer_seeds,
+        );
+        close_account(cpi_ctx)
     }
 
     /*
@@ -133,51 +328,52 @@ impl<'info> FillOrder<'info> {
         wns_accounts: WnsApprovalAccounts<'info>,
     ) -> Result<()> {
         let cpi_program = self.nft_program.to_account_info();
-        create_ata(
-            &wns_accounts.distribution_token_account.to_account_info(),
-            &self.taker.to_account_info(),
-            &self.payment_mint.to_account_info(),
-            &wns_accounts.distribution_account.to_account_info(),
-            &self.system_program.to_account_info(),
-            &self.payment_token_program.to_account_info(),
+        if wns_accounts.payment_mint.key() != self.order.payment_mint {
+            return Err(MarketError::WrongAccount.into());
+        }
+        self.create_payment_ata(
+            Some(wns_accounts.distribution_token_account.to_account_info()),
+            &wns_accounts.distribution_account,
         )?;
-        let cpi_ctx = if is_buy {
-            let cpi_accounts = ApproveTransfer {
-                payer: self.taker.to_account_info(),
-                authority: self.order.to_account_info(),
-                mint: self.nft_mint.to_account_info(),
-                approve_account: wns_accounts.approval_account.to_account_info(),
-                payment_mint: self.payment_mint.to_account_info(),
-                distribution_token_account: Some(
-                    wns_accounts.distribution_token_account.to_account_info(),
-                ),
-                authority_token_account: Some(self.buyer_payment_ta.to_account_info()),
-                distribution_account: wns_accounts.distribution_account.to_account_info(),
-                system_program: self.system_program.to_account_info(),
-                distribution_program: wns_accounts.distribution_program.to_account_info(),
-                token_program: self.nft_token_program.to_account_info(),
-                payment_token_program: Some(self.payment_token_program.to_account_info()),
-            };
-            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds)
+
+        // native royalties are a system transfer the order account can't make,
+        // so the taker pays them after being reimbursed from the escrow
+        let is_native = self.order.is_native();
+        let authority = if is_buy && !is_native {
+            self.bid_payer()?
         } else {
-            let cpi_accounts = ApproveTransfer {
-                payer: self.taker.to_account_info(),
-                authority: self.taker.to_account_info(),
-                mint: self.nft_mint.to_account_info(),
-                approve_account: wns_accounts.approval_account.to_account_info(),
-                payment_mint: self.payment_mint.to_account_info(),
-                distribution_token_account: Some(
-                    wns_accounts.distribution_token_account.to_account_info(),
-                ),
-                authority_token_account: Some(self.buyer_payment_ta.to_account_info()),
-                distribution_account: wns_accounts.distribution_account.to_account_info(),
-                system_program: self.system_program.to_account_info(),
-                distribution_program: wns_accounts.distribution_program.to_account_info(),
-                token_program: self.nft_token_program.to_account_info(),
-                payment_token_program: Some(self.payment_token_program.to_account_info()),
+            self.taker.to_account_info()
+        };
+        let (distribution_token_account, authority_token_account, payment_token_program) =
+            if is_native {
+                (None, None, None)
+            } else {
+                (
+                    Some(wns_accounts.distribution_token_account.to_account_info()),
+                    self.buyer_payment_ta
+                        .as_ref()
+                        .map(|buyer_payment_ta| buyer_payment_ta.to_account_info()),
+                    self.payment_token_program
+                        .as_ref()
+                        .map(|payment_token_program| payment_token_program.to_account_info()),
+                )
             };
-            CpiContext::new(cpi_program, cpi_accounts)
+
+        let cpi_accounts = ApproveTransfer {
+            payer: self.taker.to_account_info(),
+            authority,
+            mint: self.nft_mint.to_account_info(),
+            approve_account: wns_accounts.approval_account.to_account_info(),
+            payment_mint: wns_accounts.payment_mint.to_account_info(),
+            distribution_token_account,
+            authority_token_account,
+            distribution_account: wns_accounts.distribution_account.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            distribution_program: wns_accounts.distribution_program.to_account_info(),
+            token_program: self.nft_token_program.to_account_info(),
+            payment_token_program,
         };
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
         approve_transfer(cpi_ctx, buy_amount)
     }
 
@@ -238,33 +434,6 @@ impl<'info> FillOrder<'info> {
         Ok(())
     }
 
-    #[inline(never)]
-    fn transfer_fee(&self, signer_seeds: &[&[&[u8]]], is_buy: bool, amount: u64) -> Result<()> {
-        let cpi_ctx = if is_buy {
-            CpiContext::new_with_signer(
-                self.payment_token_program.to_account_info(),
-                TransferChecked {
-                    from: self.buyer_payment_ta.to_account_info(),
-                    to: self.fee_recipient_ta.to_account_info(),
-                    authority: self.order.to_account_info(),
-                    mint: self.payment_mint.to_account_info(),
-                },
-                signer_seeds,
-            )
-        } else {
-            CpiContext::new(
-                self.payment_token_program.to_account_info(),
-                TransferChecked {
-                    from: self.buyer_payment_ta.to_account_info(),
-                    to: self.fee_recipient_ta.to_account_info(),
-                    authority: self.taker.to_account_info(),
-                    mint: self.payment_mint.to_account_info(),
-                },
-            )
-        };
-        transfer_checked(cpi_ctx, amount, self.payment_mint.decimals)
-    }
-
 }
 
 /// Initializer is the buyer and is buying an nft from the seller
@@ -274,6 +443,7 @@ impl<'info> FillOrder<'info> {
 pub fn handler<'info>(
     ctx: Context<'_, '_, '_, 'info, FillOrder<'info>>,
     amount: u64,
+    merkle_proof: Option<Vec<[u8; 32]>>,
 ) -> Result<()> {
     let nft_token_program_key = &ctx.accounts.nft_token_program.key.to_string().clone();
     let nft_program_key = &ctx.accounts.nft_program.key.to_string().clone();
@@ -289,32 +459,108 @@ pub fn handler<'info>(
         ctx.accounts.order.owner.as_ref(),
         bump,
     ][..]];
+    // wallet backed bids pay out of the wallet of the bidder instead of the order escrow
+    let order_owner = ctx.accounts.order.owner;
+    let wallet_bump = &get_bump_in_seed_form(&ctx.bumps.wallet.unwrap_or_default());
+    let wallet_signer_seeds: &[&[&[u8]]; 1] =
+        &[&[WALLET_SEED, order_owner.as_ref(), wallet_bump][..]];
+    let payer_seeds = if ctx.accounts.order.wallet_backed {
+        wallet_signer_seeds
+    } else {
+        signer_seeds
+    };
+
+    let clock = Clock::get()?;
+    if ctx.accounts.order.is_expired(clock.unix_timestamp) {
+        return Err(MarketError::OrderExpired.into());
+    }
+    if ctx
+        .accounts
+        .order
+        .is_stale(OrderEpoch::get_current(&ctx.accounts.order_epoch)?)
+    {
+        return Err(MarketError::StaleOrder.into());
+    }
+    if ctx.accounts.order.is_private() && ctx.accounts.order.taker != ctx.accounts.taker.key() {
+        return Err(MarketError::InvalidTaker.into());
+    }
+    if !ctx
+        .accounts
+        .market
+        .is_payment_mint_allowed(&ctx.accounts.order.payment_mint)
+    {
+        return Err(MarketError::PaymentMintNotAllowed.into());
+    }
 
-    let buy_price = ctx.accounts.order.price;
+    // collection bids take any mint that passes the market membership check below
+    let is_collection_bid = ctx.accounts.order.is_collection_bid();
+    if !is_collection_bid && ctx.accounts.nft_mint.key() != ctx.accounts.order.nft_mint {
+        return Err(MarketError::InvalidNft.into());
+    }
+    // trait bids narrow the collection down to the mints of their merkle root
+    if ctx.accounts.order.is_trait_bid() {
+        verify_merkle_mint(
+            ctx.accounts.order.merkle_root,
+            merkle_proof,
+            ctx.accounts.nft_mint.key(),
+        )?;
+    }
+
+    // dutch auctions fill at the price reached at this time
+    let buy_price = ctx.accounts.order.get_current_price(clock.unix_timestamp);
     let order_size = ctx.accounts.order.size;
 
-    if order_size < amount {
+    if amount == 0 || order_size < amount {
         return Err(MarketError::InsufficientOrderSize.into());
     }
 
     let buy_value = amount.checked_mul(buy_price).unwrap();
 
-    let fee_amount = get_fee_amount(buy_value, ctx.accounts.market.fee_bps);
-
-    let mut seller_received_amount = buy_value - fee_amount;
-
     let is_buy = ctx.accounts.order.side == 0;
+    // a bid maker pays their fee out of the escrow on top of the price
+    let fill_fees = if is_buy {
+        ctx.accounts.order.get_bid_fill_fees(&ctx.accounts.market, amount)?
+    } else if ctx.accounts.order.fees_on {
+        ctx.accounts.market.get_fill_fees(buy_value, is_buy)?
+    } else {
+        FillFees::default()
+    };
+    if is_buy && ctx.accounts.order.wallet_backed {
+        let bid_amount = buy_value
+            .checked_add_signed(fill_fees.maker_fee.max(0))
+            .ok_or(MarketError::AmountOverflow)?;
+        ctx.accounts.check_wallet_balance(bid_amount)?;
+    }
+    // maker rebates are paid out of the taker fee
+    let fee_amount =
+        u64::try_from(fill_fees.maker_fee.saturating_add_unsigned(fill_fees.taker_fee))
+            .map_err(|_| MarketError::AmountUnderflow)?;
+
+    // a listing taker pays their fee on top of the price,
+    // a bid taker pays theirs out of the proceeds and a maker rebate comes out of the escrow
+    let (mut seller_received_amount, maker_rebate) = if is_buy {
+        let seller_received_amount = buy_value
+            .checked_sub(fill_fees.taker_fee)
+            .ok_or(MarketError::AmountUnderflow)?;
+        (seller_received_amount, fill_fees.maker_fee.min(0).unsigned_abs())
+    } else {
+        let seller_received_amount = buy_value
+            .checked_add_signed(-fill_fees.maker_fee)
+            .ok_or(MarketError::AmountUnderflow)?;
+        (seller_received_amount, 0)
+    };
+    let mut royalties_paid: u64 = 0;
     // Verify maker + taker accounts
     // Verify the buyer account
 
     let system_program = ctx.accounts.system_program.to_account_info();
     let nft_token_program = ctx.accounts.nft_token_program.to_account_info();
-    let payment_token_program = ctx.accounts.payment_token_program.to_account_info();
 
+    // escrow listings sell out of the token account of the order
     let nft_funder = if is_buy {
         ctx.accounts.taker.to_account_info()
     } else {
-        ctx.accounts.maker.to_account_info()
+        ctx.accounts.nft_holder()
     };
     create_ata(
         &ctx.accounts.seller_nft_ta.to_account_info(),
@@ -340,49 +586,125 @@ pub fn handler<'info>(
     )?;
 
     let payment_funder = if is_buy {
-        ctx.accounts.order.to_account_info()
+        ctx.accounts.bid_payer()?
     } else {
         ctx.accounts.taker.to_account_info()
     };
-    create_ata(
-        &ctx.accounts.buyer_payment_ta.to_account_info(),
-        &ctx.accounts.taker.to_account_info(),
-        &ctx.accounts.payment_mint.to_account_info(),
-        &payment_funder,
-        &system_program,
-        &payment_token_program,
-    )?;
+    let buyer_payment_ta = ctx
+        .accounts
+        .buyer_payment_ta
+        .as_ref()
+        .map(|buyer_payment_ta| buyer_payment_ta.to_account_info());
+    ctx.accounts
+        .create_payment_ata(buyer_payment_ta, &payment_funder)?;
 
     let payment_receiver = if is_buy {
         ctx.accounts.taker.to_account_info()
     } else {
         ctx.accounts.maker.to_account_info()
     };
-    create_ata(
-        &ctx.accounts.seller_payment_ta.to_account_info(),
-        &ctx.accounts.taker.to_account_info(),
-        &ctx.accounts.payment_mint.to_account_info(),
-        &payment_receiver,
-        &system_program,
-        &payment_token_program,
-    )?;
+    let seller_payment_ta = ctx
+        .accounts
+        .seller_payment_ta
+        .as_ref()
+        .map(|seller_payment_ta| seller_payment_ta.to_account_info());
+    ctx.accounts
+        .create_payment_ata(seller_payment_ta.clone(), &payment_receiver)?;
 
     let fee_reciever = ctx.accounts.fee_recipient.to_account_info();
-    create_ata(
-        &ctx.accounts.fee_recipient_ta.to_account_info(),
-        &ctx.accounts.taker.to_account_info(),
-        &ctx.accounts.payment_mint.to_account_info(),
-        &fee_reciever,
-        &system_program,
-        &payment_token_program,
-    )?;
+    let fee_recipient_ta = ctx
+        .accounts
+        .fee_recipient_ta
+        .as_ref()
+        .map(|fee_recipient_ta| fee_recipient_ta.to_account_info());
+    ctx.accounts
+        .create_payment_ata(fee_recipient_ta.clone(), &fee_reciever)?;
 
     // Transfer NFT
     if *nft_token_program_key == TOKEN_PID {
         // Check if its metaplex or not
         if *nft_program_key == METAPLEX_PID {
-            // TODO
-            return Err(MarketError::UnsupportedNft.into());
+            // Remaining Accounts 0-2 for metadata, edition and manual verification
+            let nft_metadata = remaining_accounts.first().ok_or(MarketError::WrongAccount)?;
+            let nft_edition = remaining_accounts.get(1).ok_or(MarketError::WrongAccount)?;
+            let verification_account = remaining_accounts.get(2).ok_or(MarketError::WrongAccount)?;
+
+            verify_mint_verification(
+                verification_account,
+                ctx.accounts.nft_mint.key(),
+                ctx.accounts.market.key(),
+            )?;
+
+            let parsed_metadata =
+                get_verified_metadata(nft_metadata, &ctx.accounts.nft_mint.key())?;
+            let is_pnft = get_is_pnft(&parsed_metadata);
+
+            // Remaining Accounts after the pNFT accounts are a creator and creator payment ta per creator
+            let creator_accounts = remaining_accounts
+                .get(if is_pnft { 7 } else { 3 }..)
+                .ok_or(MarketError::WrongAccount)?;
+            let royalties = get_amount_from_bp(
+                buy_value,
+                parsed_metadata.seller_fee_basis_points.into(),
+            )?;
+            for (i, creator) in parsed_metadata.creators.iter().flatten().enumerate() {
+                let creator_account = creator_accounts
+                    .get(2 * i)
+                    .ok_or(MarketError::WrongAccount)?;
+                let creator_payment_ta = creator_accounts
+                    .get(1 + 2 * i)
+                    .ok_or(MarketError::WrongAccount)?;
+                if *creator_account.key != creator.address {
+                    return Err(MarketError::WrongAccount.into());
+                }
+
+                let creator_amount =
+                    get_amount_from_bp(royalties, u128::from(creator.share) * 100)?;
+                if creator_amount == 0 {
+                    continue;
+                }
+                ctx.accounts.create_payment_ata(
+                    Some(creator_payment_ta.to_account_info()),
+                    creator_account,
+                )?;
+                ctx.accounts.transfer_payment(
+                    payer_seeds,
+                    is_buy,
+                    creator_account.to_account_info(),
+                    Some(creator_payment_ta.to_account_info()),
+                    creator_amount,
+                )?;
+                royalties_paid = royalties_paid
+                    .checked_add(creator_amount)
+                    .ok_or(MarketError::AmountOverflow)?;
+            }
+            seller_received_amount = seller_received_amount
+                .checked_sub(royalties_paid)
+                .ok_or(MarketError::AmountUnderflow)?;
+
+            // Remaining Accounts 3-6 for pNFT token records and rule set
+            let extra_accounts = if is_pnft {
+                let (_, extra_remaining_accounts) = remaining_accounts.split_at(3);
+                parse_remaining_accounts_pnft(extra_remaining_accounts.to_vec(), false, None)
+                    .pnft_params
+                    .into()
+            } else {
+                get_extra_transfer_params(Vec::new(), None, 0)
+            };
+
+            let transfer_params = MetaplexAdditionalTransferAccounts {
+                metadata: nft_metadata.to_account_info(),
+                edition: nft_edition.to_account_info(),
+                extra_accounts,
+            };
+
+            ctx.accounts.metaplex_nft_transfer(
+                signer_seeds,
+                is_buy,
+                amount,
+                is_pnft,
+                transfer_params,
+            )?;
         } else {
             // Transfer compressed NFT
             // TODO
@@ -424,17 +746,89 @@ pub fn handler<'info>(
 
             let royalties = get_amount_from_bp(buy_value, royalty_basis_points.into())?;
             seller_received_amount = seller_received_amount.checked_sub(royalties).unwrap();
+            royalties_paid = royalties;
+
+            if is_buy && ctx.accounts.order.is_native() {
+                lamport_transfer(
+                    ctx.accounts.bid_payer()?,
+                    ctx.accounts.taker.to_account_info(),
+                    royalties,
+                )?;
+            }
 
             // Handles royalties
             ctx.accounts
-                .approve_wns_transfer(signer_seeds, buy_value, is_buy, wns_accounts)?;
+                .approve_wns_transfer(payer_seeds, buy_value, is_buy, wns_accounts)?;
+        } else {
+            // Remaining Account 0 for the mint verification when filling a collection bid
+            if is_collection_bid {
+                let verification_account = token22_ra.first().ok_or(MarketError::WrongAccount)?;
+                verify_mint_verification(
+                    verification_account,
+                    ctx.accounts.nft_mint.key(),
+                    ctx.accounts.market.key(),
+                )?;
+                token22_ra.remove(0);
+            }
+
+            if *nft_program_key == EDITIONS_PID {
+                // Remaining Account 0 for the editions deployment, then a creator and creator payment ta per creator
+                let editions_deployment = token22_ra.first().ok_or(MarketError::WrongAccount)?;
+                let editions_royalties = get_editions_royalties(
+                    &mut ctx.accounts.nft_mint.to_account_info(),
+                    editions_deployment,
+                )?;
+                let royalties = get_amount_from_bp(
+                    buy_value,
+                    editions_royalties.royalty_basis_points.into(),
+                )?;
+
+                for (i, creator) in editions_royalties.creators.iter().enumerate() {
+                    let creator_account = token22_ra
+                        .get(1 + 2 * i)
+                        .ok_or(MarketError::WrongAccount)?;
+                    let creator_payment_ta = token22_ra
+                        .get(2 + 2 * i)
+                        .ok_or(MarketError::WrongAccount)?;
+                    if *creator_account.key != creator.address {
+                        return Err(MarketError::WrongAccount.into());
+                    }
+
+                    let creator_amount =
+                        get_amount_from_bp(royalties, u128::from(creator.share) * 100)?;
+                    if creator_amount == 0 {
+                        continue;
+                    }
+                    ctx.accounts.create_payment_ata(
+                        Some(creator_payment_ta.to_account_info()),
+                        creator_account,
+                    )?;
+                    ctx.accounts.transfer_payment(
+                        payer_seeds,
+                        is_buy,
+                        creator_account.to_account_info(),
+                        Some(creator_payment_ta.to_account_info()),
+                        creator_amount,
+                    )?;
+                    royalties_paid = royalties_paid
+                        .checked_add(creator_amount)
+                        .ok_or(MarketError::AmountOverflow)?;
+                }
+                seller_received_amount = seller_received_amount
+                    .checked_sub(royalties_paid)
+                    .ok_or(MarketError::AmountUnderflow)?;
+
+                token22_ra = token22_ra.split_off(1 + 2 * editions_royalties.creators.len());
+            } else if get_has_royalty_field(&mut ctx.accounts.nft_mint.to_account_info()) {
+                // mints carrying royalties must go through their royalty path
+                return Err(MarketError::UnsupportedNft.into());
+            }
         }
         // Any remaining accounts left are for potential transfer hook (Empty if not expecting hook)
         ctx.accounts
-            .token22_nft_transfer(signer_seeds, is_buy, order_size, token22_ra)?;
+            .token22_nft_transfer(signer_seeds, is_buy, amount, token22_ra)?;
     } else if *nft_token_program_key == BUBBLEGUM_PID {
-        // Transfer compressed NFT
-        // TODO
+        // Compressed NFTs are handled by fill_compressed_listing / fill_compressed_bid
         return Err(MarketError::UnsupportedNft.into());
     } else {
         // ERROR
@@ -442,25 +836,78 @@ pub fn handler<'info>(
     }
 
     // Transfer payment
-    ctx.accounts
-        .transfer_payment(signer_seeds, is_buy, seller_received_amount)?;
-    ctx.accounts
-        .transfer_fee(signer_seeds, is_buy, fee_amount)?;
+    ctx.accounts.transfer_payment(
+        payer_seeds,
+        is_buy,
+        payment_receiver,
+        seller_payment_ta,
+        seller_received_amount,
+    )?;
+    ctx.accounts.transfer_payment(
+        payer_seeds,
+        is_buy,
+        fee_reciever,
+        fee_recipient_ta,
+        fee_amount,
+    )?;
+    if maker_rebate > 0 {
+        let maker = ctx.accounts.maker.to_account_info();
+        let maker_payment_ta = ctx
+            .accounts
+            .maker_payment_ta
+            .as_ref()
+            .map(|maker_payment_ta| maker_payment_ta.to_account_info());
+        ctx.accounts
+            .create_payment_ata(maker_payment_ta.clone(), &maker)?;
+        ctx.accounts.transfer_payment(
+            payer_seeds,
+            is_buy,
+            maker,
+            maker_payment_ta,
+            maker_rebate,
+        )?;
+    }
+
+    emit_cpi!(ctx.accounts.order.get_fill_event(
+        ctx.accounts.order.key(),
+        ctx.accounts.market.market_identifier,
+        ctx.accounts.taker.key(),
+        ctx.accounts.nft_mint.key(),
+        amount,
+        buy_price,
+        buy_value,
+        &fill_fees,
+        royalties_paid,
+    ));
+    if is_buy && ctx.accounts.order.wallet_backed {
+        if let Some(wallet) = &ctx.accounts.wallet {
+            emit_cpi!(wallet.get_edit_event(
+                wallet.key(),
+                ctx.accounts.order.payment_mint,
+                buy_value,
+                WalletEditType::Fill,
+            ));
+        }
+    }
 
-    // close order account
+    // close the order once its whole size is filled
     let size = ctx.accounts.order.size;
+    let price = ctx.accounts.order.price;
     let payment_mint = ctx.accounts.order.payment_mint;
-    let clock = Clock::get()?;
 
     let new_size = size - amount;
+    if new_size == 0 && ctx.accounts.order.escrowed {
+        ctx.accounts.close_escrow(signer_seeds)?;
+    }
     Order::edit_order(
         &mut ctx.accounts.order,
-        buy_price,
+        price,
         payment_mint,
         new_size,
         clock.unix_timestamp,
     );
     if new_size == 0 {
+        ctx.accounts.market.remove_open_order();
         ctx.accounts.order.state = OrderState::Closed.into();
         emit_cpi!(Order::get_edit_event(
             &mut ctx.accounts.order.clone(),
@@ -471,6 +918,15 @@ pub fn handler<'info>(
         ctx.accounts
             .order
             .close(ctx.accounts.maker.to_account_info())?;
+    } else {
+        // orders with size left stay open for other takers, keeping their delegate or escrow
+        ctx.accounts.order.state = OrderState::Partial.into();
+        emit_cpi!(Order::get_edit_event(
+            &mut ctx.accounts.order.clone(),
+            ctx.accounts.order.key(),
+            ctx.accounts.market.market_identifier,
+            OrderEditType::Fill,
+        ));
     }
 
     Ok(())
@@ -6,15 +6,21 @@ use anchor_spl::{
     },
 };
 use wen_new_standard::cpi::{accounts::FreezeDelegatedAccount, freeze_mint_account};
-use mpl_token_metadata::accounts::Metadata;
 
 // use spl_token_group_interface::state::TokenGroupMember;
 
 use crate::{
     errors::MarketError,
+    instructions::auction::AuctionData,
     state::*,
     utils::{
-        get_bump_in_seed_form, metaplex::pnft::utils::get_is_pnft, mplx_transfer::{ExtraTransferParams, MetaplexAdditionalTransferAccounts}, parse_remaining_accounts_pnft, verify_wns_mint
+        get_bump_in_seed_form, get_verified_metadata,
+        metaplex::pnft::{
+            delegate::{delegate_pnft, get_revoke_args, revoke_pnft, DelegateMetaplexNft},
+            utils::{get_delegate, get_is_pnft},
+        },
+        parse_remaining_accounts_pnft, transfer_nft_with_hook, verify_mint_verification,
+        verify_wns_mint, ParsedRemainingAccounts,
     },
 };
 
@@ -24,6 +30,16 @@ pub struct ListData {
     pub payment_mint: Pubkey,
     pub price: u64,
     pub size: u64,
+    pub expiry: Option<i64>,
+    /// dutch auctions decline from price to end_price by end_time
+    pub end_price: Option<u64>,
+    pub end_time: Option<i64>,
+    /// english auctions start at price as the reserve and are settled by settle_auction
+    pub auction: Option<AuctionData>,
+    /// private listings can only be filled by this wallet
+    pub taker: Option<Pubkey>,
+    /// escrow listings move the nft into a token account of the order instead of delegating it
+    pub escrow: Option<bool>,
 }
 
 #[derive(Accounts)]
@@ -33,6 +49,7 @@ pub struct ListNft<'info> {
     #[account(mut)]
     pub initializer: Signer<'info>,
     #[account(
+        mut,
         constraint = Market::is_active(market.state),
         seeds = [MARKET_SEED,
         market.market_identifier.as_ref()],
@@ -52,6 +69,13 @@ pub struct ListNft<'info> {
         space = 8 + std::mem::size_of::<Order>()
     )]
     pub order: Box<Account<'info, Order>>,
+    #[account(
+        seeds = [ORDER_EPOCH_SEED,
+        initializer.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: seeds constraint, empty until the initializer first cancels all their orders
+    pub order_epoch: UncheckedAccount<'info>,
     #[account(
         mint::token_program = nft_token_program
     )]
@@ -63,6 +87,15 @@ pub struct ListNft<'info> {
         associated_token::token_program = nft_token_program
     )]
     pub initializer_nft_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// only needed for escrow listings
+    #[account(
+        init_if_needed,
+        payer = initializer,
+        associated_token::mint = nft_mint,
+        associated_token::authority = order,
+        associated_token::token_program = nft_token_program
+    )]
+    pub order_nft_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     /// CHECK: checked by constraint and in cpi
     #[account(address = sysvar::instructions::id())]
     pub sysvar_instructions: UncheckedAccount<'info>,
@@ -78,6 +111,80 @@ impl<'info> ListNft<'info> {
     /*
         Metaplex Delegate Instructions
     */
+    fn metaplex_nft_delegate(&self, size: u64) -> Result<()> {
+        let delegate_cpi = CpiContext::new(
+            self.nft_token_program.to_account_info(),
+            Approve {
+                to: self.initializer_nft_ta.to_account_info(),
+                authority: self.initializer.to_account_info(),
+                delegate: self.order.to_account_info(),
+            },
+        );
+
+        approve(delegate_cpi, size)
+    }
+
+    // revokes any existing delegate before adding the order as sale delegate
+    fn metaplex_pnft_delegate(
+        &self,
+        size: u64,
+        metadata: AccountInfo<'info>,
+        edition: AccountInfo<'info>,
+        parsed_accounts: ParsedRemainingAccounts<'info>,
+    ) -> Result<()> {
+        let pnft_params = parsed_accounts.pnft_params;
+        let owner_token_record = pnft_params
+            .owner_token_record
+            .clone()
+            .ok_or(MarketError::WrongAccount)?;
+
+        if let Some(existing_delegate) = get_delegate(&owner_token_record) {
+            let existing_delegate_params = parsed_accounts
+                .existing_delegate_params
+                .ok_or(MarketError::WrongAccount)?;
+            if existing_delegate_params.existing_delegate.key() != existing_delegate.address {
+                return Err(MarketError::WrongAccount.into());
+            }
+
+            let revoke_cpi = CpiContext::new(
+                self.nft_program.to_account_info(),
+                DelegateMetaplexNft {
+                    delegate: existing_delegate_params.existing_delegate,
+                    authority: self.initializer.to_account_info(),
+                    payer: self.initializer.to_account_info(),
+                    token: self.initializer_nft_ta.to_account_info(),
+                    mint: self.nft_mint.to_account_info(),
+                    metadata: metadata.clone(),
+                    edition: edition.clone(),
+                    system_program: self.system_program.to_account_info(),
+                    instructions: self.sysvar_instructions.to_account_info(),
+                    token_program: self.nft_token_program.to_account_info(),
+                },
+            );
+            revoke_pnft(
+                revoke_cpi,
+                pnft_params.clone().into(),
+                get_revoke_args(existing_delegate.role),
+            )?;
+        }
+
+        let delegate_cpi = CpiContext::new(
+            self.nft_program.to_account_info(),
+            DelegateMetaplexNft {
+                delegate: self.order.to_account_info(),
+                authority: self.initializer.to_account_info(),
+                payer: self.initializer.to_account_info(),
+                token: self.initializer_nft_ta.to_account_info(),
+                mint: self.nft_mint.to_account_info(),
+                metadata,
+                edition,
+                system_program: self.system_program.to_account_info(),
+                instructions: self.sysvar_instructions.to_account_info(),
+                token_program: self.nft_token_program.to_account_info(),
+            },
+        );
+        delegate_pnft(delegate_cpi, pnft_params.into(), size)
+    }
 
     /*
         Compressed Delegate Instructions
@@ -102,24 +209,30 @@ impl<'info> ListNft<'info> {
 
         approve(
             delegate_cpi.with_remaining_accounts(remaining_accounts),
-            size, // supply = 1
+            size,
         )
     }
 
-    fn verify_mint(&self, verification_account: &AccountInfo<'info>) -> Result<()> {
-        if verification_account.key()
-            != get_verification_pda(self.nft_mint.key(), self.market.key()).0
-        {
-            return Err(MarketError::InvalidNft.into());
-        }
-        let verification =
-            MintVerification::deserialize(&mut &verification_account.data.borrow_mut()[8..])?;
-
-        if verification.verified != 1 {
-            return Err(MarketError::InvalidNft.into());
-        }
+    /*
+        Escrow Instructions
+    */
+    fn escrow_nft(&self, size: u64, hook_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let order_nft_ta = self
+            .order_nft_ta
+            .as_ref()
+            .ok_or(MarketError::WrongAccount)?;
 
-        Ok(())
+        transfer_nft_with_hook(
+            &self.nft_token_program.to_account_info(),
+            &self.initializer_nft_ta.to_account_info(),
+            &self.nft_mint.to_account_info(),
+            &order_nft_ta.to_account_info(),
+            &self.initializer.to_account_info(),
+            size,
+            self.nft_mint.decimals,
+            hook_accounts,
+            &[],
+        )
     }
 }
 
@@ -135,7 +248,38 @@ pub fn handler<'info>(
     let remaining_accounts = ctx.remaining_accounts.to_vec();
 
     let clock = Clock::get()?;
-    // create a new order with size 1
+    let expiry = data.expiry.unwrap_or(0);
+    if expiry != 0 && expiry <= clock.unix_timestamp {
+        return Err(MarketError::OrderExpired.into());
+    }
+    if !ctx.accounts.market.is_payment_mint_allowed(&data.payment_mint) {
+        return Err(MarketError::PaymentMintNotAllowed.into());
+    }
+    let (end_price, end_time) = match (data.end_price, data.end_time) {
+        (Some(end_price), Some(end_time))
+            if end_price > 0 && end_price < data.price && end_time > clock.unix_timestamp =>
+        {
+            (end_price, end_time)
+        }
+        (None, None) => (0, 0),
+        _ => return Err(MarketError::InvalidAuctionParams.into()),
+    };
+    let side = match &data.auction {
+        Some(auction)
+            if end_time == 0
+                && expiry == 0
+                && data.size == 1
+                && data.taker.is_none()
+                && auction.end_time > clock.unix_timestamp
+                && auction.min_increment > 0
+                && auction.time_extension >= 0 =>
+        {
+            OrderSide::Auction
+        }
+        Some(_) => return Err(MarketError::InvalidAuctionParams.into()),
+        None => OrderSide::Sell,
+    };
+    // create a new order
     Order::init(
         &mut ctx.accounts.order,
         ctx.accounts.market.key(),
@@ -144,12 +288,65 @@ pub fn handler<'info>(
         ctx.accounts.nft_mint.key(),
         data.payment_mint,
         clock.unix_timestamp,
-        OrderSide::Sell.into(),
-        data.size, // always 1
+        side.into(),
+        data.size,
         data.price,
         OrderState::Ready.into(),
         true,
+        expiry,
     );
+    ctx.accounts.order.epoch = OrderEpoch::get_current(&ctx.accounts.order_epoch)?;
+    ctx.accounts.market.add_open_order();
+    ctx.accounts.order.end_price = end_price;
+    ctx.accounts.order.end_time = end_time;
+    ctx.accounts.order.taker = data.taker.unwrap_or_default();
+    if let Some(auction) = data.auction {
+        ctx.accounts.order.end_time = auction.end_time;
+        ctx.accounts.order.min_increment = auction.min_increment;
+        ctx.accounts.order.time_extension = auction.time_extension;
+    }
+
+    if data.escrow.unwrap_or(false) {
+        // english auctions keep the nft delegated until settled
+        if ctx.accounts.order.is_auction() {
+            return Err(MarketError::InvalidAuctionParams.into());
+        }
+        ctx.accounts.order.escrowed = true;
+
+        if *nft_token_program_key == TOKEN_PID && *nft_program_key == METAPLEX_PID {
+            // Remaining Accounts 0 for metadata, 2 for manual verification
+            let nft_metadata = remaining_accounts.first().ok_or(MarketError::WrongAccount)?;
+            let verification_account =
+                remaining_accounts.get(2).ok_or(MarketError::WrongAccount)?;
+            verify_mint_verification(
+                verification_account,
+                ctx.accounts.nft_mint.key(),
+                ctx.accounts.market.key(),
+            )?;
+
+            // programmable nfts can only move through metaplex
+            let parsed_metadata =
+                get_verified_metadata(nft_metadata, &ctx.accounts.nft_mint.key())?;
+            if get_is_pnft(&parsed_metadata) {
+                return Err(MarketError::UnsupportedNft.into());
+            }
+            ctx.accounts.escrow_nft(data.size, &[])?;
+        } else if *nft_token_program_key == TOKEN_EXT_PID && *nft_program_key != WNS_PID {
+            // Any remaining accounts are for potential transfer hook
+            ctx.accounts.escrow_nft(data.size, &remaining_accounts)?;
+        } else {
+            // wns transfers need a royalty approval
+            return Err(MarketError::UnsupportedNft.into());
+        }
+
+        emit_cpi!(Order::get_edit_event(
+            &mut ctx.accounts.order.clone(),
+            ctx.accounts.order.key(),
+            ctx.accounts.market.market_identifier,
+            OrderEditType::Init,
+        ));
+        return Ok(());
+    }
 
     let bump = &get_bump_in_seed_form(&ctx.bumps.order);
     let signer_seeds: &[&[&[u8]]; 1] = &[&[
@@ -164,48 +361,43 @@ pub fn handler<'info>(
         // Check if its metaplex or not
         if *nft_program_key == METAPLEX_PID {
             // If it's metaplex, we parse the first remaining account as nft_metadata
-            let nft_metadata = remaining_accounts.get(0).unwrap();
-            let nft_edition = remaining_accounts.get(1).unwrap();
+            let nft_metadata = remaining_accounts.first().unwrap();
 
             // Must use manual verify for Metaplex
             let verification_account = remaining_accounts.get(2).unwrap();
 
-            ctx.accounts.verify_mint(verification_account)?;
-
-            // The remaining metadata accounts are (PNFT ONLY):
-            // 0 owner_token_record or default,
-            // 1 authorization_rules or default,
-            // 2 authorization_rules_program or default,
-            // 3 destination_token_record or default,
-            // 4 delegate record or default,
-            // 5 existing delegate or default,
-            // 6 existing delegate record or default
-
-            let (_, extra_remaining_accounts) = remaining_accounts.split_at(3);
-
-            let parsed_accounts =
-                parse_remaining_accounts_pnft(extra_remaining_accounts.to_vec(), true, None);
-            let pnft_params = parsed_accounts.pnft_params;
-
-            let parsed_metadata = Metadata::safe_deserialize(&nft_metadata.data.borrow())?;
-            let is_pnft = get_is_pnft(&parsed_metadata);
-
-            let extra_accounts = ExtraTransferParams {
-                owner_token_record: pnft_params.owner_token_record,
-                dest_token_record: pnft_params.destination_token_record,
-                authorization_rules: pnft_params.authorization_rules,
-                authorization_data: None,
-                authorization_rules_program: pnft_params.authorization_rules_program,
-            };
-
-            let transfer_params = MetaplexAdditionalTransferAccounts {
-                metadata: nft_metadata.to_account_info(),
-                edition: nft_edition.to_account_info(),
-                extra_accounts,
-            };
-            // TODO Delegate Metaplex NFT
-            return Err(MarketError::UnsupportedNft.into());
-            // ctx.accounts.metaplex_nft_transfer(is_pnft, transfer_params)?;
+            verify_mint_verification(
+                verification_account,
+                ctx.accounts.nft_mint.key(),
+                ctx.accounts.market.key(),
+            )?;
+
+            let parsed_metadata =
+                get_verified_metadata(nft_metadata, &ctx.accounts.nft_mint.key())?;
+            if get_is_pnft(&parsed_metadata) {
+                let nft_edition = remaining_accounts.get(1).unwrap();
+
+                // The remaining metadata accounts are (PNFT ONLY):
+                // 0 owner_token_record or default,
+                // 1 authorization_rules or default,
+                // 2 authorization_rules_program or default,
+                // 3 destination_token_record or default,
+                // 4 delegate record or default,
+                // 5 existing delegate or default,
+                // 6 existing delegate record or default
+                let (_, extra_remaining_accounts) = remaining_accounts.split_at(3);
+                let parsed_accounts =
+                    parse_remaining_accounts_pnft(extra_remaining_accounts.to_vec(), true, None);
+
+                ctx.accounts.metaplex_pnft_delegate(
+                    data.size,
+                    nft_metadata.to_account_info(),
+                    nft_edition.to_account_info(),
+                    parsed_accounts,
+                )?;
+            } else {
+                ctx.accounts.metaplex_nft_delegate(data.size)?;
+            }
         } else {
             // Transfer compressed NFT
             // TODO
@@ -224,8 +416,7 @@ pub fn handler<'info>(
         // Pass in RA for delegate as needed
         ctx.accounts.token22_nft_delegate(data.size, token22_ra.clone())?;
     } else if *nft_token_program_key == BUBBLEGUM_PID {
-        // Transfer compressed NFT
-        // TODO
+        // Compressed NFTs are handled by list_compressed
         return Err(MarketError::UnsupportedNft.into());
     } else {
         // ERROR
@@ -0,0 +1,709 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::spl_token_2022::instruction::transfer_checked as transfer_2022,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use spl_transfer_hook_interface::onchain::add_extra_accounts_for_execute_cpi;
+use wen_new_standard::{
+    cpi::{accounts::ApproveTransfer, approve_transfer},
+    utils::get_mint_metadata,
+    ROYALTY_BASIS_POINTS_FIELD,
+};
+
+use crate::{
+    errors::MarketError,
+    state::*,
+    utils::{
+        create_ata, get_amount_from_bp, get_bump_in_seed_form, get_editions_royalties,
+        get_has_royalty_field, get_verified_metadata, lamport_transfer,
+        token_extensions::WnsApprovalAccounts, verify_merkle_mint, verify_mint_verification,
+    },
+};
+
+#[derive(Accounts)]
+#[instruction()]
+#[event_cpi]
+pub struct MatchOrders<'info> {
+    /// anyone can match crossed orders and earn the keeper tip
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    #[account(
+        mut,
+        constraint = Market::is_active(market.state),
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(
+        mut,
+        constraint = Order::is_active(buy_order.state),
+        constraint = buy_order.side == <OrderSide as Into<u8>>::into(OrderSide::Buy),
+        constraint = !buy_order.wallet_backed,
+        constraint = buy_order.market == market.key(),
+        constraint = buy_order.payment_mint == sell_order.payment_mint,
+        seeds = [ORDER_SEED,
+        buy_order.nonce.as_ref(),
+        buy_order.market.as_ref(),
+        buy_order.owner.as_ref()],
+        bump,
+    )]
+    pub buy_order: Box<Account<'info, Order>>,
+    #[account(
+        mut,
+        constraint = Order::is_active(sell_order.state),
+        constraint = sell_order.side == <OrderSide as Into<u8>>::into(OrderSide::Sell),
+        constraint = sell_order.market == market.key(),
+        constraint = sell_order.nft_mint == nft_mint.key(),
+        constraint = !sell_order.escrowed,
+        seeds = [ORDER_SEED,
+        sell_order.nonce.as_ref(),
+        sell_order.market.as_ref(),
+        sell_order.owner.as_ref()],
+        bump,
+    )]
+    pub sell_order: Box<Account<'info, Order>>,
+    #[account(mut, constraint = buyer.key() == buy_order.owner)]
+    /// CHECK: constraint check
+    pub buyer: UncheckedAccount<'info>,
+    #[account(mut, constraint = seller.key() == sell_order.owner)]
+    /// CHECK: constraint check
+    pub seller: UncheckedAccount<'info>,
+    #[account(
+        seeds = [ORDER_EPOCH_SEED,
+        buyer.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: seeds constraint, empty until the buyer first cancels all their orders
+    pub buyer_order_epoch: UncheckedAccount<'info>,
+    #[account(
+        seeds = [ORDER_EPOCH_SEED,
+        seller.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: seeds constraint, empty until the seller first cancels all their orders
+    pub seller_order_epoch: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub nft_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = nft_mint,
+        token::authority = seller,
+        token::token_program = nft_token_program,
+    )]
+    pub seller_nft_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub buyer_nft_ta: UncheckedAccount<'info>,
+    #[account(mut, constraint = fee_recipient.key() == market.fee_recipient.key())]
+    /// CHECK: constraint check
+    pub fee_recipient: UncheckedAccount<'info>,
+    /// payment accounts are left out for orders in native SOL
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub fee_recipient_ta: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        token::mint = buy_order.payment_mint,
+        token::authority = buy_order,
+        token::token_program = payment_token_program,
+    )]
+    pub order_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub seller_payment_ta: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub buyer_payment_ta: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub keeper_payment_ta: Option<UncheckedAccount<'info>>,
+    #[account(constraint = payment_mint.key() == buy_order.payment_mint)]
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+    pub nft_token_program: Interface<'info, TokenInterface>,
+    /// CHECK: checked by constraint and in cpi
+    pub nft_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> MatchOrders<'info> {
+    /// pays the recipient out of the bid escrow
+    #[inline(never)]
+    fn transfer_payment(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        recipient: AccountInfo<'info>,
+        recipient_ta: Option<AccountInfo<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        if self.buy_order.is_native() {
+            return lamport_transfer(self.buy_order.to_account_info(), recipient, amount);
+        }
+
+        let (
+            Some(order_payment_ta),
+            Some(recipient_ta),
+            Some(payment_mint),
+            Some(payment_token_program),
+        ) = (
+            &self.order_payment_ta,
+            recipient_ta,
+            &self.payment_mint,
+            &self.payment_token_program,
+        )
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            payment_token_program.to_account_info(),
+            TransferChecked {
+                from: order_payment_ta.to_account_info(),
+                to: recipient_ta,
+                authority: self.buy_order.to_account_info(),
+                mint: payment_mint.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)
+    }
+
+    /// native orders pay wallets directly and need no token accounts
+    #[inline(never)]
+    fn create_payment_ata(
+        &self,
+        ata: Option<AccountInfo<'info>>,
+        owner: &AccountInfo<'info>,
+    ) -> Result<()> {
+        if self.buy_order.is_native() {
+            return Ok(());
+        }
+
+        let (Some(ata), Some(payment_mint), Some(payment_token_program)) =
+            (ata, &self.payment_mint, &self.payment_token_program)
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        create_ata(
+            &ata,
+            &self.keeper.to_account_info(),
+            &payment_mint.to_account_info(),
+            owner,
+            &self.system_program.to_account_info(),
+            &payment_token_program.to_account_info(),
+        )
+    }
+
+    // WNS Pre-Transfer Approval
+    #[inline(never)]
+    fn approve_wns_transfer(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        value: u64,
+        wns_accounts: WnsApprovalAccounts<'info>,
+    ) -> Result<()> {
+        if wns_accounts.payment_mint.key() != self.buy_order.payment_mint {
+            return Err(MarketError::WrongAccount.into());
+        }
+        self.create_payment_ata(
+            Some(wns_accounts.distribution_token_account.to_account_info()),
+            &wns_accounts.distribution_account,
+        )?;
+
+        // native royalties are a system transfer the order account can't make,
+        // so the keeper pays them after being reimbursed from the escrow
+        let (authority, distribution_token_account, authority_token_account, payment_token_program) =
+            if self.buy_order.is_native() {
+                (self.keeper.to_account_info(), None, None, None)
+            } else {
+                (
+                    self.buy_order.to_account_info(),
+                    Some(wns_accounts.distribution_token_account.to_account_info()),
+                    self.order_payment_ta
+                        .as_ref()
+                        .map(|order_payment_ta| order_payment_ta.to_account_info()),
+                    self.payment_token_program
+                        .as_ref()
+                        .map(|payment_token_program| payment_token_program.to_account_info()),
+                )
+            };
+
+        let cpi_accounts = ApproveTransfer {
+            payer: self.keeper.to_account_info(),
+            authority,
+            mint: self.nft_mint.to_account_info(),
+            approve_account: wns_accounts.approval_account.to_account_info(),
+            payment_mint: wns_accounts.payment_mint.to_account_info(),
+            distribution_token_account,
+            authority_token_account,
+            distribution_account: wns_accounts.distribution_account.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            distribution_program: wns_accounts.distribution_program.to_account_info(),
+            token_program: self.nft_token_program.to_account_info(),
+            payment_token_program,
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.nft_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        approve_transfer(cpi_ctx, value)
+    }
+
+    /// the sell order is the delegate of the listed token account
+    /// any hook accounts are forwarded to the token program
+    #[inline(never)]
+    fn nft_transfer(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        amount: u64,
+        hook_accounts: Vec<AccountInfo<'info>>,
+    ) -> Result<()> {
+        let authority_info = self.sell_order.to_account_info();
+
+        let mut transfer_ix = transfer_2022(
+            self.nft_token_program.key,
+            &self.seller_nft_ta.key(),
+            &self.nft_mint.key(),
+            self.buyer_nft_ta.key,
+            authority_info.key,
+            &[],
+            amount,
+            self.nft_mint.decimals,
+        )?;
+
+        let mut account_infos = vec![
+            self.seller_nft_ta.to_account_info(),
+            self.nft_mint.to_account_info(),
+            self.buyer_nft_ta.to_account_info(),
+            authority_info.clone(),
+        ];
+
+        if let Some(hook_program) = hook_accounts.last() {
+            // transfer hook
+            add_extra_accounts_for_execute_cpi(
+                &mut transfer_ix,
+                &mut account_infos,
+                hook_program.key,
+                self.seller_nft_ta.to_account_info(),
+                self.nft_mint.to_account_info(),
+                self.buyer_nft_ta.to_account_info(),
+                authority_info,
+                amount,
+                &hook_accounts,
+            )?;
+        }
+
+        anchor_lang::solana_program::program::invoke_signed(
+            &transfer_ix,
+            &account_infos,
+            signer_seeds,
+        )?;
+
+        Ok(())
+    }
+}
+
+/// settle a bid against a listing it crosses, at the listing price
+/// the seller is paid the ask less the maker fee and royalties, the taker fee comes out of the bid escrow
+/// the bid surplus is split between the keeper and a refund to the bidder per the market keeper tip
+/// remaining accounts are those fill_order takes for the mint, programmable nfts are not supported
+#[inline(never)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, MatchOrders<'info>>,
+    merkle_proof: Option<Vec<[u8; 32]>>,
+) -> Result<()> {
+    let nft_token_program_key = &ctx.accounts.nft_token_program.key.to_string().clone();
+    let nft_program_key = &ctx.accounts.nft_program.key.to_string().clone();
+    let mut remaining_accounts = ctx.remaining_accounts.to_vec();
+
+    let clock = Clock::get()?;
+    if ctx.accounts.buy_order.is_expired(clock.unix_timestamp)
+        || ctx.accounts.sell_order.is_expired(clock.unix_timestamp)
+    {
+        return Err(MarketError::OrderExpired.into());
+    }
+    if ctx
+        .accounts
+        .buy_order
+        .is_stale(OrderEpoch::get_current(&ctx.accounts.buyer_order_epoch)?)
+        || ctx
+            .accounts
+            .sell_order
+            .is_stale(OrderEpoch::get_current(&ctx.accounts.seller_order_epoch)?)
+    {
+        return Err(MarketError::StaleOrder.into());
+    }
+    if ctx.accounts.sell_order.is_private()
+        && ctx.accounts.sell_order.taker != ctx.accounts.buyer.key()
+    {
+        return Err(MarketError::InvalidTaker.into());
+    }
+    if !ctx
+        .accounts
+        .market
+        .is_payment_mint_allowed(&ctx.accounts.buy_order.payment_mint)
+    {
+        return Err(MarketError::PaymentMintNotAllowed.into());
+    }
+
+    let is_collection_bid = ctx.accounts.buy_order.is_collection_bid();
+    if !is_collection_bid && ctx.accounts.buy_order.nft_mint != ctx.accounts.nft_mint.key() {
+        return Err(MarketError::InvalidNft.into());
+    }
+    if ctx.accounts.buy_order.is_trait_bid() {
+        verify_merkle_mint(
+            ctx.accounts.buy_order.merkle_root,
+            merkle_proof,
+            ctx.accounts.nft_mint.key(),
+        )?;
+    }
+    // programmable nfts keep their token account frozen
+    if ctx.accounts.seller_nft_ta.is_frozen() {
+        return Err(MarketError::UnsupportedNft.into());
+    }
+
+    // the listing is the resting order and sets the price
+    let ask = ctx
+        .accounts
+        .sell_order
+        .get_current_price(clock.unix_timestamp);
+    let bid = ctx.accounts.buy_order.price;
+    let amount = ctx
+        .accounts
+        .buy_order
+        .size
+        .min(ctx.accounts.sell_order.size);
+    let value = amount.checked_mul(ask).ok_or(MarketError::AmountOverflow)?;
+    let bid_value = amount.checked_mul(bid).ok_or(MarketError::AmountOverflow)?;
+
+    let fill_fees = if ctx.accounts.sell_order.fees_on {
+        ctx.accounts.market.get_fill_fees(value, false)?
+    } else {
+        FillFees::default()
+    };
+    // maker rebates are paid out of the taker fee
+    let fee_amount = u64::try_from(
+        fill_fees
+            .maker_fee
+            .saturating_add_unsigned(fill_fees.taker_fee),
+    )
+    .map_err(|_| MarketError::AmountUnderflow)?;
+    let surplus = bid_value
+        .checked_sub(value)
+        .and_then(|surplus| surplus.checked_sub(fill_fees.taker_fee))
+        .ok_or(MarketError::OrdersNotCrossed)?;
+    let keeper_tip = get_amount_from_bp(surplus, ctx.accounts.market.keeper_tip_bps.into())?;
+    // a matched bid pays no maker fee, the fee escrowed for the matched units goes back to the bidder
+    let buy_order = &ctx.accounts.buy_order;
+    let bid_maker_fee = buy_order
+        .get_bid_escrow(buy_order.size)?
+        .checked_sub(buy_order.get_bid_escrow(buy_order.size - amount)?)
+        .and_then(|released| released.checked_sub(bid_value))
+        .ok_or(MarketError::AmountUnderflow)?;
+    let refund = surplus - keeper_tip + bid_maker_fee;
+    let mut seller_received_amount = value
+        .checked_add_signed(-fill_fees.maker_fee)
+        .ok_or(MarketError::AmountUnderflow)?;
+    let mut royalties_paid: u64 = 0;
+
+    let buy_bump = &get_bump_in_seed_form(&ctx.bumps.buy_order);
+    let sell_bump = &get_bump_in_seed_form(&ctx.bumps.sell_order);
+    let signer_seeds: &[&[&[u8]]; 2] = &[
+        &[
+            ORDER_SEED,
+            ctx.accounts.buy_order.nonce.as_ref(),
+            ctx.accounts.buy_order.market.as_ref(),
+            ctx.accounts.buy_order.owner.as_ref(),
+            buy_bump,
+        ][..],
+        &[
+            ORDER_SEED,
+            ctx.accounts.sell_order.nonce.as_ref(),
+            ctx.accounts.sell_order.market.as_ref(),
+            ctx.accounts.sell_order.owner.as_ref(),
+            sell_bump,
+        ][..],
+    ];
+
+    let keeper = ctx.accounts.keeper.to_account_info();
+    let buyer = ctx.accounts.buyer.to_account_info();
+    let seller = ctx.accounts.seller.to_account_info();
+    let fee_recipient = ctx.accounts.fee_recipient.to_account_info();
+    let seller_payment_ta = ctx
+        .accounts
+        .seller_payment_ta
+        .as_ref()
+        .map(|seller_payment_ta| seller_payment_ta.to_account_info());
+    let buyer_payment_ta = ctx
+        .accounts
+        .buyer_payment_ta
+        .as_ref()
+        .map(|buyer_payment_ta| buyer_payment_ta.to_account_info());
+    let keeper_payment_ta = ctx
+        .accounts
+        .keeper_payment_ta
+        .as_ref()
+        .map(|keeper_payment_ta| keeper_payment_ta.to_account_info());
+    let fee_recipient_ta = ctx
+        .accounts
+        .fee_recipient_ta
+        .as_ref()
+        .map(|fee_recipient_ta| fee_recipient_ta.to_account_info());
+
+    create_ata(
+        &ctx.accounts.buyer_nft_ta.to_account_info(),
+        &keeper,
+        &ctx.accounts.nft_mint.to_account_info(),
+        &buyer,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.nft_token_program.to_account_info(),
+    )?;
+
+    // Transfer NFT
+    if *nft_token_program_key == TOKEN_PID {
+        if *nft_program_key != METAPLEX_PID {
+            return Err(MarketError::UnsupportedNft.into());
+        }
+        // Remaining Accounts 0-2 for metadata, edition and manual verification
+        let nft_metadata = remaining_accounts
+            .first()
+            .ok_or(MarketError::WrongAccount)?;
+        let verification_account = remaining_accounts.get(2).ok_or(MarketError::WrongAccount)?;
+        verify_mint_verification(
+            verification_account,
+            ctx.accounts.nft_mint.key(),
+            ctx.accounts.market.key(),
+        )?;
+
+        // Remaining Accounts 3+ are a creator and creator payment ta per creator
+        let parsed_metadata = get_verified_metadata(nft_metadata, &ctx.accounts.nft_mint.key())?;
+        let royalties = get_amount_from_bp(value, parsed_metadata.seller_fee_basis_points.into())?;
+        for (i, creator) in parsed_metadata.creators.iter().flatten().enumerate() {
+            let creator_account = remaining_accounts
+                .get(3 + 2 * i)
+                .ok_or(MarketError::WrongAccount)?;
+            let creator_payment_ta = remaining_accounts
+                .get(4 + 2 * i)
+                .ok_or(MarketError::WrongAccount)?;
+            if *creator_account.key != creator.address {
+                return Err(MarketError::WrongAccount.into());
+            }
+
+            let creator_amount = get_amount_from_bp(royalties, u128::from(creator.share) * 100)?;
+            if creator_amount == 0 {
+                continue;
+            }
+            ctx.accounts
+                .create_payment_ata(Some(creator_payment_ta.to_account_info()), creator_account)?;
+            ctx.accounts.transfer_payment(
+                signer_seeds,
+                creator_account.to_account_info(),
+                Some(creator_payment_ta.to_account_info()),
+                creator_amount,
+            )?;
+            royalties_paid = royalties_paid
+                .checked_add(creator_amount)
+                .ok_or(MarketError::AmountOverflow)?;
+        }
+        seller_received_amount = seller_received_amount
+            .checked_sub(royalties_paid)
+            .ok_or(MarketError::AmountUnderflow)?;
+
+        ctx.accounts
+            .nft_transfer(signer_seeds, amount, Vec::new())?;
+    } else if *nft_token_program_key == TOKEN_EXT_PID {
+        if *nft_program_key == WNS_PID {
+            // Remaining Accounts 0-4 for approval, wns listings were verified when listed
+            if remaining_accounts.len() < 5 {
+                return Err(MarketError::WrongAccount.into());
+            }
+            let hook_accounts = remaining_accounts.split_off(5);
+            let wns_accounts = WnsApprovalAccounts {
+                approval_account: remaining_accounts[0].to_account_info(),
+                distribution_account: remaining_accounts[1].to_account_info(),
+                distribution_token_account: remaining_accounts[2].to_account_info(),
+                distribution_program: remaining_accounts[3].to_account_info(),
+                payment_mint: remaining_accounts[4].to_account_info(),
+            };
+
+            let mint_metadata = get_mint_metadata(&mut ctx.accounts.nft_mint.to_account_info())?;
+            let royalty_basis_points = mint_metadata
+                .additional_metadata
+                .iter()
+                .find(|(key, _)| key == ROYALTY_BASIS_POINTS_FIELD)
+                .map(|(_, value)| value)
+                .map(|value| u64::from_str(value).unwrap())
+                .unwrap_or(0);
+            let royalties = get_amount_from_bp(value, royalty_basis_points.into())?;
+            seller_received_amount = seller_received_amount
+                .checked_sub(royalties)
+                .ok_or(MarketError::AmountUnderflow)?;
+            royalties_paid = royalties;
+
+            if ctx.accounts.buy_order.is_native() {
+                lamport_transfer(
+                    ctx.accounts.buy_order.to_account_info(),
+                    keeper.clone(),
+                    royalties,
+                )?;
+            }
+
+            // Handles royalties
+            ctx.accounts
+                .approve_wns_transfer(signer_seeds, value, wns_accounts)?;
+            ctx.accounts
+                .nft_transfer(signer_seeds, amount, hook_accounts)?;
+        } else {
+            // Remaining Account 0 for the mint verification when matching a collection bid
+            if is_collection_bid {
+                let verification_account = remaining_accounts
+                    .first()
+                    .ok_or(MarketError::WrongAccount)?;
+                verify_mint_verification(
+                    verification_account,
+                    ctx.accounts.nft_mint.key(),
+                    ctx.accounts.market.key(),
+                )?;
+                remaining_accounts.remove(0);
+            }
+
+            if *nft_program_key == EDITIONS_PID {
+                // Remaining Account 0 for the editions deployment, then a creator and creator payment ta per creator
+                let editions_deployment = remaining_accounts
+                    .first()
+                    .ok_or(MarketError::WrongAccount)?;
+                let editions_royalties = get_editions_royalties(
+                    &mut ctx.accounts.nft_mint.to_account_info(),
+                    editions_deployment,
+                )?;
+                let royalties =
+                    get_amount_from_bp(value, editions_royalties.royalty_basis_points.into())?;
+
+                for (i, creator) in editions_royalties.creators.iter().enumerate() {
+                    let creator_account = remaining_accounts
+                        .get(1 + 2 * i)
+                        .ok_or(MarketError::WrongAccount)?;
+                    let creator_payment_ta = remaining_accounts
+                        .get(2 + 2 * i)
+                        .ok_or(MarketError::WrongAccount)?;
+                    if *creator_account.key != creator.address {
+                        return Err(MarketError::WrongAccount.into());
+                    }
+
+                    let creator_amount =
+                        get_amount_from_bp(royalties, u128::from(creator.share) * 100)?;
+                    if creator_amount == 0 {
+                        continue;
+                    }
+                    ctx.accounts.create_payment_ata(
+                        Some(creator_payment_ta.to_account_info()),
+                        creator_account,
+                    )?;
+                    ctx.accounts.transfer_payment(
+                        signer_seeds,
+                        creator_account.to_account_info(),
+                        Some(creator_payment_ta.to_account_info()),
+                        creator_amount,
+                    )?;
+                    royalties_paid = royalties_paid
+                        .checked_add(creator_amount)
+                        .ok_or(MarketError::AmountOverflow)?;
+                }
+                seller_received_amount = seller_received_amount
+                    .checked_sub(royalties_paid)
+                    .ok_or(MarketError::AmountUnderflow)?;
+
+                remaining_accounts =
+                    remaining_accounts.split_off(1 + 2 * editions_royalties.creators.len());
+            } else if get_has_royalty_field(&mut ctx.accounts.nft_mint.to_account_info()) {
+                // mints carrying royalties must go through their royalty path
+                return Err(MarketError::UnsupportedNft.into());
+            }
+            // Any remaining accounts left are for potential transfer hook (Empty if not expecting hook)
+            ctx.accounts
+                .nft_transfer(signer_seeds, amount, remaining_accounts)?;
+        }
+    } else {
+        return Err(MarketError::UnsupportedNft.into());
+    }
+
+    // Transfer payment
+    ctx.accounts
+        .create_payment_ata(seller_payment_ta.clone(), &seller)?;
+    ctx.accounts.transfer_payment(
+        signer_seeds,
+        seller,
+        seller_payment_ta,
+        seller_received_amount,
+    )?;
+    ctx.accounts
+        .create_payment_ata(fee_recipient_ta.clone(), &fee_recipient)?;
+    ctx.accounts
+        .transfer_payment(signer_seeds, fee_recipient, fee_recipient_ta, fee_amount)?;
+    if keeper_tip > 0 {
+        ctx.accounts
+            .create_payment_ata(keeper_payment_ta.clone(), &keeper)?;
+        ctx.accounts
+            .transfer_payment(signer_seeds, keeper, keeper_payment_ta, keeper_tip)?;
+    }
+    if refund > 0 {
+        ctx.accounts
+            .create_payment_ata(buyer_payment_ta.clone(), &buyer)?;
+        ctx.accounts
+            .transfer_payment(signer_seeds, buyer, buyer_payment_ta, refund)?;
+    }
+
+    emit_cpi!(ctx.accounts.sell_order.get_fill_event(
+        ctx.accounts.sell_order.key(),
+        ctx.accounts.market.market_identifier,
+        ctx.accounts.buyer.key(),
+        ctx.accounts.nft_mint.key(),
+        amount,
+        ask,
+        value,
+        &fill_fees,
+        royalties_paid,
+    ));
+
+    // close each order once its whole size is filled
+    for (order, owner) in [
+        (&mut ctx.accounts.sell_order, &ctx.accounts.seller),
+        (&mut ctx.accounts.buy_order, &ctx.accounts.buyer),
+    ] {
+        let new_size = order.size - amount;
+        let price = order.price;
+        let payment_mint = order.payment_mint;
+        Order::edit_order(order, price, payment_mint, new_size, clock.unix_timestamp);
+        if new_size == 0 {
+            ctx.accounts.market.remove_open_order();
+            order.state = OrderState::Closed.into();
+            emit_cpi!(Order::get_edit_event(
+                &mut order.clone(),
+                order.key(),
+                ctx.accounts.market.market_identifier,
+                OrderEditType::FillAndClose,
+            ));
+            order.close(owner.to_account_info())?;
+        } else {
+            order.state = OrderState::Partial.into();
+            emit_cpi!(Order::get_edit_event(
+                &mut order.clone(),
+                order.key(),
+                ctx.accounts.market.market_identifier,
+                OrderEditType::Fill,
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -1,11 +1,23 @@
 pub mod bid;
+pub mod cancel_all;
 pub mod cancel_bid;
 pub mod cancel_list;
+pub mod close_expired;
+pub mod close_stale;
+pub mod edit;
 pub mod fill;
 pub mod list;
+pub mod match_orders;
+pub mod sweep;
 
 pub use bid::*;
+pub use cancel_all::*;
 pub use cancel_bid::*;
 pub use cancel_list::*;
+pub use close_expired::*;
+pub use close_stale::*;
+pub use edit::*;
 pub use fill::*;
 pub use list::*;
+pub use match_orders::*;
+pub use sweep::*;
@@ -0,0 +1,402 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{
+    errors::MarketError,
+    state::*,
+    utils::{create_ata, get_bump_in_seed_form, get_has_royalty_field, transfer_sol},
+};
+
+/// remaining accounts per swept listing:
+/// order, maker, nft mint, seller nft ta, buyer nft ta, seller payment ta, maker order epoch
+pub const SWEEP_ACCOUNTS_PER_ORDER: usize = 7;
+
+#[derive(Accounts)]
+#[instruction()]
+#[event_cpi]
+pub struct Sweep<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(
+        mut,
+        constraint = Market::is_active(market.state),
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(mut, constraint = fee_recipient.key() == market.fee_recipient.key())]
+    /// CHECK: constraint check
+    pub fee_recipient: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub fee_recipient_ta: Option<UncheckedAccount<'info>>,
+    /// payment accounts are left out when sweeping listings in native SOL
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = taker,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub buyer_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+    pub nft_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> Sweep<'info> {
+    /// pays the recipient from the taker, in lamports when sweeping native SOL listings
+    fn transfer_payment(
+        &self,
+        recipient: AccountInfo<'info>,
+        recipient_ta: AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        if self.payment_mint.is_none() {
+            return transfer_sol(
+                self.taker.to_account_info(),
+                recipient,
+                self.system_program.to_account_info(),
+                None,
+                amount,
+            );
+        }
+
+        let (Some(buyer_payment_ta), Some(payment_mint), Some(payment_token_program)) = (
+            &self.buyer_payment_ta,
+            &self.payment_mint,
+            &self.payment_token_program,
+        ) else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        let cpi_ctx = CpiContext::new(
+            payment_token_program.to_account_info(),
+            TransferChecked {
+                from: buyer_payment_ta.to_account_info(),
+                to: recipient_ta,
+                authority: self.taker.to_account_info(),
+                mint: payment_mint.to_account_info(),
+            },
+        );
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)
+    }
+
+    /// native sweeps pay wallets directly and need no token accounts
+    fn create_payment_ata(
+        &self,
+        ata: &AccountInfo<'info>,
+        owner: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let Some(payment_mint) = &self.payment_mint else {
+            return Ok(());
+        };
+        let Some(payment_token_program) = &self.payment_token_program else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        create_ata(
+            ata,
+            &self.taker.to_account_info(),
+            &payment_mint.to_account_info(),
+            owner,
+            &self.system_program.to_account_info(),
+            &payment_token_program.to_account_info(),
+        )
+    }
+
+    /// the order is the delegate of the listed token account, or its owner for escrow listings
+    fn nft_transfer(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        order: AccountInfo<'info>,
+        nft_mint: &InterfaceAccount<'info, Mint>,
+        seller_nft_ta: AccountInfo<'info>,
+        buyer_nft_ta: AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.nft_token_program.to_account_info(),
+            TransferChecked {
+                from: seller_nft_ta,
+                to: buyer_nft_ta,
+                authority: order,
+                mint: nft_mint.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_ctx, amount, nft_mint.decimals)
+    }
+
+    /// closes the emptied escrow of a listing, rent goes back to the maker
+    fn close_escrow(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        order: AccountInfo<'info>,
+        order_nft_ta: AccountInfo<'info>,
+        maker: AccountInfo<'info>,
+    ) -> Result<()> {
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.nft_token_program.to_account_info(),
+            CloseAccount {
+                account: order_nft_ta,
+                destination: maker,
+                authority: order,
+            },
+            signer_seeds,
+        );
+        close_account(cpi_ctx)
+    }
+}
+
+/// buy out several listings of the market in one go
+/// listings that were filled, cancelled, expired or delisted since the transaction was built are skipped
+/// legacy metaplex, royalty-bearing and programmable nfts must go through fill_order
+#[inline(never)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Sweep<'info>>,
+    max_spend: u64,
+) -> Result<()> {
+    let orders_accounts = ctx
+        .remaining_accounts
+        .chunks_exact(SWEEP_ACCOUNTS_PER_ORDER);
+    if orders_accounts.len() == 0 || !orders_accounts.remainder().is_empty() {
+        return Err(MarketError::WrongAccount.into());
+    }
+
+    let clock = Clock::get()?;
+    let market_key = ctx.accounts.market.key();
+    let payment_mint_key = ctx
+        .accounts
+        .payment_mint
+        .as_ref()
+        .map(|payment_mint| payment_mint.key())
+        .unwrap_or_default();
+    if !ctx
+        .accounts
+        .market
+        .is_payment_mint_allowed(&payment_mint_key)
+    {
+        return Err(MarketError::PaymentMintNotAllowed.into());
+    }
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let nft_token_program = ctx.accounts.nft_token_program.to_account_info();
+    let taker = ctx.accounts.taker.to_account_info();
+
+    let mut total_spent: u64 = 0;
+    let mut total_fees: u64 = 0;
+
+    for order_accounts in orders_accounts {
+        let order_info = &order_accounts[0];
+        let maker = &order_accounts[1];
+        let nft_mint_info = &order_accounts[2];
+        let seller_nft_ta_info = &order_accounts[3];
+        let buyer_nft_ta = &order_accounts[4];
+        let seller_payment_ta = &order_accounts[5];
+        let maker_order_epoch = &order_accounts[6];
+
+        // filled and cancelled orders have been closed
+        if *order_info.owner != crate::ID {
+            msg!("Skip closed order: {}", order_info.key());
+            continue;
+        }
+        let mut order = Account::<Order>::try_from(order_info)?;
+        if order.market != market_key
+            || order.side != <OrderSide as Into<u8>>::into(OrderSide::Sell)
+            || order.payment_mint != payment_mint_key
+            || order.owner != maker.key()
+            || order.nft_mint != nft_mint_info.key()
+            || maker_order_epoch.key()
+                != Pubkey::find_program_address(
+                    &[ORDER_EPOCH_SEED, maker.key().as_ref()],
+                    &crate::ID,
+                )
+                .0
+        {
+            return Err(MarketError::WrongAccount.into());
+        }
+        if order.is_private() && order.taker != taker.key() {
+            return Err(MarketError::InvalidTaker.into());
+        }
+        if !Order::is_active(order.state)
+            || order.is_expired(clock.unix_timestamp)
+            || order.is_stale(OrderEpoch::get_current(maker_order_epoch)?)
+        {
+            msg!("Skip inactive order: {}", order_info.key());
+            continue;
+        }
+
+        let nft_mint = InterfaceAccount::<Mint>::try_from(nft_mint_info)?;
+        if *nft_mint_info.owner != nft_token_program.key() {
+            return Err(MarketError::WrongAccount.into());
+        }
+        // legacy metaplex nfts carry their royalties in the metadata account
+        if nft_token_program.key.to_string() == TOKEN_PID
+            || get_has_royalty_field(&mut nft_mint_info.to_account_info())
+        {
+            return Err(MarketError::UnsupportedNft.into());
+        }
+
+        let seller_nft_ta = InterfaceAccount::<TokenAccount>::try_from(seller_nft_ta_info)?;
+        // escrow listings sell out of the token account of the order
+        let nft_holder = if order.escrowed {
+            order_info.key()
+        } else {
+            maker.key()
+        };
+        if seller_nft_ta.mint != nft_mint.key() || seller_nft_ta.owner != nft_holder {
+            return Err(MarketError::WrongAccount.into());
+        }
+        // programmable nfts keep their token account frozen
+        if seller_nft_ta.is_frozen() {
+            return Err(MarketError::UnsupportedNft.into());
+        }
+
+        let amount = order.size;
+        let is_delegated = order.escrowed
+            || (seller_nft_ta.delegate.contains(&order.key())
+                && seller_nft_ta.delegated_amount >= amount);
+        if !is_delegated || seller_nft_ta.amount < amount {
+            msg!("Skip delisted order: {}", order_info.key());
+            continue;
+        }
+
+        let current_price = order.get_current_price(clock.unix_timestamp);
+        let value = amount
+            .checked_mul(current_price)
+            .ok_or(MarketError::AmountOverflow)?;
+        let fill_fees = if order.fees_on {
+            ctx.accounts.market.get_fill_fees(value, false)?
+        } else {
+            FillFees::default()
+        };
+        // the taker pays their fee on top of the price, the maker fee comes out of the proceeds
+        let spend = value
+            .checked_add(fill_fees.taker_fee)
+            .ok_or(MarketError::AmountOverflow)?;
+        let new_total_spent = total_spent
+            .checked_add(spend)
+            .ok_or(MarketError::AmountOverflow)?;
+        if new_total_spent > max_spend {
+            msg!("Skip order over max spend: {}", order_info.key());
+            continue;
+        }
+        total_spent = new_total_spent;
+        total_fees = u64::try_from(
+            fill_fees
+                .maker_fee
+                .saturating_add_unsigned(fill_fees.taker_fee),
+        )
+        .ok()
+        .and_then(|fee_amount| total_fees.checked_add(fee_amount))
+        .ok_or(MarketError::AmountOverflow)?;
+        let seller_received_amount = value
+            .checked_add_signed(-fill_fees.maker_fee)
+            .ok_or(MarketError::AmountUnderflow)?;
+
+        let bump = Pubkey::find_program_address(
+            &[
+                ORDER_SEED,
+                order.nonce.as_ref(),
+                order.market.as_ref(),
+                order.owner.as_ref(),
+            ],
+            &crate::ID,
+        )
+        .1;
+        let bump = &get_bump_in_seed_form(&bump);
+        let signer_seeds: &[&[&[u8]]; 1] = &[&[
+            ORDER_SEED,
+            order.nonce.as_ref(),
+            order.market.as_ref(),
+            order.owner.as_ref(),
+            bump,
+        ][..]];
+
+        create_ata(
+            buyer_nft_ta,
+            &taker,
+            nft_mint_info,
+            &taker,
+            &system_program,
+            &nft_token_program,
+        )?;
+        ctx.accounts.nft_transfer(
+            signer_seeds,
+            order_info.to_account_info(),
+            &nft_mint,
+            seller_nft_ta_info.to_account_info(),
+            buyer_nft_ta.to_account_info(),
+            amount,
+        )?;
+        if order.escrowed {
+            ctx.accounts.close_escrow(
+                signer_seeds,
+                order_info.to_account_info(),
+                seller_nft_ta_info.to_account_info(),
+                maker.to_account_info(),
+            )?;
+        }
+
+        ctx.accounts.create_payment_ata(seller_payment_ta, maker)?;
+        ctx.accounts.transfer_payment(
+            maker.to_account_info(),
+            seller_payment_ta.to_account_info(),
+            seller_received_amount,
+        )?;
+
+        emit_cpi!(order.get_fill_event(
+            order.key(),
+            ctx.accounts.market.market_identifier,
+            ctx.accounts.taker.key(),
+            nft_mint.key(),
+            amount,
+            current_price,
+            value,
+            &fill_fees,
+            0,
+        ));
+
+        // close order account
+        let payment_mint = order.payment_mint;
+        let price = order.price;
+        Order::edit_order(&mut order, price, payment_mint, 0, clock.unix_timestamp);
+        ctx.accounts.market.remove_open_order();
+        order.state = OrderState::Closed.into();
+        emit_cpi!(Order::get_edit_event(
+            &mut order.clone(),
+            order.key(),
+            ctx.accounts.market.market_identifier,
+            OrderEditType::FillAndClose,
+        ));
+        order.close(maker.to_account_info())?;
+    }
+
+    // one fee transfer for the whole sweep
+    if total_fees > 0 {
+        let fee_recipient = ctx.accounts.fee_recipient.to_account_info();
+        let fee_recipient_ta = ctx
+            .accounts
+            .fee_recipient_ta
+            .as_ref()
+            .map(|fee_recipient_ta| fee_recipient_ta.to_account_info())
+            .unwrap_or_else(|| fee_recipient.clone());
+        ctx.accounts
+            .create_payment_ata(&fee_recipient_ta, &fee_recipient)?;
+        ctx.accounts
+            .transfer_payment(fee_recipient, fee_recipient_ta, total_fees)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::MarketError, state::*};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct CancelSignedListings<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        constraint = nonce_bitmap.owner == owner.key(),
+        seeds = [NONCE_SEED,
+        nonce_bitmap.market.as_ref(),
+        owner.key().as_ref()],
+        bump,
+    )]
+    pub nonce_bitmap: Box<Account<'info, NonceBitmap>>,
+}
+
+/// burn the nonces of signed listings so they can no longer be filled
+#[inline(always)]
+pub fn handler(ctx: Context<CancelSignedListings>, nonces: Vec<u64>) -> Result<()> {
+    msg!(
+        "Cancel signed listings of: {}",
+        ctx.accounts.nonce_bitmap.key()
+    );
+
+    for nonce in nonces {
+        if nonce >= MAX_SIGNED_NONCES {
+            return Err(MarketError::NonceUsed.into());
+        }
+        ctx.accounts.nonce_bitmap.use_nonce(nonce);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,563 @@
+use std::str::FromStr;
+
+use anchor_lang::{prelude::*, solana_program::sysvar};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::spl_token_2022::instruction::transfer_checked as transfer_2022,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use spl_transfer_hook_interface::onchain::add_extra_accounts_for_execute_cpi;
+use wen_new_standard::{
+    cpi::{accounts::ApproveTransfer, approve_transfer},
+    utils::get_mint_metadata,
+    ROYALTY_BASIS_POINTS_FIELD,
+};
+
+use crate::{
+    errors::MarketError,
+    instructions::signed::SignedListing,
+    state::*,
+    utils::{
+        create_ata, get_amount_from_bp, get_bump_in_seed_form, get_editions_royalties,
+        get_has_royalty_field, get_verified_metadata, token_extensions::WnsApprovalAccounts,
+        transfer_sol, verify_ed25519_signature,
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(listing: SignedListing)]
+#[event_cpi]
+pub struct FillSignedListing<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut)]
+    /// CHECK: signature checked against the ed25519 instruction
+    pub seller: UncheckedAccount<'info>,
+    #[account(
+        constraint = Market::is_active(market.state),
+        constraint = listing.market == market.key(),
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(
+        mut,
+        seeds = [NONCE_SEED,
+        market.key().as_ref(),
+        seller.key().as_ref()],
+        bump,
+    )]
+    pub nonce_bitmap: Box<Account<'info, NonceBitmap>>,
+    #[account(
+        seeds = [ORDER_EPOCH_SEED,
+        seller.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: seeds constraint, empty until the seller first cancels all their orders
+    pub order_epoch: UncheckedAccount<'info>,
+    #[account(constraint = listing.nft_mint == nft_mint.key())]
+    pub nft_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = nft_mint,
+        token::authority = seller,
+        token::token_program = nft_token_program,
+    )]
+    pub seller_nft_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub buyer_nft_ta: UncheckedAccount<'info>,
+    #[account(mut, constraint = fee_recipient.key() == market.fee_recipient.key())]
+    /// CHECK: constraint check
+    pub fee_recipient: UncheckedAccount<'info>,
+    /// payment accounts are left out for listings in native SOL
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub fee_recipient_ta: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub seller_payment_ta: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub buyer_payment_ta: Option<UncheckedAccount<'info>>,
+    #[account(constraint = payment_mint.key() == listing.payment_mint)]
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+    pub nft_token_program: Interface<'info, TokenInterface>,
+    /// CHECK: checked by constraint and in cpi
+    pub nft_program: UncheckedAccount<'info>,
+    /// CHECK: checked by address constraint
+    #[account(address = sysvar::instructions::id())]
+    pub sysvar_instructions: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> FillSignedListing<'info> {
+    /// pays the recipient from the taker, in lamports when the listing is priced in native SOL
+    #[inline(never)]
+    fn transfer_payment(
+        &self,
+        recipient: AccountInfo<'info>,
+        recipient_ta: Option<AccountInfo<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        if self.payment_mint.is_none() {
+            return transfer_sol(
+                self.taker.to_account_info(),
+                recipient,
+                self.system_program.to_account_info(),
+                None,
+                amount,
+            );
+        }
+
+        let (
+            Some(buyer_payment_ta),
+            Some(recipient_ta),
+            Some(payment_mint),
+            Some(payment_token_program),
+        ) = (
+            &self.buyer_payment_ta,
+            recipient_ta,
+            &self.payment_mint,
+            &self.payment_token_program,
+        )
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        let cpi_ctx = CpiContext::new(
+            payment_token_program.to_account_info(),
+            TransferChecked {
+                from: buyer_payment_ta.to_account_info(),
+                to: recipient_ta,
+                authority: self.taker.to_account_info(),
+                mint: payment_mint.to_account_info(),
+            },
+        );
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)
+    }
+
+    /// native listings pay wallets directly and need no token accounts
+    #[inline(never)]
+    fn create_payment_ata(
+        &self,
+        ata: Option<AccountInfo<'info>>,
+        owner: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let Some(payment_mint) = &self.payment_mint else {
+            return Ok(());
+        };
+        let (Some(ata), Some(payment_token_program)) = (ata, &self.payment_token_program) else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        create_ata(
+            &ata,
+            &self.taker.to_account_info(),
+            &payment_mint.to_account_info(),
+            owner,
+            &self.system_program.to_account_info(),
+            &payment_token_program.to_account_info(),
+        )
+    }
+
+    // WNS Pre-Transfer Approval
+    #[inline(never)]
+    fn approve_wns_transfer(
+        &self,
+        value: u64,
+        wns_accounts: WnsApprovalAccounts<'info>,
+    ) -> Result<()> {
+        if wns_accounts.payment_mint.key() != self.listing_payment_mint() {
+            return Err(MarketError::WrongAccount.into());
+        }
+        self.create_payment_ata(
+            Some(wns_accounts.distribution_token_account.to_account_info()),
+            &wns_accounts.distribution_account,
+        )?;
+
+        let (distribution_token_account, authority_token_account, payment_token_program) =
+            if self.payment_mint.is_none() {
+                (None, None, None)
+            } else {
+                (
+                    Some(wns_accounts.distribution_token_account.to_account_info()),
+                    self.buyer_payment_ta
+                        .as_ref()
+                        .map(|buyer_payment_ta| buyer_payment_ta.to_account_info()),
+                    self.payment_token_program
+                        .as_ref()
+                        .map(|payment_token_program| payment_token_program.to_account_info()),
+                )
+            };
+
+        let cpi_accounts = ApproveTransfer {
+            payer: self.taker.to_account_info(),
+            authority: self.taker.to_account_info(),
+            mint: self.nft_mint.to_account_info(),
+            approve_account: wns_accounts.approval_account.to_account_info(),
+            payment_mint: wns_accounts.payment_mint.to_account_info(),
+            distribution_token_account,
+            authority_token_account,
+            distribution_account: wns_accounts.distribution_account.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            distribution_program: wns_accounts.distribution_program.to_account_info(),
+            token_program: self.nft_token_program.to_account_info(),
+            payment_token_program,
+        };
+        let cpi_ctx = CpiContext::new(self.nft_program.to_account_info(), cpi_accounts);
+        approve_transfer(cpi_ctx, value)
+    }
+
+    /// payment mint of the listing, default pubkey if SOL
+    fn listing_payment_mint(&self) -> Pubkey {
+        self.payment_mint
+            .as_ref()
+            .map(|payment_mint| payment_mint.key())
+            .unwrap_or_default()
+    }
+
+    /// the nonce bitmap is the delegate of the listed token account
+    /// any hook accounts are forwarded to the token program
+    #[inline(never)]
+    fn nft_transfer(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        amount: u64,
+        hook_accounts: Vec<AccountInfo<'info>>,
+    ) -> Result<()> {
+        let authority_info = self.nonce_bitmap.to_account_info();
+
+        let mut transfer_ix = transfer_2022(
+            self.nft_token_program.key,
+            &self.seller_nft_ta.key(),
+            &self.nft_mint.key(),
+            self.buyer_nft_ta.key,
+            authority_info.key,
+            &[],
+            amount,
+            self.nft_mint.decimals,
+        )?;
+
+        let mut account_infos = vec![
+            self.seller_nft_ta.to_account_info(),
+            self.nft_mint.to_account_info(),
+            self.buyer_nft_ta.to_account_info(),
+            authority_info.clone(),
+        ];
+
+        if let Some(hook_program) = hook_accounts.last() {
+            // transfer hook
+            add_extra_accounts_for_execute_cpi(
+                &mut transfer_ix,
+                &mut account_infos,
+                hook_program.key,
+                self.seller_nft_ta.to_account_info(),
+                self.nft_mint.to_account_info(),
+                self.buyer_nft_ta.to_account_info(),
+                authority_info,
+                amount,
+                &hook_accounts,
+            )?;
+        }
+
+        anchor_lang::solana_program::program::invoke_signed(
+            &transfer_ix,
+            &account_infos,
+            signer_seeds,
+        )?;
+
+        Ok(())
+    }
+}
+
+/// buy a listing signed off-chain by the seller, without an order account
+/// the transaction must verify the seller's signature of the listing with an ed25519 instruction
+/// right before this one, and the nonce of the listing is burned on fill
+/// remaining accounts are the mint verification, metadata and creator accounts for metaplex mints,
+/// the wns approval accounts and group member for wns mints, or the editions deployment and creator
+/// accounts for editions mints, followed by any transfer hook accounts; programmable nfts are not supported
+#[inline(never)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, FillSignedListing<'info>>,
+    listing: SignedListing,
+) -> Result<()> {
+    msg!("Fill signed listing of: {}", ctx.accounts.seller.key());
+
+    let nft_token_program_key = &ctx.accounts.nft_token_program.key.to_string().clone();
+    let nft_program_key = &ctx.accounts.nft_program.key.to_string().clone();
+    let mut remaining_accounts = ctx.remaining_accounts.to_vec();
+
+    let message = listing.try_to_vec()?;
+    verify_ed25519_signature(
+        &ctx.accounts.sysvar_instructions.to_account_info(),
+        &ctx.accounts.seller.key(),
+        &message,
+    )?;
+
+    let clock = Clock::get()?;
+    if listing.expiry != 0 && clock.unix_timestamp >= listing.expiry {
+        return Err(MarketError::OrderExpired.into());
+    }
+    if listing.is_stale(OrderEpoch::get_current(&ctx.accounts.order_epoch)?) {
+        return Err(MarketError::StaleOrder.into());
+    }
+    if !ctx
+        .accounts
+        .market
+        .is_payment_mint_allowed(&listing.payment_mint)
+    {
+        return Err(MarketError::PaymentMintNotAllowed.into());
+    }
+    // payment accounts are only passed for listings priced in a token
+    if listing.is_native() == ctx.accounts.payment_mint.is_some() {
+        return Err(MarketError::WrongAccount.into());
+    }
+    if listing.price == 0 || listing.size == 0 {
+        return Err(MarketError::InvalidSignature.into());
+    }
+    if !ctx.accounts.nonce_bitmap.is_nonce_free(listing.nonce) {
+        return Err(MarketError::NonceUsed.into());
+    }
+    ctx.accounts.nonce_bitmap.use_nonce(listing.nonce);
+    // programmable nfts keep their token account frozen
+    if ctx.accounts.seller_nft_ta.is_frozen() {
+        return Err(MarketError::UnsupportedNft.into());
+    }
+
+    let value = listing
+        .size
+        .checked_mul(listing.price)
+        .ok_or(MarketError::AmountOverflow)?;
+    let fill_fees = ctx.accounts.market.get_fill_fees(value, false)?;
+    // maker rebates are paid out of the taker fee
+    let fee_amount = u64::try_from(
+        fill_fees
+            .maker_fee
+            .saturating_add_unsigned(fill_fees.taker_fee),
+    )
+    .map_err(|_| MarketError::AmountUnderflow)?;
+    // the taker pays their fee on top of the price
+    let mut seller_received_amount = value
+        .checked_add_signed(-fill_fees.maker_fee)
+        .ok_or(MarketError::AmountUnderflow)?;
+    let mut royalties_paid: u64 = 0;
+
+    let bump = &get_bump_in_seed_form(&ctx.bumps.nonce_bitmap);
+    let market_key = ctx.accounts.market.key();
+    let seller_key = ctx.accounts.seller.key();
+    let signer_seeds: &[&[&[u8]]; 1] =
+        &[&[NONCE_SEED, market_key.as_ref(), seller_key.as_ref(), bump][..]];
+
+    let taker = ctx.accounts.taker.to_account_info();
+    let seller = ctx.accounts.seller.to_account_info();
+    let fee_recipient = ctx.accounts.fee_recipient.to_account_info();
+    let seller_payment_ta = ctx
+        .accounts
+        .seller_payment_ta
+        .as_ref()
+        .map(|seller_payment_ta| seller_payment_ta.to_account_info());
+    let buyer_payment_ta = ctx
+        .accounts
+        .buyer_payment_ta
+        .as_ref()
+        .map(|buyer_payment_ta| buyer_payment_ta.to_account_info());
+    let fee_recipient_ta = ctx
+        .accounts
+        .fee_recipient_ta
+        .as_ref()
+        .map(|fee_recipient_ta| fee_recipient_ta.to_account_info());
+
+    create_ata(
+        &ctx.accounts.buyer_nft_ta.to_account_info(),
+        &taker,
+        &ctx.accounts.nft_mint.to_account_info(),
+        &taker,
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.nft_token_program.to_account_info(),
+    )?;
+    ctx.accounts.create_payment_ata(buyer_payment_ta, &taker)?;
+    ctx.accounts
+        .create_payment_ata(seller_payment_ta.clone(), &seller)?;
+    ctx.accounts
+        .create_payment_ata(fee_recipient_ta.clone(), &fee_recipient)?;
+
+    // Transfer NFT
+    if *nft_token_program_key == TOKEN_PID {
+        if *nft_program_key != METAPLEX_PID {
+            return Err(MarketError::UnsupportedNft.into());
+        }
+        // Remaining Accounts 0-1 for the manual verification and metadata of metaplex mints
+        let verification_account = remaining_accounts
+            .first()
+            .ok_or(MarketError::WrongAccount)?;
+        let nft_metadata = remaining_accounts.get(1).ok_or(MarketError::WrongAccount)?;
+        crate::utils::verify_mint_verification(
+            verification_account,
+            ctx.accounts.nft_mint.key(),
+            market_key,
+        )?;
+
+        // Remaining Accounts after the metadata are a creator and creator payment ta per creator
+        let parsed_metadata = get_verified_metadata(nft_metadata, &ctx.accounts.nft_mint.key())?;
+        let royalties = get_amount_from_bp(value, parsed_metadata.seller_fee_basis_points.into())?;
+        for (i, creator) in parsed_metadata.creators.iter().flatten().enumerate() {
+            let creator_account = remaining_accounts
+                .get(2 + 2 * i)
+                .ok_or(MarketError::WrongAccount)?;
+            let creator_payment_ta = remaining_accounts
+                .get(3 + 2 * i)
+                .ok_or(MarketError::WrongAccount)?;
+            if *creator_account.key != creator.address {
+                return Err(MarketError::WrongAccount.into());
+            }
+
+            let creator_amount = get_amount_from_bp(royalties, u128::from(creator.share) * 100)?;
+            if creator_amount == 0 {
+                continue;
+            }
+            ctx.accounts
+                .create_payment_ata(Some(creator_payment_ta.to_account_info()), creator_account)?;
+            ctx.accounts.transfer_payment(
+                creator_account.to_account_info(),
+                Some(creator_payment_ta.to_account_info()),
+                creator_amount,
+            )?;
+            royalties_paid = royalties_paid
+                .checked_add(creator_amount)
+                .ok_or(MarketError::AmountOverflow)?;
+        }
+        seller_received_amount = seller_received_amount
+            .checked_sub(royalties_paid)
+            .ok_or(MarketError::AmountUnderflow)?;
+
+        ctx.accounts
+            .nft_transfer(signer_seeds, listing.size, Vec::new())?;
+    } else if *nft_token_program_key == TOKEN_EXT_PID {
+        if *nft_program_key == WNS_PID {
+            // Remaining Accounts 0-4 for approval, 5 for the wns group member
+            if remaining_accounts.len() < 6 {
+                return Err(MarketError::WrongAccount.into());
+            }
+            let hook_accounts = remaining_accounts.split_off(6);
+            crate::utils::verify_wns_mint(
+                ctx.accounts.nft_mint.to_account_info(),
+                remaining_accounts[5].to_account_info(),
+                ctx.accounts.market.market_identifier,
+            )?;
+            let wns_accounts = WnsApprovalAccounts {
+                approval_account: remaining_accounts[0].to_account_info(),
+                distribution_account: remaining_accounts[1].to_account_info(),
+                distribution_token_account: remaining_accounts[2].to_account_info(),
+                distribution_program: remaining_accounts[3].to_account_info(),
+                payment_mint: remaining_accounts[4].to_account_info(),
+            };
+
+            let mint_metadata = get_mint_metadata(&mut ctx.accounts.nft_mint.to_account_info())?;
+            let royalty_basis_points = mint_metadata
+                .additional_metadata
+                .iter()
+                .find(|(key, _)| key == ROYALTY_BASIS_POINTS_FIELD)
+                .map(|(_, value)| value)
+                .map(|value| u64::from_str(value).unwrap())
+                .unwrap_or(0);
+            let royalties = get_amount_from_bp(value, royalty_basis_points.into())?;
+            seller_received_amount = seller_received_amount
+                .checked_sub(royalties)
+                .ok_or(MarketError::AmountUnderflow)?;
+            royalties_paid = royalties;
+
+            // Handles royalties
+            ctx.accounts.approve_wns_transfer(value, wns_accounts)?;
+            ctx.accounts
+                .nft_transfer(signer_seeds, listing.size, hook_accounts)?;
+        } else {
+            if *nft_program_key == EDITIONS_PID {
+                // Remaining Account 0 for the editions deployment, then a creator and creator payment ta per creator
+                let editions_deployment = remaining_accounts
+                    .first()
+                    .ok_or(MarketError::WrongAccount)?;
+                let editions_royalties = get_editions_royalties(
+                    &mut ctx.accounts.nft_mint.to_account_info(),
+                    editions_deployment,
+                )?;
+                let royalties =
+                    get_amount_from_bp(value, editions_royalties.royalty_basis_points.into())?;
+
+                for (i, creator) in editions_royalties.creators.iter().enumerate() {
+                    let creator_account = remaining_accounts
+                        .get(1 + 2 * i)
+                        .ok_or(MarketError::WrongAccount)?;
+                    let creator_payment_ta = remaining_accounts
+                        .get(2 + 2 * i)
+                        .ok_or(MarketError::WrongAccount)?;
+                    if *creator_account.key != creator.address {
+                        return Err(MarketError::WrongAccount.into());
+                    }
+
+                    let creator_amount =
+                        get_amount_from_bp(royalties, u128::from(creator.share) * 100)?;
+                    if creator_amount == 0 {
+                        continue;
+                    }
+                    ctx.accounts.create_payment_ata(
+                        Some(creator_payment_ta.to_account_info()),
+                        creator_account,
+                    )?;
+                    ctx.accounts.transfer_payment(
+                        creator_account.to_account_info(),
+                        Some(creator_payment_ta.to_account_info()),
+                        creator_amount,
+                    )?;
+                    royalties_paid = royalties_paid
+                        .checked_add(creator_amount)
+                        .ok_or(MarketError::AmountOverflow)?;
+                }
+                seller_received_amount = seller_received_amount
+                    .checked_sub(royalties_paid)
+                    .ok_or(MarketError::AmountUnderflow)?;
+
+                remaining_accounts =
+                    remaining_accounts.split_off(1 + 2 * editions_royalties.creators.len());
+            } else if get_has_royalty_field(&mut ctx.accounts.nft_mint.to_account_info()) {
+                // mints carrying royalties must go through their royalty path
+                return Err(MarketError::UnsupportedNft.into());
+            }
+            // Any remaining accounts left are for potential transfer hook (Empty if not expecting hook)
+            ctx.accounts
+                .nft_transfer(signer_seeds, listing.size, remaining_accounts)?;
+        }
+    } else {
+        return Err(MarketError::UnsupportedNft.into());
+    }
+
+    // Transfer payment
+    ctx.accounts
+        .transfer_payment(seller, seller_payment_ta, seller_received_amount)?;
+    ctx.accounts
+        .transfer_payment(fee_recipient, fee_recipient_ta, fee_amount)?;
+
+    emit_cpi!(OrderFillEvent {
+        address: ctx.accounts.nonce_bitmap.key().to_string(),
+        market_identifier: ctx.accounts.market.market_identifier.to_string(),
+        maker: seller_key.to_string(),
+        taker: ctx.accounts.taker.key().to_string(),
+        side: OrderSide::Sell.into(),
+        nft_mint: ctx.accounts.nft_mint.key().to_string(),
+        payment_mint: listing.payment_mint.to_string(),
+        amount: listing.size,
+        price: listing.price,
+        value,
+        maker_fee: fill_fees.maker_fee,
+        taker_fee: fill_fees.taker_fee,
+        royalties: royalties_paid,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct InitNonceBitmap<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(
+        init,
+        seeds = [NONCE_SEED,
+        market.key().as_ref(),
+        owner.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + std::mem::size_of::<NonceBitmap>()
+    )]
+    pub nonce_bitmap: Box<Account<'info, NonceBitmap>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// one time setup before listing by signature
+/// listed token accounts are then delegated to the nonce bitmap account
+#[inline(always)]
+pub fn handler(ctx: Context<InitNonceBitmap>) -> Result<()> {
+    msg!(
+        "Initialize a new nonce bitmap: {}",
+        ctx.accounts.nonce_bitmap.key()
+    );
+
+    let market = ctx.accounts.market.key();
+    let owner = ctx.accounts.owner.key();
+    ctx.accounts.nonce_bitmap.init(market, owner);
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+/// listing terms signed off-chain by the seller, the signed message is their borsh serialization
+pub struct SignedListing {
+    pub market: Pubkey,
+    pub nft_mint: Pubkey,
+    pub payment_mint: Pubkey,
+    pub price: u64,
+    pub size: u64,
+    /// index in the nonce bitmap of the seller
+    pub nonce: u64,
+    /// time after which the listing can no longer be filled, 0 if it never expires
+    pub expiry: i64,
+    /// order epoch of the seller when signing, stale once the seller moves past it
+    pub epoch: u64,
+}
+
+impl SignedListing {
+    /// return true if the listing is priced in native SOL
+    pub fn is_native(&self) -> bool {
+        self.payment_mint == Pubkey::default()
+    }
+
+    /// return true if the seller has cancelled the listing by moving to a later epoch
+    pub fn is_stale(&self, current_epoch: u64) -> bool {
+        self.epoch < current_epoch
+    }
+}
+
+pub mod cancel;
+pub mod fill;
+pub mod init;
+
+pub use cancel::*;
+pub use fill::*;
+pub use init::*;
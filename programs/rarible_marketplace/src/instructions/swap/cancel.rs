@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    close_account, revoke, transfer_checked, CloseAccount, Mint, Revoke, TokenAccount,
+    TokenInterface, TransferChecked,
+};
+
+use crate::{errors::MarketError, state::*, utils::get_bump_in_seed_form};
+
+#[derive(Accounts)]
+#[instruction()]
+#[event_cpi]
+pub struct CancelSwap<'info> {
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = order.owner == initializer.key(),
+        constraint = order.market == market.key(),
+        constraint = Order::is_active(order.state),
+        constraint = order.is_swap(),
+        constraint = order.nft_mint == bundle.key(),
+        seeds = [ORDER_SEED,
+        order.nonce.as_ref(),
+        order.market.as_ref(),
+        initializer.key().as_ref()],
+        bump,
+        close = initializer,
+    )]
+    pub order: Box<Account<'info, Order>>,
+    #[account(
+        mut,
+        seeds = [BUNDLE_SEED,
+        order.key().as_ref()],
+        bump,
+        close = initializer,
+    )]
+    pub bundle: Box<Account<'info, Bundle>>,
+    #[account(
+        mut,
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+    /// payment accounts are only needed to refund a sweetener escrowed in a token
+    #[account(
+        mut,
+        token::mint = order.payment_mint,
+        token::authority = initializer,
+        token::token_program = payment_token_program,
+    )]
+    pub initializer_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        token::mint = order.payment_mint,
+        token::authority = order,
+        token::token_program = payment_token_program,
+    )]
+    pub order_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(constraint = payment_mint.key() == order.payment_mint)]
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+    pub nft_token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CancelSwap<'info> {
+    fn nft_revoke(&self, initializer_nft_ta: AccountInfo<'info>) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            self.nft_token_program.to_account_info(),
+            Revoke {
+                authority: self.initializer.to_account_info(),
+                source: initializer_nft_ta,
+            },
+        );
+        revoke(cpi_ctx)
+    }
+
+    /// refunds the escrowed sweetener and closes the escrow
+    fn refund_sweetener(&self, signer_seeds: &[&[&[u8]]], amount: u64) -> Result<()> {
+        let (
+            Some(initializer_payment_ta),
+            Some(order_payment_ta),
+            Some(payment_mint),
+            Some(payment_token_program),
+        ) = (
+            &self.initializer_payment_ta,
+            &self.order_payment_ta,
+            &self.payment_mint,
+            &self.payment_token_program,
+        )
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            payment_token_program.to_account_info(),
+            TransferChecked {
+                from: order_payment_ta.to_account_info(),
+                to: initializer_payment_ta.to_account_info(),
+                authority: self.order.to_account_info(),
+                mint: payment_mint.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)?;
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            payment_token_program.to_account_info(),
+            CloseAccount {
+                account: order_payment_ta.to_account_info(),
+                destination: self.initializer.to_account_info(),
+                authority: self.order.to_account_info(),
+            },
+            signer_seeds,
+        );
+        close_account(cpi_ctx)
+    }
+}
+
+/// cancel a swap order, revoking the offered mints and refunding any escrowed sweetener
+/// remaining accounts are the initializer nft ta of each offered mint, in bundle order
+#[inline(always)]
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, CancelSwap<'info>>) -> Result<()> {
+    msg!("Close swap order account: {}", ctx.accounts.order.key());
+
+    let mints = ctx.accounts.bundle.get_mints();
+    if ctx.remaining_accounts.len() != mints.len() {
+        return Err(MarketError::WrongAccount.into());
+    }
+
+    for (initializer_nft_ta_info, mint) in ctx.remaining_accounts.iter().zip(mints) {
+        let initializer_nft_ta =
+            InterfaceAccount::<TokenAccount>::try_from(initializer_nft_ta_info)?;
+        if initializer_nft_ta.owner != ctx.accounts.initializer.key()
+            || initializer_nft_ta.mint != *mint
+        {
+            return Err(MarketError::WrongAccount.into());
+        }
+        // items moved or delegated elsewhere since the swap was created are left alone
+        if initializer_nft_ta
+            .delegate
+            .contains(&ctx.accounts.order.key())
+        {
+            ctx.accounts
+                .nft_revoke(initializer_nft_ta_info.to_account_info())?;
+        }
+    }
+
+    // native escrow is refunded when the order account is closed
+    let sweetener = ctx.accounts.order.price;
+    if ctx.accounts.order.maker_pays && sweetener > 0 && !ctx.accounts.order.is_native() {
+        let bump = &get_bump_in_seed_form(&ctx.bumps.order);
+        let signer_seeds: &[&[&[u8]]; 1] = &[&[
+            ORDER_SEED,
+            ctx.accounts.order.nonce.as_ref(),
+            ctx.accounts.order.market.as_ref(),
+            ctx.accounts.order.owner.as_ref(),
+            bump,
+        ][..]];
+        let sweetener_escrow = ctx.accounts.order.get_bid_escrow(1)?;
+        ctx.accounts
+            .refund_sweetener(signer_seeds, sweetener_escrow)?;
+    }
+
+    let clock = Clock::get()?;
+    let payment_mint = ctx.accounts.order.payment_mint;
+    Order::edit_order(
+        &mut ctx.accounts.order,
+        sweetener,
+        payment_mint,
+        0,
+        clock.unix_timestamp,
+    );
+    ctx.accounts.market.remove_open_order();
+    ctx.accounts.order.state = OrderState::Closed.into();
+    emit_cpi!(Order::get_edit_event(
+        &mut ctx.accounts.order.clone(),
+        ctx.accounts.order.key(),
+        ctx.accounts.market.market_identifier,
+        OrderEditType::Close,
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,241 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        approve, transfer_checked, Approve, Mint, TokenAccount, TokenInterface, TransferChecked,
+    },
+};
+
+use crate::{
+    errors::MarketError,
+    instructions::swap::SwapData,
+    state::*,
+    utils::{transfer_sol, verify_wns_mint},
+};
+
+#[derive(Accounts)]
+#[instruction(data: SwapData)]
+#[event_cpi]
+pub struct CreateSwap<'info> {
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = Market::is_active(market.state),
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(
+        init,
+        seeds = [ORDER_SEED,
+        data.nonce.as_ref(),
+        market.key().as_ref(),
+        initializer.key().as_ref()],
+        bump,
+        payer = initializer,
+        space = 8 + std::mem::size_of::<Order>()
+    )]
+    pub order: Box<Account<'info, Order>>,
+    #[account(
+        seeds = [ORDER_EPOCH_SEED,
+        initializer.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: seeds constraint, empty until the initializer first cancels all their orders
+    pub order_epoch: UncheckedAccount<'info>,
+    #[account(
+        init,
+        seeds = [BUNDLE_SEED,
+        order.key().as_ref()],
+        bump,
+        payer = initializer,
+        space = 8 + std::mem::size_of::<Bundle>()
+    )]
+    pub bundle: Box<Account<'info, Bundle>>,
+    /// payment accounts are only needed when the maker pays a sweetener in a token
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = initializer,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub initializer_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        init_if_needed,
+        payer = initializer,
+        associated_token::mint = payment_mint,
+        associated_token::authority = order,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub order_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(constraint = payment_mint.key() == data.payment_mint)]
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+    pub nft_token_program: Interface<'info, TokenInterface>,
+    /// CHECK: checked by constraint and in cpi
+    pub nft_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> CreateSwap<'info> {
+    fn nft_delegate(&self, initializer_nft_ta: AccountInfo<'info>) -> Result<()> {
+        let delegate_cpi = CpiContext::new(
+            self.nft_token_program.to_account_info(),
+            Approve {
+                to: initializer_nft_ta,
+                authority: self.initializer.to_account_info(),
+                delegate: self.order.to_account_info(),
+            },
+        );
+
+        approve(delegate_cpi, 1)
+    }
+
+    /// escrows the sweetener of the maker in the order
+    fn escrow_sweetener(&self, amount: u64) -> Result<()> {
+        if self.order.is_native() {
+            return transfer_sol(
+                self.initializer.to_account_info(),
+                self.order.to_account_info(),
+                self.system_program.to_account_info(),
+                None,
+                amount,
+            );
+        }
+
+        let (
+            Some(initializer_payment_ta),
+            Some(order_payment_ta),
+            Some(payment_mint),
+            Some(payment_token_program),
+        ) = (
+            &self.initializer_payment_ta,
+            &self.order_payment_ta,
+            &self.payment_mint,
+            &self.payment_token_program,
+        )
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        let cpi_ctx = CpiContext::new(
+            payment_token_program.to_account_info(),
+            TransferChecked {
+                from: initializer_payment_ta.to_account_info(),
+                to: order_payment_ta.to_account_info(),
+                authority: self.initializer.to_account_info(),
+                mint: payment_mint.to_account_info(),
+            },
+        );
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)
+    }
+}
+
+/// offer token 2022 mints of the market for a wanted mint, with an optional sweetener on either side
+/// remaining accounts per offered mint are the mint, the initializer nft ta and,
+/// for wns mints, the wns group member account
+/// a sweetener is charged market fees like a sale
+#[inline(always)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateSwap<'info>>,
+    data: SwapData,
+) -> Result<()> {
+    msg!("Initialize a new swap order: {}", ctx.accounts.order.key());
+
+    let nft_token_program_key = &ctx.accounts.nft_token_program.key.to_string().clone();
+    let nft_program_key = &ctx.accounts.nft_program.key.to_string().clone();
+    if *nft_token_program_key != TOKEN_EXT_PID {
+        return Err(MarketError::UnsupportedNft.into());
+    }
+
+    let clock = Clock::get()?;
+    let expiry = data.expiry.unwrap_or(0);
+    if expiry != 0 && expiry <= clock.unix_timestamp {
+        return Err(MarketError::OrderExpired.into());
+    }
+    if data.sweetener > 0
+        && !ctx
+            .accounts
+            .market
+            .is_payment_mint_allowed(&data.payment_mint)
+    {
+        return Err(MarketError::PaymentMintNotAllowed.into());
+    }
+    // a merkle root only narrows down a swap for any mint
+    if data.merkle_root.is_some() && data.swap_mint != Pubkey::default() {
+        return Err(MarketError::InvalidSwapParams.into());
+    }
+
+    let item_accounts_len = if *nft_program_key == WNS_PID { 3 } else { 2 };
+    let items = ctx.remaining_accounts.chunks_exact(item_accounts_len);
+    if items.len() == 0 || items.len() > MAX_BUNDLE_SIZE || !items.remainder().is_empty() {
+        return Err(MarketError::WrongAccount.into());
+    }
+
+    let mut mints: Vec<Pubkey> = Vec::with_capacity(items.len());
+    for item in items {
+        let nft_mint = InterfaceAccount::<Mint>::try_from(&item[0])?;
+        let initializer_nft_ta = InterfaceAccount::<TokenAccount>::try_from(&item[1])?;
+        if *item[0].owner != ctx.accounts.nft_token_program.key()
+            || initializer_nft_ta.mint != nft_mint.key()
+            || initializer_nft_ta.owner != ctx.accounts.initializer.key()
+            || nft_mint.key() == data.swap_mint
+            || mints.contains(&nft_mint.key())
+        {
+            return Err(MarketError::WrongAccount.into());
+        }
+
+        if *nft_program_key == WNS_PID {
+            verify_wns_mint(
+                item[0].to_account_info(),
+                item[2].to_account_info(),
+                ctx.accounts.market.market_identifier,
+            )?;
+        }
+
+        ctx.accounts.nft_delegate(item[1].to_account_info())?;
+        mints.push(nft_mint.key());
+    }
+
+    Order::init(
+        &mut ctx.accounts.order,
+        ctx.accounts.market.key(),
+        ctx.accounts.initializer.key(),
+        data.nonce,
+        ctx.accounts.bundle.key(),
+        data.payment_mint,
+        clock.unix_timestamp,
+        OrderSide::Swap.into(),
+        1,
+        data.sweetener,
+        OrderState::Ready.into(),
+        true,
+        expiry,
+    );
+    ctx.accounts.order.epoch = OrderEpoch::get_current(&ctx.accounts.order_epoch)?;
+    ctx.accounts.market.add_open_order();
+    ctx.accounts.order.swap_mint = data.swap_mint;
+    ctx.accounts.order.merkle_root = data.merkle_root.unwrap_or_default();
+    ctx.accounts.order.maker_pays = data.maker_pays;
+    let order_key = ctx.accounts.order.key();
+    ctx.accounts.bundle.init(order_key, &mints);
+
+    // a maker paying the sweetener escrows their maker fee on top of it like a bid
+    if data.maker_pays && data.sweetener > 0 {
+        ctx.accounts.order.maker_fee_bps = ctx.accounts.market.get_bid_maker_fee_bps();
+        let sweetener_escrow = ctx.accounts.order.get_bid_escrow(1)?;
+        ctx.accounts.escrow_sweetener(sweetener_escrow)?;
+    }
+
+    emit_cpi!(Order::get_edit_event(
+        &mut ctx.accounts.order.clone(),
+        ctx.accounts.order.key(),
+        ctx.accounts.market.market_identifier,
+        OrderEditType::Init,
+    ));
+
+    Ok(())
+}
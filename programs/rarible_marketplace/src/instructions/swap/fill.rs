@@ -0,0 +1,686 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::spl_token_2022::instruction::transfer_checked as transfer_2022,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+use spl_transfer_hook_interface::onchain::add_extra_accounts_for_execute_cpi;
+use wen_new_standard::{
+    cpi::{accounts::ApproveTransfer, approve_transfer},
+    utils::get_mint_metadata,
+    ROYALTY_BASIS_POINTS_FIELD,
+};
+
+use crate::{
+    errors::MarketError,
+    instructions::swap::SwapFillData,
+    state::*,
+    utils::{
+        create_ata, get_amount_from_bp, get_bump_in_seed_form, get_editions_royalties,
+        get_has_royalty_field, lamport_transfer, token_extensions::WnsApprovalAccounts,
+        transfer_sol, verify_merkle_mint, verify_mint_verification, verify_wns_mint,
+    },
+};
+
+#[derive(Accounts)]
+#[instruction()]
+#[event_cpi]
+pub struct FillSwap<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut, constraint = maker.key() == order.owner)]
+    /// CHECK: constraint check
+    pub maker: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = Market::is_active(market.state),
+        seeds = [MARKET_SEED,
+        market.market_identifier.as_ref()],
+        bump,
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(
+        mut,
+        constraint = Order::is_active(order.state),
+        constraint = order.is_swap(),
+        constraint = order.market == market.key(),
+        constraint = order.nft_mint == bundle.key(),
+        seeds = [ORDER_SEED,
+        order.nonce.as_ref(),
+        order.market.as_ref(),
+        order.owner.as_ref()],
+        bump,
+        close = maker
+    )]
+    pub order: Box<Account<'info, Order>>,
+    #[account(
+        seeds = [ORDER_EPOCH_SEED,
+        order.owner.as_ref()],
+        bump,
+    )]
+    /// CHECK: seeds constraint, empty until the order owner first cancels all their orders
+    pub order_epoch: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [BUNDLE_SEED,
+        order.key().as_ref()],
+        bump,
+        close = maker
+    )]
+    pub bundle: Box<Account<'info, Bundle>>,
+    #[account(mut, constraint = fee_recipient.key() == market.fee_recipient.key())]
+    /// CHECK: constraint check
+    pub fee_recipient: UncheckedAccount<'info>,
+    /// payment accounts are only needed for a sweetener in a token
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub maker_payment_ta: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub taker_payment_ta: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: checked by create_ata function
+    pub fee_recipient_ta: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        token::mint = order.payment_mint,
+        token::authority = order,
+        token::token_program = payment_token_program,
+    )]
+    pub order_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(constraint = payment_mint.key() == order.payment_mint)]
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+    pub nft_token_program: Interface<'info, TokenInterface>,
+    /// CHECK: checked by constraint and in cpi
+    pub nft_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> FillSwap<'info> {
+    /// pays the recipient out of the sweetener, from the order escrow if the maker pays it
+    /// and from the taker otherwise
+    #[inline(never)]
+    fn transfer_payment(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        recipient: AccountInfo<'info>,
+        recipient_ta: Option<AccountInfo<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        let maker_pays = self.order.maker_pays;
+        if self.order.is_native() {
+            return if maker_pays {
+                lamport_transfer(self.order.to_account_info(), recipient, amount)
+            } else {
+                transfer_sol(
+                    self.taker.to_account_info(),
+                    recipient,
+                    self.system_program.to_account_info(),
+                    None,
+                    amount,
+                )
+            };
+        }
+
+        let (from, authority) = if maker_pays {
+            (
+                self.order_payment_ta
+                    .as_ref()
+                    .map(|order_payment_ta| order_payment_ta.to_account_info()),
+                self.order.to_account_info(),
+            )
+        } else {
+            (
+                self.taker_payment_ta
+                    .as_ref()
+                    .map(|taker_payment_ta| taker_payment_ta.to_account_info()),
+                self.taker.to_account_info(),
+            )
+        };
+        let (Some(from), Some(recipient_ta), Some(payment_mint), Some(payment_token_program)) = (
+            from,
+            recipient_ta,
+            &self.payment_mint,
+            &self.payment_token_program,
+        ) else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        create_ata(
+            &recipient_ta,
+            &self.taker.to_account_info(),
+            &payment_mint.to_account_info(),
+            &recipient,
+            &self.system_program.to_account_info(),
+            &payment_token_program.to_account_info(),
+        )?;
+        let cpi_ctx = CpiContext::new_with_signer(
+            payment_token_program.to_account_info(),
+            TransferChecked {
+                from,
+                to: recipient_ta,
+                authority,
+                mint: payment_mint.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)
+    }
+
+    /// returns the rent of the emptied escrow to the maker
+    #[inline(never)]
+    fn close_order_payment_ta(&self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let (Some(order_payment_ta), Some(payment_token_program)) =
+            (&self.order_payment_ta, &self.payment_token_program)
+        else {
+            return Ok(());
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            payment_token_program.to_account_info(),
+            CloseAccount {
+                account: order_payment_ta.to_account_info(),
+                destination: self.maker.to_account_info(),
+                authority: self.order.to_account_info(),
+            },
+            signer_seeds,
+        );
+        close_account(cpi_ctx)
+    }
+
+    // WNS Pre-Transfer Approval, royalties are only due on the share of the sweetener
+    // paid for this mint
+    #[inline(never)]
+    fn approve_wns_transfer(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        authority: AccountInfo<'info>,
+        nft_mint: AccountInfo<'info>,
+        wns_accounts: WnsApprovalAccounts<'info>,
+        royalty_value: u64,
+    ) -> Result<u64> {
+        let mut royalties = 0;
+        let mut authority = authority;
+        let mut distribution_token_account = None;
+        let mut authority_token_account = None;
+        let mut payment_token_program = None;
+        if royalty_value > 0 {
+            if wns_accounts.payment_mint.key() != self.order.payment_mint {
+                return Err(MarketError::WrongAccount.into());
+            }
+            let mint_metadata = get_mint_metadata(&mut nft_mint.to_account_info())?;
+            let royalty_basis_points = mint_metadata
+                .additional_metadata
+                .iter()
+                .find(|(key, _)| key == ROYALTY_BASIS_POINTS_FIELD)
+                .and_then(|(_, value)| u64::from_str(value).ok())
+                .unwrap_or(0);
+            royalties = get_amount_from_bp(royalty_value, royalty_basis_points.into())?;
+
+            let maker_pays = self.order.maker_pays;
+            if self.order.is_native() {
+                // native royalties are a system transfer the order account can't make,
+                // so the taker pays them after being reimbursed from the escrow
+                if maker_pays {
+                    lamport_transfer(
+                        self.order.to_account_info(),
+                        self.taker.to_account_info(),
+                        royalties,
+                    )?;
+                }
+                authority = self.taker.to_account_info();
+            } else {
+                let (Some(payment_mint), Some(token_program)) =
+                    (&self.payment_mint, &self.payment_token_program)
+                else {
+                    return Err(MarketError::WrongAccount.into());
+                };
+                create_ata(
+                    &wns_accounts.distribution_token_account,
+                    &self.taker.to_account_info(),
+                    &payment_mint.to_account_info(),
+                    &wns_accounts.distribution_account,
+                    &self.system_program.to_account_info(),
+                    &token_program.to_account_info(),
+                )?;
+                let payer_payment_ta = if maker_pays {
+                    authority = self.order.to_account_info();
+                    self.order_payment_ta
+                        .as_ref()
+                        .map(|order_payment_ta| order_payment_ta.to_account_info())
+                } else {
+                    authority = self.taker.to_account_info();
+                    self.taker_payment_ta
+                        .as_ref()
+                        .map(|taker_payment_ta| taker_payment_ta.to_account_info())
+                };
+                distribution_token_account =
+                    Some(wns_accounts.distribution_token_account.to_account_info());
+                authority_token_account = Some(payer_payment_ta.ok_or(MarketError::WrongAccount)?);
+                payment_token_program = Some(token_program.to_account_info());
+            }
+        }
+
+        let cpi_accounts = ApproveTransfer {
+            payer: self.taker.to_account_info(),
+            authority,
+            mint: nft_mint,
+            approve_account: wns_accounts.approval_account.to_account_info(),
+            payment_mint: wns_accounts.payment_mint.to_account_info(),
+            distribution_token_account,
+            authority_token_account,
+            distribution_account: wns_accounts.distribution_account.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            distribution_program: wns_accounts.distribution_program.to_account_info(),
+            token_program: self.nft_token_program.to_account_info(),
+            payment_token_program,
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.nft_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        approve_transfer(cpi_ctx, royalty_value)?;
+
+        Ok(royalties)
+    }
+
+    /// pays the creators of an editions mint their share of the royalties on the royalty value
+    /// accounts are the editions deployment, then a creator and creator payment ta per creator
+    /// returns the royalties paid and the number of accounts used
+    #[inline(never)]
+    fn pay_editions_royalties(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        nft_mint: &AccountInfo<'info>,
+        accounts: &[AccountInfo<'info>],
+        royalty_value: u64,
+    ) -> Result<(u64, usize)> {
+        let editions_deployment = accounts.first().ok_or(MarketError::WrongAccount)?;
+        let editions_royalties =
+            get_editions_royalties(&mut nft_mint.to_account_info(), editions_deployment)?;
+        let royalties = get_amount_from_bp(
+            royalty_value,
+            editions_royalties.royalty_basis_points.into(),
+        )?;
+
+        let mut royalties_paid: u64 = 0;
+        for (i, creator) in editions_royalties.creators.iter().enumerate() {
+            let creator_account = accounts.get(1 + 2 * i).ok_or(MarketError::WrongAccount)?;
+            let creator_payment_ta = accounts.get(2 + 2 * i).ok_or(MarketError::WrongAccount)?;
+            if *creator_account.key != creator.address {
+                return Err(MarketError::WrongAccount.into());
+            }
+
+            let creator_amount = get_amount_from_bp(royalties, u128::from(creator.share) * 100)?;
+            self.transfer_payment(
+                signer_seeds,
+                creator_account.to_account_info(),
+                Some(creator_payment_ta.to_account_info()),
+                creator_amount,
+            )?;
+            royalties_paid = royalties_paid
+                .checked_add(creator_amount)
+                .ok_or(MarketError::AmountOverflow)?;
+        }
+
+        Ok((royalties_paid, 1 + 2 * editions_royalties.creators.len()))
+    }
+
+    /// moves one mint, wns mints are approved first and royalties are paid on the royalty value
+    /// item accounts are the mint, source ta and destination ta, then the wns approval
+    /// or editions royalty accounts and transfer hook accounts
+    /// returns the royalties paid
+    #[inline(never)]
+    fn nft_transfer(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        authority_info: AccountInfo<'info>,
+        destination_owner: &AccountInfo<'info>,
+        nft_mint: &InterfaceAccount<'info, Mint>,
+        item: &[AccountInfo<'info>],
+        royalty_value: u64,
+    ) -> Result<u64> {
+        let source_ta = item[1].to_account_info();
+        let destination_ta = item[2].to_account_info();
+        let mut hook_accounts = &item[3..];
+        let mut royalties = 0;
+
+        create_ata(
+            &destination_ta,
+            &self.taker.to_account_info(),
+            &item[0],
+            destination_owner,
+            &self.system_program.to_account_info(),
+            &self.nft_token_program.to_account_info(),
+        )?;
+
+        if self.nft_program.key.to_string() == WNS_PID {
+            if hook_accounts.len() < 5 {
+                return Err(MarketError::WrongAccount.into());
+            }
+            // Item Accounts 3-7 for approval
+            let wns_accounts = WnsApprovalAccounts {
+                approval_account: hook_accounts[0].to_account_info(),
+                distribution_account: hook_accounts[1].to_account_info(),
+                distribution_token_account: hook_accounts[2].to_account_info(),
+                distribution_program: hook_accounts[3].to_account_info(),
+                payment_mint: hook_accounts[4].to_account_info(),
+            };
+            hook_accounts = &hook_accounts[5..];
+            royalties = self.approve_wns_transfer(
+                signer_seeds,
+                authority_info.clone(),
+                item[0].to_account_info(),
+                wns_accounts,
+                royalty_value,
+            )?;
+        } else if royalty_value > 0 {
+            if self.nft_program.key.to_string() == EDITIONS_PID {
+                let (royalties_paid, accounts_used) = self.pay_editions_royalties(
+                    signer_seeds,
+                    &item[0],
+                    hook_accounts,
+                    royalty_value,
+                )?;
+                royalties = royalties_paid;
+                hook_accounts = &hook_accounts[accounts_used..];
+            } else if get_has_royalty_field(&mut item[0].to_account_info()) {
+                // mints carrying royalties must go through their royalty path
+                return Err(MarketError::UnsupportedNft.into());
+            }
+        }
+
+        let mut transfer_ix = transfer_2022(
+            self.nft_token_program.key,
+            source_ta.key,
+            item[0].key,
+            destination_ta.key,
+            authority_info.key,
+            &[],
+            1,
+            nft_mint.decimals,
+        )?;
+
+        let mut account_infos = vec![
+            source_ta.clone(),
+            item[0].to_account_info(),
+            destination_ta.clone(),
+            authority_info.clone(),
+        ];
+
+        if let Some(hook_program) = hook_accounts.last() {
+            // transfer hook
+            add_extra_accounts_for_execute_cpi(
+                &mut transfer_ix,
+                &mut account_infos,
+                hook_program.key,
+                source_ta,
+                item[0].to_account_info(),
+                destination_ta,
+                authority_info,
+                1,
+                hook_accounts,
+            )?;
+        }
+
+        anchor_lang::solana_program::program::invoke_signed(
+            &transfer_ix,
+            &account_infos,
+            signer_seeds,
+        )?;
+
+        Ok(royalties)
+    }
+}
+
+/// trade the wanted mint for the offered mints of a swap order, along with any sweetener
+/// remaining accounts are those of each offered mint in bundle order, then those of the wanted mint
+/// a swap for any mint takes the mint verification or wns group member account
+/// right after the wanted mint's destination ta
+/// the sweetener is charged market fees, and royalties on the mints its payer receives
+#[inline(never)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FillSwap<'info>>,
+    data: SwapFillData,
+) -> Result<()> {
+    let nft_program_key = &ctx.accounts.nft_program.key.to_string().clone();
+
+    let clock = Clock::get()?;
+    if ctx.accounts.order.is_expired(clock.unix_timestamp) {
+        return Err(MarketError::OrderExpired.into());
+    }
+    if ctx
+        .accounts
+        .order
+        .is_stale(OrderEpoch::get_current(&ctx.accounts.order_epoch)?)
+    {
+        return Err(MarketError::StaleOrder.into());
+    }
+
+    let mints = ctx.accounts.bundle.get_mints().to_vec();
+    let total_item_accounts = data
+        .item_accounts
+        .iter()
+        .map(|item_accounts| *item_accounts as usize)
+        .sum::<usize>();
+    if data.item_accounts.len() != mints.len() + 1
+        || data
+            .item_accounts
+            .iter()
+            .any(|item_accounts| *item_accounts < 3)
+        || total_item_accounts != ctx.remaining_accounts.len()
+    {
+        return Err(MarketError::WrongAccount.into());
+    }
+
+    let bump = &get_bump_in_seed_form(&ctx.bumps.order);
+    let signer_seeds: &[&[&[u8]]; 1] = &[&[
+        ORDER_SEED,
+        ctx.accounts.order.nonce.as_ref(),
+        ctx.accounts.order.market.as_ref(),
+        ctx.accounts.order.owner.as_ref(),
+        bump,
+    ][..]];
+
+    // royalties on the sweetener are due on the mints its payer receives,
+    // split evenly between the offered mints when the taker pays it
+    let sweetener = ctx.accounts.order.price;
+    let maker_pays = ctx.accounts.order.maker_pays;
+    let (offered_value, offered_remainder, wanted_value) = if maker_pays {
+        (0, 0, sweetener)
+    } else {
+        let offered_count = mints.len() as u64;
+        (sweetener / offered_count, sweetener % offered_count, 0)
+    };
+    let mut royalties_paid: u64 = 0;
+
+    let order_info = ctx.accounts.order.to_account_info();
+    let taker = ctx.accounts.taker.to_account_info();
+    let maker = ctx.accounts.maker.to_account_info();
+
+    // offered mints move from the maker to the taker with the order as delegate
+    let mut item_start = 0;
+    for (i, mint) in mints.iter().enumerate() {
+        let item_end = item_start + data.item_accounts[i] as usize;
+        let item = &ctx.remaining_accounts[item_start..item_end];
+        item_start = item_end;
+
+        let nft_mint = InterfaceAccount::<Mint>::try_from(&item[0])?;
+        if nft_mint.key() != *mint {
+            return Err(MarketError::InvalidNft.into());
+        }
+        let royalty_value = if i == 0 {
+            offered_value + offered_remainder
+        } else {
+            offered_value
+        };
+        let royalties = ctx.accounts.nft_transfer(
+            signer_seeds,
+            order_info.clone(),
+            &taker,
+            &nft_mint,
+            item,
+            royalty_value,
+        )?;
+        royalties_paid = royalties_paid
+            .checked_add(royalties)
+            .ok_or(MarketError::AmountOverflow)?;
+    }
+
+    // the wanted mint moves from the taker to the maker
+    let wanted_item = &ctx.remaining_accounts[item_start..];
+    let wanted_nft_mint = InterfaceAccount::<Mint>::try_from(&wanted_item[0])?;
+    let wanted_mint = wanted_nft_mint.key();
+    let wanted_royalties = if ctx.accounts.order.swap_mint == Pubkey::default() {
+        let membership_account = wanted_item.get(3).ok_or(MarketError::WrongAccount)?;
+        if *nft_program_key == WNS_PID {
+            verify_wns_mint(
+                wanted_item[0].to_account_info(),
+                membership_account.to_account_info(),
+                ctx.accounts.market.market_identifier,
+            )?;
+        } else {
+            verify_mint_verification(membership_account, wanted_mint, ctx.accounts.market.key())?;
+        }
+        // trait swaps narrow the collection down to the mints of their merkle root
+        if ctx.accounts.order.merkle_root != [0; 32] {
+            verify_merkle_mint(
+                ctx.accounts.order.merkle_root,
+                data.merkle_proof,
+                wanted_mint,
+            )?;
+        }
+
+        let mut transfer_item = wanted_item[..3].to_vec();
+        transfer_item.extend_from_slice(&wanted_item[4..]);
+        ctx.accounts.nft_transfer(
+            signer_seeds,
+            taker.clone(),
+            &maker,
+            &wanted_nft_mint,
+            &transfer_item,
+            wanted_value,
+        )?
+    } else {
+        if wanted_mint != ctx.accounts.order.swap_mint {
+            return Err(MarketError::InvalidNft.into());
+        }
+        ctx.accounts.nft_transfer(
+            signer_seeds,
+            taker.clone(),
+            &maker,
+            &wanted_nft_mint,
+            wanted_item,
+            wanted_value,
+        )?
+    };
+    royalties_paid = royalties_paid
+        .checked_add(wanted_royalties)
+        .ok_or(MarketError::AmountOverflow)?;
+
+    // the payer of the sweetener buys with it, a maker paying it is charged like a bid
+    let fill_fees = if maker_pays {
+        ctx.accounts
+            .order
+            .get_bid_fill_fees(&ctx.accounts.market, 1)?
+    } else if ctx.accounts.order.fees_on {
+        ctx.accounts.market.get_fill_fees(sweetener, false)?
+    } else {
+        FillFees::default()
+    };
+    // maker rebates are paid out of the taker fee
+    let fee_amount = u64::try_from(
+        fill_fees
+            .maker_fee
+            .saturating_add_unsigned(fill_fees.taker_fee),
+    )
+    .map_err(|_| MarketError::AmountUnderflow)?;
+    // a taker paying the sweetener pays their fee on top of it,
+    // a taker receiving it pays theirs out of it and a maker rebate comes out of the escrow
+    let (recipient, recipient_ta, received_amount, maker_rebate) = if maker_pays {
+        (
+            taker.clone(),
+            ctx.accounts.taker_payment_ta.as_ref(),
+            sweetener.checked_sub(fill_fees.taker_fee),
+            fill_fees.maker_fee.min(0).unsigned_abs(),
+        )
+    } else {
+        (
+            maker.clone(),
+            ctx.accounts.maker_payment_ta.as_ref(),
+            sweetener.checked_add_signed(-fill_fees.maker_fee),
+            0,
+        )
+    };
+    let received_amount = received_amount
+        .and_then(|received_amount| received_amount.checked_sub(royalties_paid))
+        .ok_or(MarketError::AmountUnderflow)?;
+    let recipient_ta = recipient_ta.map(|recipient_ta| recipient_ta.to_account_info());
+
+    ctx.accounts
+        .transfer_payment(signer_seeds, recipient, recipient_ta, received_amount)?;
+    let fee_recipient_ta = ctx
+        .accounts
+        .fee_recipient_ta
+        .as_ref()
+        .map(|fee_recipient_ta| fee_recipient_ta.to_account_info());
+    ctx.accounts.transfer_payment(
+        signer_seeds,
+        ctx.accounts.fee_recipient.to_account_info(),
+        fee_recipient_ta,
+        fee_amount,
+    )?;
+    let maker_payment_ta = ctx
+        .accounts
+        .maker_payment_ta
+        .as_ref()
+        .map(|maker_payment_ta| maker_payment_ta.to_account_info());
+    ctx.accounts
+        .transfer_payment(signer_seeds, maker, maker_payment_ta, maker_rebate)?;
+    if maker_pays {
+        ctx.accounts.close_order_payment_ta(signer_seeds)?;
+    }
+
+    emit_cpi!(ctx.accounts.order.get_fill_event(
+        ctx.accounts.order.key(),
+        ctx.accounts.market.market_identifier,
+        ctx.accounts.taker.key(),
+        wanted_mint,
+        1,
+        sweetener,
+        sweetener,
+        &fill_fees,
+        royalties_paid,
+    ));
+
+    // close order account
+    let payment_mint = ctx.accounts.order.payment_mint;
+    Order::edit_order(
+        &mut ctx.accounts.order,
+        sweetener,
+        payment_mint,
+        0,
+        clock.unix_timestamp,
+    );
+    ctx.accounts.market.remove_open_order();
+    ctx.accounts.order.state = OrderState::Closed.into();
+    emit_cpi!(Order::get_edit_event(
+        &mut ctx.accounts.order.clone(),
+        ctx.accounts.order.key(),
+        ctx.accounts.market.market_identifier,
+        OrderEditType::FillAndClose,
+    ));
+
+    Ok(())
+}
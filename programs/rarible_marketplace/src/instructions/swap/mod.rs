@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct SwapData {
+    pub nonce: Pubkey,
+    /// mint wanted in return, default pubkey for any verified mint of the market
+    pub swap_mint: Pubkey,
+    /// narrows a swap for any mint down to the mints of a merkle root
+    pub merkle_root: Option<[u8; 32]>,
+    /// mint of the sweetener, default pubkey if SOL
+    pub payment_mint: Pubkey,
+    /// optional payment on top of the nfts, 0 for a straight swap
+    pub sweetener: u64,
+    /// true if the maker pays the sweetener, which is escrowed in the order
+    pub maker_pays: bool,
+    pub expiry: Option<i64>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct SwapFillData {
+    /// number of remaining accounts each offered mint uses, in bundle order,
+    /// followed by the number the wanted mint uses
+    pub item_accounts: Vec<u8>,
+    pub merkle_proof: Option<Vec<[u8; 32]>>,
+}
+
+pub mod cancel;
+pub mod create;
+pub mod fill;
+
+pub use cancel::*;
+pub use create::*;
+pub use fill::*;
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{errors::MarketError, state::*, utils::transfer_sol};
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+#[event_cpi]
+pub struct DepositWallet<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        constraint = amount > 0,
+        init_if_needed,
+        seeds = [WALLET_SEED,
+        owner.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + std::mem::size_of::<Wallet>()
+    )]
+    pub wallet: Box<Account<'info, Wallet>>,
+    /// payment accounts are left out for deposits in native SOL
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub owner_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = payment_mint,
+        associated_token::authority = wallet,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub wallet_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> DepositWallet<'info> {
+    fn transfer_payment(&self, amount: u64) -> Result<()> {
+        // native balance is held in the lamports of the wallet account
+        if self.payment_mint.is_none() {
+            return transfer_sol(
+                self.owner.to_account_info(),
+                self.wallet.to_account_info(),
+                self.system_program.to_account_info(),
+                None,
+                amount,
+            );
+        }
+
+        let (
+            Some(owner_payment_ta),
+            Some(wallet_payment_ta),
+            Some(payment_mint),
+            Some(payment_token_program),
+        ) = (
+            &self.owner_payment_ta,
+            &self.wallet_payment_ta,
+            &self.payment_mint,
+            &self.payment_token_program,
+        )
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+
+        let cpi_ctx = CpiContext::new(
+            payment_token_program.to_account_info(),
+            TransferChecked {
+                from: owner_payment_ta.to_account_info(),
+                to: wallet_payment_ta.to_account_info(),
+                authority: self.owner.to_account_info(),
+                mint: payment_mint.to_account_info(),
+            },
+        );
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)
+    }
+}
+
+/// add to the bidding balance of the owner, creating their wallet account on first deposit
+#[inline(always)]
+pub fn handler(ctx: Context<DepositWallet>, amount: u64) -> Result<()> {
+    msg!("Deposit to wallet: {}", ctx.accounts.wallet.key());
+
+    if ctx.accounts.wallet.version == 0 {
+        let owner = ctx.accounts.owner.key();
+        ctx.accounts.wallet.init(owner);
+    }
+    ctx.accounts.transfer_payment(amount)?;
+
+    let payment_mint = ctx
+        .accounts
+        .payment_mint
+        .as_ref()
+        .map(|payment_mint| payment_mint.key())
+        .unwrap_or_default();
+    emit_cpi!(ctx.accounts.wallet.get_edit_event(
+        ctx.accounts.wallet.key(),
+        payment_mint,
+        amount,
+        WalletEditType::Deposit,
+    ));
+
+    Ok(())
+}
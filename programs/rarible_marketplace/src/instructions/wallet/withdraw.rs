@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    errors::MarketError,
+    state::*,
+    utils::{get_bump_in_seed_form, lamport_transfer},
+};
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+#[event_cpi]
+pub struct WithdrawWallet<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        constraint = amount > 0,
+        constraint = wallet.owner == owner.key(),
+        seeds = [WALLET_SEED,
+        owner.key().as_ref()],
+        bump,
+    )]
+    pub wallet: Box<Account<'info, Wallet>>,
+    /// payment accounts are left out for withdrawals in native SOL
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = payment_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub owner_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = wallet,
+        associated_token::token_program = payment_token_program,
+    )]
+    pub wallet_payment_ta: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> WithdrawWallet<'info> {
+    fn transfer_payment(&self, signer_seeds: &[&[&[u8]]], amount: u64) -> Result<()> {
+        // the wallet account keeps its rent exempt minimum
+        if self.payment_mint.is_none() {
+            let wallet = self.wallet.to_account_info();
+            if Wallet::get_native_balance(&wallet)? < amount {
+                return Err(MarketError::InsufficientBalance.into());
+            }
+            return lamport_transfer(wallet, self.owner.to_account_info(), amount);
+        }
+
+        let (
+            Some(owner_payment_ta),
+            Some(wallet_payment_ta),
+            Some(payment_mint),
+            Some(payment_token_program),
+        ) = (
+            &self.owner_payment_ta,
+            &self.wallet_payment_ta,
+            &self.payment_mint,
+            &self.payment_token_program,
+        )
+        else {
+            return Err(MarketError::WrongAccount.into());
+        };
+        if wallet_payment_ta.amount < amount {
+            return Err(MarketError::InsufficientBalance.into());
+        }
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            payment_token_program.to_account_info(),
+            TransferChecked {
+                from: wallet_payment_ta.to_account_info(),
+                to: owner_payment_ta.to_account_info(),
+                authority: self.wallet.to_account_info(),
+                mint: payment_mint.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_ctx, amount, payment_mint.decimals)
+    }
+}
+
+/// take back part of the bidding balance of the owner
+/// wallet backed bids the balance no longer covers fail to fill
+#[inline(always)]
+pub fn handler(ctx: Context<WithdrawWallet>, amount: u64) -> Result<()> {
+    msg!("Withdraw from wallet: {}", ctx.accounts.wallet.key());
+
+    let bump = &get_bump_in_seed_form(&ctx.bumps.wallet);
+    let signer_seeds: &[&[&[u8]]; 1] =
+        &[&[WALLET_SEED, ctx.accounts.wallet.owner.as_ref(), bump][..]];
+    ctx.accounts.transfer_payment(signer_seeds, amount)?;
+
+    let payment_mint = ctx
+        .accounts
+        .payment_mint
+        .as_ref()
+        .map(|payment_mint| payment_mint.key())
+        .unwrap_or_default();
+    emit_cpi!(ctx.accounts.wallet.get_edit_event(
+        ctx.accounts.wallet.key(),
+        payment_mint,
+        amount,
+        WalletEditType::Withdraw,
+    ));
+
+    Ok(())
+}
@@ -21,6 +21,18 @@ pub mod marketplace {
         instructions::market::init::handler(ctx, params)
     }
 
+    /// update market fee settings, state and payment mint allowlist
+    #[inline(never)]
+    pub fn update_market(ctx: Context<UpdateMarket>, params: UpdateMarketParams) -> Result<()> {
+        instructions::market::update::handler(ctx, params)
+    }
+
+    /// close a market account
+    #[inline(never)]
+    pub fn close_market(ctx: Context<CloseMarket>) -> Result<()> {
+        instructions::market::close::handler(ctx)
+    }
+
     /// initializer a new market
     #[inline(never)]
     pub fn verify_mint(ctx: Context<VerifyMint>) -> Result<()> {
@@ -47,8 +59,37 @@ pub mod marketplace {
     pub fn fill_order<'info>(
         ctx: Context<'_, '_, '_, 'info, FillOrder<'info>>,
         amount: u64,
+        merkle_proof: Option<Vec<[u8; 32]>>,
+    ) -> Result<()> {
+        instructions::order::fill::handler(ctx, amount, merkle_proof)
+    }
+
+    /// edit the price or size of an order
+    #[inline(never)]
+    pub fn edit_order(ctx: Context<EditOrder>, data: EditOrderData) -> Result<()> {
+        instructions::order::edit::handler(ctx, data)
+    }
+
+    /// close an expired order, callable by anyone
+    #[inline(never)]
+    pub fn close_expired_order<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseExpiredOrder<'info>>,
     ) -> Result<()> {
-        instructions::order::fill::handler(ctx, amount)
+        instructions::order::close_expired::handler(ctx)
+    }
+
+    /// cancel every order of the signer by moving to a new order epoch
+    #[inline(never)]
+    pub fn cancel_all_orders(ctx: Context<CancelAllOrders>) -> Result<()> {
+        instructions::order::cancel_all::handler(ctx)
+    }
+
+    /// close orders cancelled by an order epoch increase, callable by anyone
+    #[inline(never)]
+    pub fn close_stale_orders<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseStaleOrders<'info>>,
+    ) -> Result<()> {
+        instructions::order::close_stale::handler(ctx)
     }
 
     /// cancel a buy order
@@ -64,4 +105,186 @@ pub mod marketplace {
     ) -> Result<()> {
         instructions::order::cancel_list::handler(ctx)
     }
+
+    /// fill several listings of a market in one instruction
+    #[inline(never)]
+    pub fn sweep<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Sweep<'info>>,
+        max_spend: u64,
+    ) -> Result<()> {
+        instructions::order::sweep::handler(ctx, max_spend)
+    }
+
+    /// settle a bid against a listing it crosses
+    #[inline(never)]
+    pub fn match_orders<'info>(
+        ctx: Context<'_, '_, '_, 'info, MatchOrders<'info>>,
+        merkle_proof: Option<Vec<[u8; 32]>>,
+    ) -> Result<()> {
+        instructions::order::match_orders::handler(ctx, merkle_proof)
+    }
+
+    /// create the nonce bitmap used to list by signature
+    #[inline(never)]
+    pub fn init_nonce_bitmap(ctx: Context<InitNonceBitmap>) -> Result<()> {
+        instructions::signed::init::handler(ctx)
+    }
+
+    /// cancel listings signed off-chain
+    #[inline(never)]
+    pub fn cancel_signed_listings(
+        ctx: Context<CancelSignedListings>,
+        nonces: Vec<u64>,
+    ) -> Result<()> {
+        instructions::signed::cancel::handler(ctx, nonces)
+    }
+
+    /// fill a listing signed off-chain
+    #[inline(never)]
+    pub fn fill_signed_listing<'info>(
+        ctx: Context<'_, '_, '_, 'info, FillSignedListing<'info>>,
+        listing: SignedListing,
+    ) -> Result<()> {
+        instructions::signed::fill::handler(ctx, listing)
+    }
+
+    /// list a compressed nft, held by the order until filled or cancelled
+    #[inline(never)]
+    pub fn list_compressed<'info>(
+        ctx: Context<'_, '_, '_, 'info, CompressedInitSellOrder<'info>>,
+        data: CompressedOrderData,
+    ) -> Result<()> {
+        instructions::compressed::sell::init::handler(ctx, data)
+    }
+
+    /// fill a compressed nft listing
+    #[inline(never)]
+    pub fn fill_compressed_listing<'info>(
+        ctx: Context<'_, '_, '_, 'info, CompressedFillSellOrder<'info>>,
+        data: CompressedFillOrderData,
+    ) -> Result<()> {
+        instructions::compressed::sell::fill::handler(ctx, data)
+    }
+
+    /// fill a bid with a compressed nft
+    #[inline(never)]
+    pub fn fill_compressed_bid<'info>(
+        ctx: Context<'_, '_, '_, 'info, CompressedFillBuyOrder<'info>>,
+        data: CompressedFillOrderData,
+    ) -> Result<()> {
+        instructions::compressed::buy::fill::handler(ctx, data)
+    }
+
+    /// cancel a compressed nft listing
+    #[inline(never)]
+    pub fn cancel_compressed_listing<'info>(
+        ctx: Context<'_, '_, '_, 'info, CompressedCloseSellOrder<'info>>,
+        data: CompressedFillOrderData,
+    ) -> Result<()> {
+        instructions::compressed::sell::close::handler(ctx, data)
+    }
+
+    /// list a core asset with the order as its transfer delegate
+    #[inline(never)]
+    pub fn list_core<'info>(
+        ctx: Context<'_, '_, '_, 'info, ListCoreAsset<'info>>,
+        data: CoreListData,
+    ) -> Result<()> {
+        instructions::core_asset::list::handler(ctx, data)
+    }
+
+    /// fill a core asset listing or bid
+    #[inline(never)]
+    pub fn fill_core_order<'info>(
+        ctx: Context<'_, '_, '_, 'info, FillCoreOrder<'info>>,
+        merkle_proof: Option<Vec<[u8; 32]>>,
+    ) -> Result<()> {
+        instructions::core_asset::fill::handler(ctx, merkle_proof)
+    }
+
+    /// cancel a core asset listing
+    #[inline(never)]
+    pub fn cancel_core_listing<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelCoreListing<'info>>,
+    ) -> Result<()> {
+        instructions::core_asset::cancel_list::handler(ctx)
+    }
+
+    /// outbid the highest bidder of an english auction
+    #[inline(never)]
+    pub fn bid_auction(ctx: Context<BidAuction>, amount: u64) -> Result<()> {
+        instructions::auction::bid::handler(ctx, amount)
+    }
+
+    /// settle an ended english auction with the highest bidder
+    #[inline(never)]
+    pub fn settle_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, SettleAuction<'info>>,
+    ) -> Result<()> {
+        instructions::auction::settle::handler(ctx)
+    }
+
+    /// list several nfts for one total price
+    #[inline(never)]
+    pub fn list_bundle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ListBundle<'info>>,
+        data: BundleListData,
+    ) -> Result<()> {
+        instructions::bundle::list::handler(ctx, data)
+    }
+
+    /// buy every nft of a bundle listing
+    #[inline(never)]
+    pub fn fill_bundle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FillBundle<'info>>,
+        data: BundleFillData,
+    ) -> Result<()> {
+        instructions::bundle::fill::handler(ctx, data)
+    }
+
+    /// cancel a bundle listing
+    #[inline(never)]
+    pub fn cancel_bundle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelBundle<'info>>,
+    ) -> Result<()> {
+        instructions::bundle::cancel::handler(ctx)
+    }
+
+    /// offer nfts for a wanted nft, with an optional sweetener
+    #[inline(never)]
+    pub fn create_swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateSwap<'info>>,
+        data: SwapData,
+    ) -> Result<()> {
+        instructions::swap::create::handler(ctx, data)
+    }
+
+    /// trade the wanted nft for the nfts offered by a swap order
+    #[inline(never)]
+    pub fn fill_swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FillSwap<'info>>,
+        data: SwapFillData,
+    ) -> Result<()> {
+        instructions::swap::fill::handler(ctx, data)
+    }
+
+    /// cancel a swap order
+    #[inline(never)]
+    pub fn cancel_swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelSwap<'info>>,
+    ) -> Result<()> {
+        instructions::swap::cancel::handler(ctx)
+    }
+
+    /// add to the bidding balance backing wallet backed bids
+    #[inline(never)]
+    pub fn deposit_wallet(ctx: Context<DepositWallet>, amount: u64) -> Result<()> {
+        instructions::wallet::deposit::handler(ctx, amount)
+    }
+
+    /// take back part of the bidding balance
+    #[inline(never)]
+    pub fn withdraw_wallet(ctx: Context<WithdrawWallet>, amount: u64) -> Result<()> {
+        instructions::wallet::withdraw::handler(ctx, amount)
+    }
 }
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+pub const BUNDLE_VERSION: u8 = 1;
+/// most mints a bundle can hold while filling within compute and account limits
+pub const MAX_BUNDLE_SIZE: usize = 5;
+
+#[account()]
+/// bundle account - mints sold together by a bundle order or offered by a swap
+pub struct Bundle {
+    /// bundle account version
+    pub version: u8,
+    /// order selling or swapping the bundle, its nft_mint is the bundle address
+    pub order: Pubkey,
+    /// number of mints in the bundle
+    pub size: u8,
+    /// mints in the bundle, default pubkey past size
+    pub mints: [Pubkey; MAX_BUNDLE_SIZE],
+    /// reserved space for future changes
+    reserve: [u8; 64],
+}
+
+impl Bundle {
+    /// initialize a new bundle account
+    pub fn init(&mut self, order: Pubkey, mints: &[Pubkey]) {
+        self.version = BUNDLE_VERSION;
+        self.order = order;
+        self.size = mints.len() as u8;
+        self.mints[..mints.len()].copy_from_slice(mints);
+    }
+
+    /// mints in the bundle
+    pub fn get_mints(&self) -> &[Pubkey] {
+        &self.mints[..self.size as usize]
+    }
+}
@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
 use num_enum::IntoPrimitive;
 
-use super::VERIFICATION_SEED;
+use super::{MAX_FEE_BPS, MAX_PAYMENT_MINTS, VERIFICATION_SEED};
+use crate::{errors::MarketError, utils::get_amount_from_bp};
 
-pub const MARKET_VERSION: u8 = 1;
+pub const MARKET_VERSION: u8 = 2;
+/// first market version counting its open orders
+pub const OPEN_ORDERS_MARKET_VERSION: u8 = 2;
 
 #[account()]
 pub struct Market {
@@ -17,10 +20,25 @@ pub struct Market {
     pub state: u8,
     /// address that should receive market fees
     pub fee_recipient: Pubkey,
-    /// fee basis points
+    /// fee basis points, charged to the seller when maker and taker fees are unset
     pub fee_bps: u64,
+    /// maker fee basis points, negative for a maker rebate
+    pub maker_fee_bps: i64,
+    /// taker fee basis points
+    pub taker_fee_bps: u64,
+    /// true if orders are limited to the allowlisted payment mints
+    pub payment_allowlist_on: bool,
+    /// true if native SOL is allowlisted
+    pub native_allowed: bool,
+    /// allowlisted payment mints, default pubkey for empty slots
+    pub payment_mints: [Pubkey; MAX_PAYMENT_MINTS],
+    /// basis points of the bid surplus of a match paid to the keeper, the rest is refunded to the bidder
+    pub keeper_tip_bps: u64,
+    /// number of orders on the market that are not closed yet, the market can only be closed at 0
+    /// markets older than OPEN_ORDERS_MARKET_VERSION opened orders before it was counted
+    pub open_orders: u64,
     /// reserved space for future changes
-    pub reserve: [u8; 512],
+    pub reserve: [u8; 222],
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, PartialEq, IntoPrimitive)]
@@ -36,6 +54,8 @@ pub enum MarketState {
 #[repr(u8)]
 pub enum MarketEditType {
     Init,
+    Edit,
+    Close,
 }
 
 #[account()]
@@ -64,6 +84,19 @@ pub struct MarketEditEvent {
     pub state: u8,
     pub fee_recipient: String,
     pub fee_bps: u64,
+    pub maker_fee_bps: i64,
+    pub taker_fee_bps: u64,
+    pub payment_allowlist_on: bool,
+    pub native_allowed: bool,
+    pub payment_mints: Vec<String>,
+    pub keeper_tip_bps: u64,
+}
+
+/// fees charged on a fill, a negative maker fee is a rebate paid out of the taker fee
+#[derive(Default)]
+pub struct FillFees {
+    pub maker_fee: i64,
+    pub taker_fee: u64,
 }
 
 impl Market {
@@ -83,11 +116,135 @@ impl Market {
         self.fee_bps = fee_bps;
     }
 
+    /// edit market fee settings and state, None leaves the value unchanged
+    pub fn edit(
+        &mut self,
+        fee_recipient: Option<Pubkey>,
+        fee_bps: Option<u64>,
+        maker_fee_bps: Option<i64>,
+        taker_fee_bps: Option<u64>,
+        state: Option<u8>,
+    ) {
+        if let Some(fee_recipient) = fee_recipient {
+            self.fee_recipient = fee_recipient;
+        }
+        if let Some(fee_bps) = fee_bps {
+            self.fee_bps = fee_bps;
+        }
+        if let Some(maker_fee_bps) = maker_fee_bps {
+            self.maker_fee_bps = maker_fee_bps;
+        }
+        if let Some(taker_fee_bps) = taker_fee_bps {
+            self.taker_fee_bps = taker_fee_bps;
+        }
+        if let Some(state) = state {
+            self.state = state;
+        }
+    }
+
+    /// edit the payment mint allowlist, None leaves the value unchanged
+    /// payment_mints replaces the whole list
+    pub fn edit_payment_allowlist(
+        &mut self,
+        payment_allowlist_on: Option<bool>,
+        native_allowed: Option<bool>,
+        payment_mints: Option<&[Pubkey]>,
+    ) {
+        if let Some(payment_allowlist_on) = payment_allowlist_on {
+            self.payment_allowlist_on = payment_allowlist_on;
+        }
+        if let Some(native_allowed) = native_allowed {
+            self.native_allowed = native_allowed;
+        }
+        if let Some(payment_mints) = payment_mints {
+            self.payment_mints = [Pubkey::default(); MAX_PAYMENT_MINTS];
+            self.payment_mints[..payment_mints.len()].copy_from_slice(payment_mints);
+        }
+    }
+
+    /// return true if orders can be priced in the payment mint, default pubkey for SOL
+    /// markets without an allowlist take any payment mint
+    pub fn is_payment_mint_allowed(&self, payment_mint: &Pubkey) -> bool {
+        if !self.payment_allowlist_on {
+            return true;
+        }
+        if *payment_mint == Pubkey::default() {
+            return self.native_allowed;
+        }
+        self.payment_mints.contains(payment_mint)
+    }
+
+    /// return true if the market has counted its open orders since it was created
+    pub fn counts_open_orders(&self) -> bool {
+        self.version >= OPEN_ORDERS_MARKET_VERSION
+    }
+
+    /// count a new order on the market
+    pub fn add_open_order(&mut self) {
+        self.open_orders = self.open_orders.saturating_add(1);
+    }
+
+    /// uncount a closed order of the market
+    pub fn remove_open_order(&mut self) {
+        self.open_orders = self.open_orders.saturating_sub(1);
+    }
+
     /// return true if the market is active
     pub fn is_active(state: u8) -> bool {
         state != <MarketState as Into<u8>>::into(MarketState::Closed)
     }
 
+    /// return true if a maker rebate is covered by the taker fee and total fees stay under 100%
+    pub fn is_valid_fee_split(maker_fee_bps: i64, taker_fee_bps: u64) -> bool {
+        let total_fee_bps = maker_fee_bps.saturating_add_unsigned(taker_fee_bps);
+        taker_fee_bps <= MAX_FEE_BPS && (0..=MAX_FEE_BPS as i64).contains(&total_fee_bps)
+    }
+
+    /// maker and taker fees on a fill value
+    /// markets without a maker/taker split charge fee_bps to the seller
+    /// a positive maker fee on a bid is escrowed with the bid and charged by Order::get_bid_fill_fees
+    pub fn get_fill_fees(&self, value: u64, is_buy: bool) -> Result<FillFees> {
+        if self.maker_fee_bps == 0 && self.taker_fee_bps == 0 {
+            let fee = get_amount_from_bp(value, self.fee_bps.into())?;
+            return Ok(if is_buy {
+                FillFees {
+                    maker_fee: 0,
+                    taker_fee: fee,
+                }
+            } else {
+                FillFees {
+                    maker_fee: i64::try_from(fee).map_err(|_| MarketError::AmountOverflow)?,
+                    taker_fee: 0,
+                }
+            });
+        }
+
+        let maker_fee = get_amount_from_bp(value, self.maker_fee_bps.unsigned_abs().into())?;
+        let maker_fee = i64::try_from(maker_fee).map_err(|_| MarketError::AmountOverflow)?;
+        let taker_fee = get_amount_from_bp(value, self.taker_fee_bps.into())?;
+        Ok(if self.maker_fee_bps < 0 {
+            FillFees {
+                maker_fee: -maker_fee,
+                taker_fee,
+            }
+        } else if is_buy {
+            FillFees {
+                maker_fee: 0,
+                taker_fee,
+            }
+        } else {
+            FillFees {
+                maker_fee,
+                taker_fee,
+            }
+        })
+    }
+
+    /// maker fee a new bid escrows on top of its price, rebates are paid on fill instead
+    pub fn get_bid_maker_fee_bps(&self) -> u16 {
+        u16::try_from(self.maker_fee_bps.max(0)).unwrap_or(MAX_FEE_BPS as u16)
+    }
+
     pub fn get_edit_event(
         &mut self,
         address: Pubkey,
@@ -102,6 +259,17 @@ impl Market {
             state: self.state,
             fee_recipient: self.fee_recipient.to_string(),
             fee_bps: self.fee_bps,
+            maker_fee_bps: self.maker_fee_bps,
+            taker_fee_bps: self.taker_fee_bps,
+            payment_allowlist_on: self.payment_allowlist_on,
+            native_allowed: self.native_allowed,
+            payment_mints: self
+                .payment_mints
+                .iter()
+                .filter(|payment_mint| **payment_mint != Pubkey::default())
+                .map(|payment_mint| payment_mint.to_string())
+                .collect(),
+            keeper_tip_bps: self.keeper_tip_bps,
         }
     }
 }
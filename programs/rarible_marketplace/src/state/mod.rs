@@ -1,16 +1,33 @@
 pub const MARKET_SEED: &[u8] = b"market";
 pub const ORDER_SEED: &[u8] = b"order";
 pub const VERIFICATION_SEED: &[u8] = b"verification";
+pub const BUNDLE_SEED: &[u8] = b"bundle";
+pub const NONCE_SEED: &[u8] = b"nonce";
+pub const ORDER_EPOCH_SEED: &[u8] = b"epoch";
+pub const WALLET_SEED: &[u8] = b"wallet";
 
-pub const TOKEN_PID: &str = "";
+pub const MAX_FEE_BPS: u64 = 10000;
+/// most payment mints a market allowlist can hold
+pub const MAX_PAYMENT_MINTS: usize = 8;
+
+pub const TOKEN_PID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
 pub const TOKEN_EXT_PID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
-pub const BUBBLEGUM_PID: &str = "";
+pub const BUBBLEGUM_PID: &str = "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY";
 
-pub const METAPLEX_PID: &str = "";
+pub const METAPLEX_PID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
 pub const WNS_PID: &str = "wns1gDLt8fgLcGhWi5MqAqgXpwEP1JftKE9eZnXS1HM";
+pub const EDITIONS_PID: &str = "DsaxZA54w7N9z8jxobtzy3rhQQmXjngjvJNvX1HubtkJ";
 
+pub mod bundle;
 pub mod market;
+pub mod nonce_bitmap;
 pub mod order;
+pub mod order_epoch;
+pub mod wallet;
 
+pub use bundle::*;
 pub use market::*;
+pub use nonce_bitmap::*;
 pub use order::*;
+pub use order_epoch::*;
+pub use wallet::*;
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+pub const NONCE_BITMAP_VERSION: u8 = 1;
+/// number of signed listing nonces a user has per market
+pub const MAX_SIGNED_NONCES: u64 = 8192;
+
+#[account()]
+/// nonce bitmap account - replay protection for the signed listings of a user on a market
+/// also the delegate of the token accounts listed by signature
+pub struct NonceBitmap {
+    /// nonce bitmap account version
+    pub version: u8,
+    /// market the signed listings belong to
+    pub market: Pubkey,
+    /// signer of the listings
+    pub owner: Pubkey,
+    /// one bit per nonce, set once the listing is filled or cancelled
+    pub bitmap: [u8; MAX_SIGNED_NONCES as usize / 8],
+    /// reserved space for future changes
+    reserve: [u8; 64],
+}
+
+impl NonceBitmap {
+    /// initialize a new nonce bitmap account
+    pub fn init(&mut self, market: Pubkey, owner: Pubkey) {
+        self.version = NONCE_BITMAP_VERSION;
+        self.market = market;
+        self.owner = owner;
+    }
+
+    /// return true if the nonce can still be used
+    pub fn is_nonce_free(&self, nonce: u64) -> bool {
+        nonce < MAX_SIGNED_NONCES && self.bitmap[(nonce / 8) as usize] & (1 << (nonce % 8)) == 0
+    }
+
+    /// mark the nonce as used
+    pub fn use_nonce(&mut self, nonce: u64) {
+        self.bitmap[(nonce / 8) as usize] |= 1 << (nonce % 8);
+    }
+}
@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 use num_enum::IntoPrimitive;
 
+use super::{FillFees, Market};
+use crate::{errors::MarketError, utils::get_amount_from_bp};
+
 pub const ORDER_VERSION: u8 = 1;
 
 #[account()]
@@ -14,12 +17,13 @@ pub struct Order {
     pub market: Pubkey,
     /// owner of the order account
     pub owner: Pubkey,
-    /// type of order - buy/sell
+    /// type of order - buy/sell/auction/swap
     pub side: u8,
-    /// number of bids order is making
-    /// always for 1 for sell
+    /// number of units the order is buying or selling
+    /// reduced by partial fills
     pub size: u64,
     /// bid amount in lamports
+    /// starting price of a dutch auction
     pub price: u64,
     /// order state - ready/partial/closed
     pub state: u8,
@@ -28,13 +32,47 @@ pub struct Order {
     /// last time the order was edited
     pub last_edit_time: i64,
     /// nft mint in case order is a sell order
+    /// default pubkey for collection bids, which any verified mint of the market can fill
+    /// bundle address for bundle listings and swaps
     pub nft_mint: Pubkey,
     /// mint for the payment, default pubkey if SOL
     pub payment_mint: Pubkey,
     /// fees on for this order
     pub fees_on: bool,
+    /// time after which the order can no longer be filled, 0 if the order never expires
+    pub expiry: i64,
+    /// merkle root of the mints a trait bid accepts, zeroed for any other order
+    pub merkle_root: [u8; 32],
+    /// price a dutch auction listing declines to
+    pub end_price: u64,
+    /// time a dutch auction listing reaches end_price or an english auction ends
+    /// 0 for fixed price orders
+    pub end_time: i64,
+    /// current highest bidder of an english auction
+    pub highest_bidder: Pubkey,
+    /// current highest bid of an english auction, escrowed in the order
+    pub highest_bid: u64,
+    /// amount a new bid must add to the highest bid
+    pub min_increment: u64,
+    /// bids placed this close to the end push end_time back to this far from the bid
+    pub time_extension: i64,
+    /// only wallet that can fill a private listing, default pubkey for public orders
+    pub taker: Pubkey,
+    /// mint a swap wants in return, default pubkey for any verified mint of the market
+    pub swap_mint: Pubkey,
+    /// true if the maker of a swap adds price on top of their nfts, false if the taker does
+    pub maker_pays: bool,
+    /// order epoch of the owner when the order was created, stale once the owner moves past it
+    pub epoch: u64,
+    /// true if the listed nft is held in a token account of the order instead of delegated to it
+    pub escrowed: bool,
+    /// true if a bid is paid from the wallet account of the owner when filled instead of an escrow
+    pub wallet_backed: bool,
+    /// maker fee of a bid or of a swap sweetener its maker pays, escrowed on top of the price
+    /// 0 when the market charged no positive maker fee as the order was placed
+    pub maker_fee_bps: u16,
     /// reserved space for future changes split up due to serialization constraints
-    reserve_0: [u8; 256],
+    reserve_0: [u8; 67],
     /// reserved space for future changes
     reserve_1: [u8; 128],
     /// reserved space for future changes
@@ -55,6 +93,7 @@ pub enum OrderEditType {
     Fill,
     Close,
     FillAndClose,
+    AuctionBid,
 }
 
 #[event]
@@ -74,6 +113,36 @@ pub struct OrderEditEvent {
     pub nft_mint: String,
     pub payment_mint: String,
     pub market_identifier: String,
+    pub expiry: i64,
+    pub end_price: u64,
+    pub end_time: i64,
+    pub highest_bidder: String,
+    pub highest_bid: u64,
+    pub is_private: bool,
+    pub taker: String,
+    pub swap_mint: String,
+    pub maker_pays: bool,
+    pub epoch: u64,
+    pub escrowed: bool,
+    pub wallet_backed: bool,
+    pub maker_fee_bps: u16,
+}
+
+#[event]
+pub struct OrderFillEvent {
+    pub address: String,
+    pub market_identifier: String,
+    pub maker: String,
+    pub taker: String,
+    pub side: u8,
+    pub nft_mint: String,
+    pub payment_mint: String,
+    pub amount: u64,
+    pub price: u64,
+    pub value: u64,
+    pub maker_fee: i64,
+    pub taker_fee: u64,
+    pub royalties: u64,
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, IntoPrimitive)]
@@ -84,6 +153,10 @@ pub enum OrderSide {
     Buy,
     /// bid for selling NFT
     Sell,
+    /// english auction of an NFT, filled by settle_auction
+    Auction,
+    /// trade of NFTs for an NFT, filled by fill_swap
+    Swap,
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, PartialEq, IntoPrimitive)]
@@ -92,7 +165,7 @@ pub enum OrderSide {
 pub enum OrderState {
     /// order account has been created and ready to be filled
     Ready,
-    /// some of the bids have been filled, used only in UI to show some orders have been filled
+    /// some of the size has been filled, the rest can still be filled
     Partial,
     /// all bids have been filled and the order account is now closed
     Closed,
@@ -114,6 +187,7 @@ impl Order {
         price: u64,
         state: u8,
         fees_on: bool,
+        expiry: i64,
     ) {
         self.version = ORDER_VERSION;
         self.market = market;
@@ -128,11 +202,12 @@ impl Order {
         self.init_time = time;
         self.last_edit_time = time;
         self.fees_on = fees_on;
+        self.expiry = expiry;
     }
 
     /// edit a buy order account
     /// if size is 0, order is closed
-    /// any size change is considered partial
+    /// fills that leave size are partial
     pub fn edit_order(
         &mut self,
         new_price: u64,
@@ -151,6 +226,107 @@ impl Order {
         state != <OrderState as Into<u8>>::into(OrderState::Closed)
     }
 
+    /// return true if the order is priced in native SOL
+    pub fn is_native(&self) -> bool {
+        self.payment_mint == Pubkey::default()
+    }
+
+    /// return true if the order is a bid on any mint of the market
+    pub fn is_collection_bid(&self) -> bool {
+        self.side == <OrderSide as Into<u8>>::into(OrderSide::Buy)
+            && self.nft_mint == Pubkey::default()
+    }
+
+    /// return true if the order is a bid on the mints of a merkle root
+    pub fn is_trait_bid(&self) -> bool {
+        self.is_collection_bid() && self.merkle_root != [0; 32]
+    }
+
+    /// return true if the listing price declines over time
+    pub fn is_dutch_auction(&self) -> bool {
+        self.side == <OrderSide as Into<u8>>::into(OrderSide::Sell) && self.end_time != 0
+    }
+
+    /// return true if the order is an english auction
+    pub fn is_auction(&self) -> bool {
+        self.side == <OrderSide as Into<u8>>::into(OrderSide::Auction)
+    }
+
+    /// return true if the order trades nfts for an nft
+    pub fn is_swap(&self) -> bool {
+        self.side == <OrderSide as Into<u8>>::into(OrderSide::Swap)
+    }
+
+    /// price of one unit at the given time
+    /// dutch auctions decline linearly from price at init_time to end_price at end_time
+    pub fn get_current_price(&self, time: i64) -> u64 {
+        if !self.is_dutch_auction() || time <= self.init_time {
+            return self.price;
+        }
+        if time >= self.end_time {
+            return self.end_price;
+        }
+
+        let elapsed = (time - self.init_time) as u128;
+        let duration = (self.end_time - self.init_time) as u128;
+        let decay = u128::from(self.price - self.end_price) * elapsed / duration;
+        self.price - decay as u64
+    }
+
+    /// return true if the order is reserved for a single taker
+    pub fn is_private(&self) -> bool {
+        self.taker != Pubkey::default()
+    }
+
+    /// return true if the owner has cancelled the order by moving to a later epoch
+    pub fn is_stale(&self, current_epoch: u64) -> bool {
+        self.epoch < current_epoch
+    }
+
+    /// escrow held for size units of a bid, or for the sweetener of a swap its maker pays
+    /// the price plus the maker fee of the order
+    pub fn get_bid_escrow(&self, size: u64) -> Result<u64> {
+        let value = size
+            .checked_mul(self.price)
+            .ok_or(MarketError::AmountOverflow)?;
+        let maker_fee = get_amount_from_bp(value, self.maker_fee_bps.into())?;
+        Ok(value
+            .checked_add(maker_fee)
+            .ok_or(MarketError::AmountOverflow)?)
+    }
+
+    /// fees on a fill of amount units of a bid or of a swap sweetener its maker pays
+    /// a positive maker fee is the escrow released by the fill beyond its value,
+    /// so partial fills add up to the fee escrowed with the order
+    pub fn get_bid_fill_fees(&self, market: &Market, amount: u64) -> Result<FillFees> {
+        let value = amount
+            .checked_mul(self.price)
+            .ok_or(MarketError::AmountOverflow)?;
+        if !self.fees_on {
+            return Ok(FillFees::default());
+        }
+        let mut fill_fees = market.get_fill_fees(value, true)?;
+        if self.maker_fee_bps > 0 {
+            let remaining_size = self
+                .size
+                .checked_sub(amount)
+                .ok_or(MarketError::InsufficientOrderSize)?;
+            let maker_fee = self
+                .get_bid_escrow(self.size)?
+                .checked_sub(self.get_bid_escrow(remaining_size)?)
+                .and_then(|released| released.checked_sub(value))
+                .ok_or(MarketError::AmountUnderflow)?;
+            fill_fees.maker_fee =
+                i64::try_from(maker_fee).map_err(|_| MarketError::AmountOverflow)?;
+        }
+        Ok(fill_fees)
+    }
+
+    /// return true if the order has an expiry and it has passed
+    pub fn is_expired(&self, time: i64) -> bool {
+        self.expiry != 0 && time >= self.expiry
+    }
+
     pub fn get_edit_event(
         &mut self,
         address: Pubkey,
@@ -173,6 +349,51 @@ impl Order {
             nft_mint: self.nft_mint.to_string(),
             payment_mint: self.payment_mint.to_string(),
             market_identifier: market_identifier.to_string(),
+            expiry: self.expiry,
+            end_price: self.end_price,
+            end_time: self.end_time,
+            highest_bidder: self.highest_bidder.to_string(),
+            highest_bid: self.highest_bid,
+            is_private: self.is_private(),
+            taker: self.taker.to_string(),
+            swap_mint: self.swap_mint.to_string(),
+            maker_pays: self.maker_pays,
+            epoch: self.epoch,
+            escrowed: self.escrowed,
+            wallet_backed: self.wallet_backed,
+            maker_fee_bps: self.maker_fee_bps,
+        }
+    }
+
+    /// fill event with the fees each side paid
+    /// nft_mint is the filled mint, which differs from the order mint for collection bids
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_fill_event(
+        &self,
+        address: Pubkey,
+        market_identifier: Pubkey,
+        taker: Pubkey,
+        nft_mint: Pubkey,
+        amount: u64,
+        price: u64,
+        value: u64,
+        fees: &FillFees,
+        royalties: u64,
+    ) -> OrderFillEvent {
+        OrderFillEvent {
+            address: address.to_string(),
+            market_identifier: market_identifier.to_string(),
+            maker: self.owner.to_string(),
+            taker: taker.to_string(),
+            side: self.side,
+            nft_mint: nft_mint.to_string(),
+            payment_mint: self.payment_mint.to_string(),
+            amount,
+            price,
+            value,
+            maker_fee: fees.maker_fee,
+            taker_fee: fees.taker_fee,
+            royalties,
         }
     }
 }
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+pub const ORDER_EPOCH_VERSION: u8 = 1;
+
+#[account()]
+/// order epoch account - one per owner, orders created under an older epoch can no longer be filled
+pub struct OrderEpoch {
+    /// order epoch account version
+    pub version: u8,
+    /// owner of the orders
+    pub owner: Pubkey,
+    /// epoch recorded in the new orders of the owner
+    pub epoch: u64,
+    /// reserved space for future changes
+    reserve: [u8; 64],
+}
+
+#[event]
+pub struct OrderEpochEvent {
+    pub address: String,
+    pub owner: String,
+    pub epoch: u64,
+}
+
+impl OrderEpoch {
+    /// initialize a new order epoch account
+    pub fn init(&mut self, owner: Pubkey) {
+        self.version = ORDER_EPOCH_VERSION;
+        self.owner = owner;
+    }
+
+    /// current epoch of an owner, 0 until their order epoch account exists
+    /// the account address must be checked by the caller
+    pub fn get_current(order_epoch: &AccountInfo) -> Result<u64> {
+        if order_epoch.data_is_empty() {
+            return Ok(0);
+        }
+        if *order_epoch.owner != crate::ID {
+            return Err(ErrorCode::AccountOwnedByWrongProgram.into());
+        }
+        let order_epoch = OrderEpoch::try_deserialize(&mut &order_epoch.data.borrow()[..])?;
+        Ok(order_epoch.epoch)
+    }
+
+    pub fn get_edit_event(&self, address: Pubkey) -> OrderEpochEvent {
+        OrderEpochEvent {
+            address: address.to_string(),
+            owner: self.owner.to_string(),
+            epoch: self.epoch,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use num_enum::IntoPrimitive;
+
+pub const WALLET_VERSION: u8 = 1;
+
+#[account()]
+/// wallet account - bidding balance of a user, drawn on by their wallet backed bids when filled
+/// native balance is held in the lamports of the account, token balances in its token accounts
+pub struct Wallet {
+    /// wallet account version
+    pub version: u8,
+    /// owner of the balance
+    pub owner: Pubkey,
+    /// reserved space for future changes
+    reserve: [u8; 64],
+}
+
+#[derive(IntoPrimitive)]
+#[repr(u8)]
+pub enum WalletEditType {
+    Deposit,
+    Withdraw,
+    Fill,
+}
+
+#[event]
+pub struct WalletEditEvent {
+    pub edit_type: u8,
+    pub address: String,
+    pub owner: String,
+    pub payment_mint: String,
+    pub amount: u64,
+}
+
+impl Wallet {
+    /// initialize a new wallet account
+    pub fn init(&mut self, owner: Pubkey) {
+        self.version = WALLET_VERSION;
+        self.owner = owner;
+    }
+
+    /// lamports of the wallet above its rent exempt minimum
+    pub fn get_native_balance(wallet: &AccountInfo) -> Result<u64> {
+        let rent_exempt_lamports = Rent::get()?.minimum_balance(wallet.data_len());
+        Ok(wallet.lamports().saturating_sub(rent_exempt_lamports))
+    }
+
+    pub fn get_edit_event(
+        &self,
+        address: Pubkey,
+        payment_mint: Pubkey,
+        amount: u64,
+        edit_type: WalletEditType,
+    ) -> WalletEditEvent {
+        WalletEditEvent {
+            edit_type: edit_type.into(),
+            address: address.to_string(),
+            owner: self.owner.to_string(),
+            payment_mint: payment_mint.to_string(),
+            amount,
+        }
+    }
+}
@@ -1,20 +1,29 @@
 use anchor_lang::{
-    prelude::{AccountInfo, Pubkey, Result},
+    prelude::{AccountInfo, AnchorDeserialize, Pubkey, Result},
     require,
     solana_program::{
+        ed25519_program,
+        hash::hashv,
         program::{invoke, invoke_signed},
         system_instruction::transfer,
+        sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
     },
     ToAccountInfo,
 };
-use anchor_spl::associated_token::{get_associated_token_address, spl_associated_token_account};
+use anchor_spl::{
+    associated_token::{get_associated_token_address, spl_associated_token_account},
+    token_2022::spl_token_2022::instruction::transfer_checked,
+};
 use mpl_token_metadata::accounts::Metadata;
 use mpl_token_metadata::types::{AuthorizationData, TokenStandard};
+use rarible_merkle_verify::verify;
 
 use crate::errors::MarketError;
+use crate::state::{get_verification_pda, MintVerification};
 
 use super::metaplex::pnft::utils::{ExistingDelegateParams, PnftParams};
 use spl_associated_token_account::instruction::create_associated_token_account;
+use spl_transfer_hook_interface::onchain::add_extra_accounts_for_execute_cpi;
 
 pub fn get_bump_in_seed_form(bump: &u8) -> [u8; 1] {
     let bump_val = *bump;
@@ -54,6 +63,104 @@ pub fn get_is_metaplex_nft(nft_account_info: &AccountInfo) -> bool {
     }
 }
 
+/// deserialize metaplex metadata, checking it is owned by token metadata and belongs to the mint
+pub fn get_verified_metadata(metadata_account: &AccountInfo, mint: &Pubkey) -> Result<Metadata> {
+    require!(
+        *metadata_account.owner == mpl_token_metadata::ID,
+        MarketError::WrongAccount
+    );
+    let metadata = Metadata::safe_deserialize(&metadata_account.data.borrow()[..])?;
+    require!(metadata.mint == *mint, MarketError::WrongAccount);
+
+    Ok(metadata)
+}
+
+/// check the mint has been manually verified for the market by the market initializer
+pub fn verify_mint_verification(
+    verification_account: &AccountInfo,
+    mint: Pubkey,
+    market: Pubkey,
+) -> Result<()> {
+    // unverified mints have no verification account
+    if *verification_account.key != get_verification_pda(mint, market).0
+        || *verification_account.owner != crate::ID
+        || verification_account.data_len() < 8
+    {
+        return Err(MarketError::InvalidNft.into());
+    }
+    let verification =
+        MintVerification::deserialize(&mut &verification_account.data.borrow()[8..])?;
+
+    if verification.verified != 1 {
+        return Err(MarketError::InvalidNft.into());
+    }
+
+    Ok(())
+}
+
+/// leaves are prefixed to tell them apart from intermediate nodes
+const MERKLE_LEAF_PREFIX: &[u8] = &[0];
+
+/// check the mint is one of the leaves of a trait bid merkle root
+pub fn verify_merkle_mint(
+    merkle_root: [u8; 32],
+    merkle_proof: Option<Vec<[u8; 32]>>,
+    mint: Pubkey,
+) -> Result<()> {
+    let Some(proof) = merkle_proof else {
+        return Err(MarketError::MerkleProofRequired.into());
+    };
+    let node = hashv(&[MERKLE_LEAF_PREFIX, &mint.to_bytes()]);
+    if !verify(proof, merkle_root, node.to_bytes()) {
+        return Err(MarketError::InvalidMerkleProof.into());
+    }
+
+    Ok(())
+}
+
+/// ed25519 instruction header of a single signature, followed by its offsets
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_OFFSETS_END: usize = ED25519_OFFSETS_START + 14;
+
+/// check the instruction before the current one is an ed25519 verification of the message by the signer
+/// signature, key and message must all be inlined in that instruction
+pub fn verify_ed25519_signature(
+    sysvar_instructions: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(sysvar_instructions)?;
+    let Some(previous_index) = current_index.checked_sub(1) else {
+        return Err(MarketError::InvalidSignature.into());
+    };
+    let ix = load_instruction_at_checked(previous_index.into(), sysvar_instructions)?;
+    if ix.program_id != ed25519_program::ID
+        || !ix.accounts.is_empty()
+        || ix.data.len() < ED25519_OFFSETS_END
+        || ix.data[0] != 1
+    {
+        return Err(MarketError::InvalidSignature.into());
+    }
+
+    let offsets: Vec<usize> = ix.data[ED25519_OFFSETS_START..ED25519_OFFSETS_END]
+        .chunks_exact(2)
+        .map(|offset| u16::from_le_bytes([offset[0], offset[1]]) as usize)
+        .collect();
+    let (public_key_offset, message_offset, message_size) = (offsets[2], offsets[4], offsets[5]);
+    // instruction indexes of u16::MAX point at the ed25519 instruction itself
+    let is_inline = [offsets[1], offsets[3], offsets[6]]
+        .iter()
+        .all(|index| *index == u16::MAX as usize);
+
+    let public_key = ix.data.get(public_key_offset..public_key_offset + 32);
+    let signed_message = ix.data.get(message_offset..message_offset + message_size);
+    if !is_inline || public_key != Some(signer.as_ref()) || signed_message != Some(message) {
+        return Err(MarketError::InvalidSignature.into());
+    }
+
+    Ok(())
+}
+
 fn get_pnft_params(ra: Vec<AccountInfo>) -> PnftParams {
     let fourth_account = ra.get(3).cloned().unwrap();
     let dest_token_record = if *fourth_account.key == Pubkey::default() {
@@ -104,6 +211,56 @@ pub fn transfer_sol<'info>(
     }
 }
 
+/// transfer_checked of an nft in or out of an order escrow
+/// hook accounts are forwarded to the token program, the hook program last
+#[allow(clippy::too_many_arguments)]
+#[inline(never)]
+pub fn transfer_nft_with_hook<'info>(
+    token_program: &AccountInfo<'info>,
+    source: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    amount: u64,
+    decimals: u8,
+    hook_accounts: &[AccountInfo<'info>],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut transfer_ix = transfer_checked(
+        token_program.key,
+        source.key,
+        mint.key,
+        destination.key,
+        authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+
+    let mut account_infos = vec![
+        source.to_account_info(),
+        mint.to_account_info(),
+        destination.to_account_info(),
+        authority.to_account_info(),
+    ];
+
+    if let Some(hook_program) = hook_accounts.last() {
+        add_extra_accounts_for_execute_cpi(
+            &mut transfer_ix,
+            &mut account_infos,
+            hook_program.key,
+            source.to_account_info(),
+            mint.to_account_info(),
+            destination.to_account_info(),
+            authority.to_account_info(),
+            amount,
+            hook_accounts,
+        )?;
+    }
+
+    invoke_signed(&transfer_ix, &account_infos, signer_seeds).map_err(Into::into)
+}
+
 /// result of parsing remaining accounts
 pub struct ParsedRemainingAccounts<'info> {
     //params for pnft ix's
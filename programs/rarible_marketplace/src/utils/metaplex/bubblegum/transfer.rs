@@ -13,3 +13,13 @@ pub fn compressed_transfer<'a>(
 
     Ok(())
 }
+
+/// proof path accounts are passed in as remaining accounts
+pub fn get_proof_accounts<'a, 'b>(
+    remaining_accounts: &'b [AccountInfo<'a>],
+) -> Vec<(&'b AccountInfo<'a>, bool, bool)> {
+    remaining_accounts
+        .iter()
+        .map(|account| (account, account.is_signer, account.is_writable))
+        .collect()
+}
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::BaseAssetV1,
+    fetch_plugin,
+    instructions::{
+        AddPluginV1CpiBuilder, ApprovePluginAuthorityV1CpiBuilder,
+        RevokePluginAuthorityV1CpiBuilder, TransferV1CpiBuilder,
+    },
+    types::{Plugin, PluginAuthority, PluginType, TransferDelegate, UpdateAuthority},
+};
+
+use crate::errors::MarketError;
+
+#[derive(Accounts)]
+pub struct DelegateCoreAsset<'info> {
+    /// CHECK: checked in CPI
+    pub asset: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    pub collection: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    pub delegate: AccountInfo<'info>,
+    /// CHECK: authority is owner
+    pub authority: AccountInfo<'info>,
+    /// CHECK: payer is owner
+    pub payer: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TransferCoreAsset<'info> {
+    /// CHECK: checked in CPI
+    pub asset: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    pub collection: AccountInfo<'info>,
+    /// CHECK: authority is owner or transfer delegate
+    pub authority: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    pub payer: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    pub new_owner: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    pub system_program: AccountInfo<'info>,
+}
+
+/// deserialize a core asset, checking it is owned by core and belongs to the collection
+pub fn get_verified_core_asset(asset: &AccountInfo, collection: Pubkey) -> Result<BaseAssetV1> {
+    require!(*asset.owner == mpl_core::ID, MarketError::WrongAccount);
+    let parsed_asset = BaseAssetV1::from_bytes(&asset.data.borrow()[..])?;
+    require!(
+        parsed_asset.update_authority == UpdateAuthority::Collection(collection),
+        MarketError::InvalidNft
+    );
+
+    Ok(parsed_asset)
+}
+
+/// make the delegate the transfer delegate of the asset, core resets it on transfer
+pub fn delegate_core_asset<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, DelegateCoreAsset<'info>>,
+) -> Result<()> {
+    let new_authority = PluginAuthority::Address {
+        address: ctx.accounts.delegate.key(),
+    };
+    let has_transfer_delegate = fetch_plugin::<BaseAssetV1, TransferDelegate>(
+        &ctx.accounts.asset,
+        PluginType::TransferDelegate,
+    )
+    .is_ok();
+
+    if has_transfer_delegate {
+        ApprovePluginAuthorityV1CpiBuilder::new(&ctx.program)
+            .asset(&ctx.accounts.asset)
+            .collection(Some(&ctx.accounts.collection))
+            .payer(&ctx.accounts.payer)
+            .authority(Some(&ctx.accounts.authority))
+            .system_program(&ctx.accounts.system_program)
+            .plugin_type(PluginType::TransferDelegate)
+            .new_authority(new_authority)
+            .invoke_signed(ctx.signer_seeds)?;
+    } else {
+        AddPluginV1CpiBuilder::new(&ctx.program)
+            .asset(&ctx.accounts.asset)
+            .collection(Some(&ctx.accounts.collection))
+            .payer(&ctx.accounts.payer)
+            .authority(Some(&ctx.accounts.authority))
+            .system_program(&ctx.accounts.system_program)
+            .plugin(Plugin::TransferDelegate(TransferDelegate {}))
+            .init_authority(new_authority)
+            .invoke_signed(ctx.signer_seeds)?;
+    }
+
+    Ok(())
+}
+
+/// hand the transfer delegate back to the owner, authority can be the owner or the delegate
+pub fn revoke_core_asset<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, DelegateCoreAsset<'info>>,
+) -> Result<()> {
+    RevokePluginAuthorityV1CpiBuilder::new(&ctx.program)
+        .asset(&ctx.accounts.asset)
+        .collection(Some(&ctx.accounts.collection))
+        .payer(&ctx.accounts.payer)
+        .authority(Some(&ctx.accounts.authority))
+        .system_program(&ctx.accounts.system_program)
+        .plugin_type(PluginType::TransferDelegate)
+        .invoke_signed(ctx.signer_seeds)?;
+
+    Ok(())
+}
+
+pub fn transfer_core_asset<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferCoreAsset<'info>>,
+) -> Result<()> {
+    TransferV1CpiBuilder::new(&ctx.program)
+        .asset(&ctx.accounts.asset)
+        .collection(Some(&ctx.accounts.collection))
+        .payer(&ctx.accounts.payer)
+        .authority(Some(&ctx.accounts.authority))
+        .new_owner(&ctx.accounts.new_owner)
+        .system_program(Some(&ctx.accounts.system_program))
+        .invoke_signed(ctx.signer_seeds)?;
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
 pub mod bubblegum;
+pub mod core_asset;
 pub mod mplx_transfer;
 pub mod pnft;
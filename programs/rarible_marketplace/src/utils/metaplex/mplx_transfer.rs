@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::*;
+use mpl_token_metadata::types::AuthorizationData;
 
-use crate::utils::metaplex::pnft::{transfer::metaplex_transfer, utils::AuthorizationDataLocal};
+use crate::utils::metaplex::pnft::{
+    transfer::metaplex_transfer,
+    utils::{AuthorizationDataLocal, PnftParams},
+};
 
 pub struct MetaplexAdditionalTransferAccounts<'info> {
     pub metadata: AccountInfo<'info>,
@@ -43,10 +47,22 @@ pub struct ExtraTransferParams<'info> {
     pub owner_token_record: Option<AccountInfo<'info>>,
     pub dest_token_record: Option<AccountInfo<'info>>,
     pub authorization_rules: Option<AccountInfo<'info>>,
-    pub authorization_data: Option<AuthorizationDataLocal>,
+    pub authorization_data: Option<AuthorizationData>,
     pub authorization_rules_program: Option<AccountInfo<'info>>,
 }
 
+impl<'info> From<PnftParams<'info>> for ExtraTransferParams<'info> {
+    fn from(params: PnftParams<'info>) -> Self {
+        ExtraTransferParams {
+            owner_token_record: params.owner_token_record,
+            dest_token_record: params.destination_token_record,
+            authorization_rules: params.authorization_rules,
+            authorization_data: params.authorization_data,
+            authorization_rules_program: params.authorization_rules_program,
+        }
+    }
+}
+
 pub fn transfer_metaplex_nft<'info>(
     ctx: CpiContext<'_, '_, '_, 'info, TransferMetaplexNft<'info>>,
     params: ExtraTransferParams<'info>,
@@ -84,7 +100,7 @@ pub fn get_extra_transfer_params(
             owner_token_record: None,
             dest_token_record: None,
             authorization_rules: None,
-            authorization_data,
+            authorization_data: authorization_data.map(AuthorizationData::from),
             authorization_rules_program: None,
         };
     }
@@ -98,7 +114,7 @@ pub fn get_extra_transfer_params(
         owner_token_record,
         dest_token_record,
         authorization_rules,
-        authorization_data,
+        authorization_data: authorization_data.map(AuthorizationData::from),
         authorization_rules_program,
     }
 }
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use mpl_token_metadata::{
+    instructions::{DelegateSaleV1CpiBuilder, RevokeCpiBuilder},
+    types::{RevokeArgs, TokenDelegateRole},
+};
+
+use crate::utils::metaplex::mplx_transfer::ExtraTransferParams;
+
+#[derive(Accounts)]
+pub struct DelegateMetaplexNft<'info> {
+    /// CHECK: checked in CPI
+    pub delegate: AccountInfo<'info>,
+    /// CHECK: authority is owner
+    pub authority: AccountInfo<'info>,
+    /// CHECK: payer is owner
+    pub payer: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    pub token: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    pub mint: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    pub metadata: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    pub edition: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    pub system_program: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    pub instructions: AccountInfo<'info>,
+    /// CHECK: checked in CPI
+    pub token_program: AccountInfo<'info>,
+}
+
+/// default pubkeys are passed in place of accounts the pnft doesn't use
+fn get_optional_account<'a, 'info>(
+    account: &'a Option<AccountInfo<'info>>,
+) -> Option<&'a AccountInfo<'info>> {
+    account
+        .as_ref()
+        .filter(|account| *account.key != Pubkey::default())
+}
+
+/// revoke args matching the role of an existing token delegate
+pub fn get_revoke_args(role: TokenDelegateRole) -> RevokeArgs {
+    match role {
+        TokenDelegateRole::Sale => RevokeArgs::SaleV1,
+        TokenDelegateRole::Transfer => RevokeArgs::TransferV1,
+        TokenDelegateRole::Utility => RevokeArgs::UtilityV1,
+        TokenDelegateRole::Staking => RevokeArgs::StakingV1,
+        TokenDelegateRole::Standard => RevokeArgs::StandardV1,
+        TokenDelegateRole::LockedTransfer => RevokeArgs::LockedTransferV1,
+        TokenDelegateRole::Migration => RevokeArgs::MigrationV1,
+    }
+}
+
+/// add a sale delegate to a pnft, locking it in the owner's wallet until revoked or transferred
+pub fn delegate_pnft<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, DelegateMetaplexNft<'info>>,
+    params: ExtraTransferParams<'info>,
+    amount: u64,
+) -> Result<()> {
+    let mut builder = DelegateSaleV1CpiBuilder::new(&ctx.program);
+    builder
+        .delegate(&ctx.accounts.delegate)
+        .metadata(&ctx.accounts.metadata)
+        .master_edition(Some(&ctx.accounts.edition))
+        .token_record(get_optional_account(&params.owner_token_record))
+        .mint(&ctx.accounts.mint)
+        .token(&ctx.accounts.token)
+        .authority(&ctx.accounts.authority)
+        .payer(&ctx.accounts.payer)
+        .system_program(&ctx.accounts.system_program)
+        .sysvar_instructions(&ctx.accounts.instructions)
+        .spl_token_program(Some(&ctx.accounts.token_program))
+        .authorization_rules_program(get_optional_account(
+            &params.authorization_rules_program,
+        ))
+        .authorization_rules(get_optional_account(&params.authorization_rules))
+        .amount(amount);
+
+    if let Some(authorization_data) = params.authorization_data {
+        builder.authorization_data(authorization_data);
+    }
+
+    builder.invoke_signed(ctx.signer_seeds)?;
+
+    Ok(())
+}
+
+/// revoke a token delegate from a pnft, authority must be the token owner
+pub fn revoke_pnft<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, DelegateMetaplexNft<'info>>,
+    params: ExtraTransferParams<'info>,
+    revoke_args: RevokeArgs,
+) -> Result<()> {
+    RevokeCpiBuilder::new(&ctx.program)
+        .delegate(&ctx.accounts.delegate)
+        .metadata(&ctx.accounts.metadata)
+        .master_edition(Some(&ctx.accounts.edition))
+        .token_record(get_optional_account(&params.owner_token_record))
+        .mint(&ctx.accounts.mint)
+        .token(Some(&ctx.accounts.token))
+        .authority(&ctx.accounts.authority)
+        .payer(&ctx.accounts.payer)
+        .system_program(&ctx.accounts.system_program)
+        .sysvar_instructions(&ctx.accounts.instructions)
+        .spl_token_program(Some(&ctx.accounts.token_program))
+        .authorization_rules_program(get_optional_account(
+            &params.authorization_rules_program,
+        ))
+        .authorization_rules(get_optional_account(&params.authorization_rules))
+        .revoke_args(revoke_args)
+        .invoke_signed(ctx.signer_seeds)?;
+
+    Ok(())
+}
@@ -1,2 +1,3 @@
+pub mod delegate;
 pub mod transfer;
 pub mod utils;
@@ -1,3 +1,4 @@
+use crate::errors::MarketError;
 use crate::utils::metaplex::mplx_transfer::{ExtraTransferParams, TransferMetaplexNft};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program::invoke_signed;
@@ -5,7 +6,7 @@ use anchor_lang::Key;
 use mpl_token_metadata::{
     accounts::Metadata,
     instructions::TransferBuilder,
-    types::{AuthorizationData, ProgrammableConfig, TokenStandard, TransferArgs},
+    types::{ProgrammableConfig, TokenStandard, TransferArgs},
 };
 
 use super::utils::get_is_nft;
@@ -95,7 +96,7 @@ pub fn metaplex_transfer<'info>(
 
                     //1. validate
                     if rule_set != *authorization_rules.key {
-                        // return Err(error!("bad rule set")); // fix this
+                        return Err(MarketError::WrongAccount.into());
                     }
 
                     //2. add to builder
@@ -113,7 +114,7 @@ pub fn metaplex_transfer<'info>(
     let transfer_ix = builder
         .transfer_args(TransferArgs::V1 {
             amount,
-            authorization_data: params.authorization_data.map(AuthorizationData::from),
+            authorization_data: params.authorization_data,
         })
         .instruction();
 
@@ -0,0 +1,63 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+use wen_new_standard::{utils::get_mint_metadata, ROYALTY_BASIS_POINTS_FIELD};
+
+use crate::{errors::MarketError, state::EDITIONS_PID};
+
+pub struct EditionsCreator {
+    pub address: Pubkey,
+    pub share: u8,
+}
+
+pub struct EditionsRoyalties {
+    pub royalty_basis_points: u64,
+    pub creators: Vec<EditionsCreator>,
+}
+
+/// true if the mint's token metadata carries a royalty basis points field
+pub fn get_has_royalty_field(mint: &mut AccountInfo) -> bool {
+    get_mint_metadata(mint)
+        .map(|metadata| {
+            metadata
+                .additional_metadata
+                .iter()
+                .any(|(key, _)| key == ROYALTY_BASIS_POINTS_FIELD)
+        })
+        .unwrap_or(false)
+}
+
+/// read royalties from an editions mint, the metadata update authority must be an editions deployment
+pub fn get_editions_royalties(
+    mint: &mut AccountInfo,
+    editions_deployment: &AccountInfo,
+) -> Result<EditionsRoyalties> {
+    let metadata = get_mint_metadata(mint)?;
+
+    if *editions_deployment.owner != Pubkey::from_str(EDITIONS_PID).unwrap()
+        || Option::<Pubkey>::from(metadata.update_authority) != Some(editions_deployment.key())
+    {
+        return Err(MarketError::InvalidNft.into());
+    }
+
+    let mut royalty_basis_points = 0;
+    let mut creators = Vec::new();
+    for (key, value) in metadata.additional_metadata.iter() {
+        if key == ROYALTY_BASIS_POINTS_FIELD {
+            royalty_basis_points =
+                u64::from_str(value).map_err(|_| MarketError::InvalidNft)?;
+        } else if let (Ok(address), Ok(share)) = (Pubkey::from_str(key), u8::from_str(value)) {
+            creators.push(EditionsCreator { address, share });
+        }
+    }
+
+    let total_share: u64 = creators.iter().map(|creator| creator.share as u64).sum();
+    if royalty_basis_points > 0 && total_share != 100 {
+        return Err(MarketError::InvalidNft.into());
+    }
+
+    Ok(EditionsRoyalties {
+        royalty_basis_points,
+        creators,
+    })
+}
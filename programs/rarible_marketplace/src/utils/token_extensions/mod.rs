@@ -1,3 +1,5 @@
+pub mod editions;
 pub mod wns;
 
+pub use editions::*;
 pub use wns::*;
@@ -0,0 +1,213 @@
+import * as anchor from '@coral-xyz/anchor';
+import { AccountMeta, Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID, createAccount, getAccount } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import { EDITIONS_PROGRAM_ID } from '../../constants';
+import {
+  airdrop,
+  cancelListing,
+  confirmOptions,
+  createPaymentMint,
+  expectError,
+  getAta,
+  getEventAuthority,
+  getOrderEpochPda,
+  getOrderPda,
+  getTokenBalance,
+  initMarket,
+  isClosed,
+  marketplace,
+  mintNft,
+} from '../../utils/marketplace';
+import { mintEdition } from '../../utils/editions';
+
+describe('Marketplace: bundles', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const { connection } = provider;
+  const program = marketplace();
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const feeRecipient = Keypair.generate().publicKey;
+  const price = new anchor.BN(2_000_000);
+
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+
+  const getBundlePda = (order: PublicKey) => PublicKey.findProgramAddressSync([Buffer.from('bundle'), order.toBuffer()], program.programId)[0];
+
+  // plain token 2022 mints need no membership account
+  const listBundle = (
+    nonce: PublicKey,
+    mints: PublicKey[],
+    nftProgram = SystemProgram.programId,
+    nftTas = mints.map((mint) => getAta(mint, seller.publicKey)),
+  ) => {
+    const order = getOrderPda(nonce, market, seller.publicKey);
+    return program.methods
+      .listBundle({ nonce, paymentMint, price })
+      .accountsStrict({
+        initializer: seller.publicKey,
+        market,
+        order,
+        orderEpoch: getOrderEpochPda(seller.publicKey),
+        bundle: getBundlePda(order),
+        systemProgram: SystemProgram.programId,
+        nftTokenProgram: TOKEN_2022_PROGRAM_ID,
+        nftProgram,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .remainingAccounts(
+        mints.flatMap((mint, i) => [
+          { pubkey: mint, isSigner: false, isWritable: false },
+          { pubkey: nftTas[i], isSigner: false, isWritable: true },
+        ]),
+      )
+      .signers([seller]);
+  };
+
+  const bundle = async (mints: PublicKey[], nftProgram = SystemProgram.programId) => {
+    const nonce = Keypair.generate().publicKey;
+    await listBundle(nonce, mints, nftProgram).rpc(confirmOptions);
+    return getOrderPda(nonce, market, seller.publicKey);
+  };
+
+  // each item takes its mint, seller and buyer nft ta, then its royalty accounts
+  const fillBundle = (order: PublicKey, mints: PublicKey[], nftProgram = SystemProgram.programId, royaltyAccounts: AccountMeta[][] = []) => {
+    const items = mints.map((mint, i) => [
+      { pubkey: mint, isSigner: false, isWritable: true },
+      { pubkey: getAta(mint, seller.publicKey), isSigner: false, isWritable: true },
+      { pubkey: getAta(mint, buyer.publicKey), isSigner: false, isWritable: true },
+      ...(royaltyAccounts[i] ?? []),
+    ]);
+    return program.methods
+      .fillBundle({ itemAccounts: Buffer.from(items.map((item) => item.length)) })
+      .accountsStrict({
+        taker: buyer.publicKey,
+        maker: seller.publicKey,
+        market,
+        order,
+        orderEpoch: getOrderEpochPda(seller.publicKey),
+        bundle: getBundlePda(order),
+        feeRecipient,
+        feeRecipientTa: getAta(paymentMint, feeRecipient, TOKEN_PROGRAM_ID),
+        sellerPaymentTa: getAta(paymentMint, seller.publicKey, TOKEN_PROGRAM_ID),
+        buyerPaymentTa: getAta(paymentMint, buyer.publicKey, TOKEN_PROGRAM_ID),
+        paymentMint,
+        paymentTokenProgram: TOKEN_PROGRAM_ID,
+        nftTokenProgram: TOKEN_2022_PROGRAM_ID,
+        nftProgram,
+        systemProgram: SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .remainingAccounts(items.flat())
+      .signers([buyer]);
+  };
+
+  const cancelBundle = (order: PublicKey, mints: PublicKey[]) =>
+    program.methods
+      .cancelBundle()
+      .accountsStrict({
+        initializer: seller.publicKey,
+        order,
+        bundle: getBundlePda(order),
+        market,
+        nftTokenProgram: TOKEN_2022_PROGRAM_ID,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .remainingAccounts(mints.map((mint) => ({ pubkey: getAta(mint, seller.publicKey), isSigner: false, isWritable: true })))
+      .signers([seller]);
+
+  const getNftAccount = (mint: PublicKey, owner: PublicKey) => getAccount(connection, getAta(mint, owner), 'confirmed', TOKEN_2022_PROGRAM_ID);
+  const getBalance = async (owner: PublicKey) => Number(await getTokenBalance(connection, getAta(paymentMint, owner, TOKEN_PROGRAM_ID)));
+
+  before(async () => {
+    await airdrop(connection, seller.publicKey);
+    await airdrop(connection, buyer.publicKey);
+    paymentMint = await createPaymentMint(provider, [buyer.publicKey], 10_000_000);
+    market = await initMarket(provider, { feeRecipient, feeBps: 100 });
+  });
+
+  it('should need at least two mints', async () => {
+    const mint = await mintNft(provider, seller.publicKey);
+    await expectError(listBundle(Keypair.generate().publicKey, [mint]).rpc(), 'WrongAccount');
+  });
+
+  it('should only list items from the associated token accounts of the initializer', async () => {
+    const mints = [await mintNft(provider, seller.publicKey), await mintNft(provider, seller.publicKey)];
+    const otherNftTa = await createAccount(connection, seller, mints[1], seller.publicKey, Keypair.generate(), confirmOptions, TOKEN_2022_PROGRAM_ID);
+    await expectError(
+      listBundle(Keypair.generate().publicKey, mints, SystemProgram.programId, [getAta(mints[0], seller.publicKey), otherNftTa]).rpc(),
+      'WrongAccount',
+    );
+  });
+
+  it('should revoke every item when cancelled', async () => {
+    const mints = [await mintNft(provider, seller.publicKey), await mintNft(provider, seller.publicKey)];
+    const order = await bundle(mints);
+    const bundleAccount = await program.account.bundle.fetch(getBundlePda(order), 'confirmed');
+    expect(bundleAccount.size).to.eql(2);
+
+    await cancelBundle(order, mints).rpc(confirmOptions);
+
+    expect(await isClosed(connection, order)).to.be.true;
+    expect(await isClosed(connection, getBundlePda(order))).to.be.true;
+    for (const mint of mints) {
+      expect((await getNftAccount(mint, seller.publicKey)).delegate).to.be.null;
+    }
+  });
+
+  it('should not be cancelled as a listing of one of its items', async () => {
+    const mints = [await mintNft(provider, seller.publicKey), await mintNft(provider, seller.publicKey)];
+    const order = await bundle(mints);
+    await expectError((await cancelListing(seller, order, undefined, mints[0])).rpc(), 'ConstraintRaw');
+  });
+
+  it('should transfer every item for the bundle price', async () => {
+    const mints = [await mintNft(provider, seller.publicKey), await mintNft(provider, seller.publicKey)];
+    const order = await bundle(mints);
+
+    await fillBundle(order, mints).rpc(confirmOptions);
+
+    for (const mint of mints) {
+      expect(Number((await getNftAccount(mint, buyer.publicKey)).amount)).to.eql(1);
+    }
+    expect(await getBalance(seller.publicKey)).to.eql(1_980_000);
+    expect(await isClosed(connection, order)).to.be.true;
+  });
+
+  it('should split royalties per item across creators', async () => {
+    const creators = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+    const sellerBalance = await getBalance(seller.publicKey);
+    // 10% royalties on the first edition and 5% on the second
+    const editions = [
+      await mintEdition(provider, seller, { royaltyBasisPoints: new anchor.BN(1000), creators: [{ address: creators[0], share: 100 }] }),
+      await mintEdition(provider, seller, { royaltyBasisPoints: new anchor.BN(500), creators: [{ address: creators[1], share: 100 }] }),
+    ];
+    const mints = editions.map(({ mint }) => mint);
+    const editionsProgram = new PublicKey(EDITIONS_PROGRAM_ID);
+    const order = await bundle(mints, editionsProgram);
+
+    await fillBundle(
+      order,
+      mints,
+      editionsProgram,
+      editions.map(({ editionsDeployment }, i) => [
+        { pubkey: editionsDeployment, isSigner: false, isWritable: false },
+        { pubkey: creators[i], isSigner: false, isWritable: true },
+        { pubkey: getAta(paymentMint, creators[i], TOKEN_PROGRAM_ID), isSigner: false, isWritable: true },
+      ]),
+    ).rpc(confirmOptions);
+
+    // each item is valued at half of the bundle price
+    expect(await getBalance(creators[0])).to.eql(100_000);
+    expect(await getBalance(creators[1])).to.eql(50_000);
+    expect((await getBalance(seller.publicKey)) - sellerBalance).to.eql(2_000_000 - 20_000 - 150_000);
+  });
+});
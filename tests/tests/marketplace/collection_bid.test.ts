@@ -0,0 +1,84 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  NftParams,
+  airdrop,
+  bid,
+  confirmOptions,
+  createPaymentMint,
+  expectError,
+  fillOrder,
+  getAta,
+  getTokenBalance,
+  getVerificationPda,
+  initMarket,
+  isClosed,
+  marketplace,
+  mintNft,
+  verifyMint,
+} from '../../utils/marketplace';
+
+describe('Marketplace: collection bids', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const { connection } = provider;
+  const program = marketplace();
+
+  const buyer = Keypair.generate();
+  const sellers = [Keypair.generate(), Keypair.generate()];
+  const price = new anchor.BN(1_000_000);
+  const one = new anchor.BN(1);
+
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+
+  // plain token 2022 mints prove their membership with the verification pda
+  const verifiedNft = (nftMint: PublicKey): NftParams => ({
+    nftTokenProgram: TOKEN_2022_PROGRAM_ID,
+    nftProgram: SystemProgram.programId,
+    remainingAccounts: [{ pubkey: getVerificationPda(nftMint, market), isSigner: false, isWritable: false }],
+  });
+
+  before(async () => {
+    await airdrop(connection, buyer.publicKey);
+    for (const seller of sellers) {
+      await airdrop(connection, seller.publicKey);
+    }
+    paymentMint = await createPaymentMint(provider, [buyer.publicKey], 10_000_000);
+    market = await initMarket(provider, { feeRecipient: Keypair.generate().publicKey, feeBps: 0 });
+  });
+
+  it('should leave the nft mint of the order unset', async () => {
+    const buyOrder = await bid(market, buyer, null, paymentMint, { price, size: one });
+    const order = await program.account.order.fetch(buyOrder, 'confirmed');
+    expect(order.nftMint.toBase58()).to.eql(PublicKey.default.toBase58());
+  });
+
+  it('should be filled by several sellers until it is used up', async () => {
+    const buyOrder = await bid(market, buyer, null, paymentMint, { price, size: new anchor.BN(2) });
+
+    for (const seller of sellers) {
+      const nftMint = await mintNft(provider, seller.publicKey);
+      await verifyMint(provider, market, nftMint);
+      await (await fillOrder(seller, buyOrder, one, verifiedNft(nftMint), nftMint)).rpc(confirmOptions);
+
+      expect(Number(await getTokenBalance(connection, getAta(nftMint, buyer.publicKey), TOKEN_2022_PROGRAM_ID))).to.eql(1);
+      expect(Number(await getTokenBalance(connection, getAta(paymentMint, seller.publicKey, TOKEN_PROGRAM_ID)))).to.eql(1_000_000);
+    }
+
+    expect(await isClosed(connection, buyOrder)).to.be.true;
+  });
+
+  it('should only be filled by a mint of the order when it is not a collection bid', async () => {
+    const seller = sellers[0];
+    const biddenMint = await mintNft(provider, buyer.publicKey);
+    const nftMint = await mintNft(provider, seller.publicKey);
+    await verifyMint(provider, market, nftMint);
+    const buyOrder = await bid(market, buyer, biddenMint, paymentMint, { price, size: one });
+
+    await expectError((await fillOrder(seller, buyOrder, one, verifiedNft(nftMint), nftMint)).rpc(), 'InvalidNft');
+  });
+});
@@ -0,0 +1,145 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import { describe, it } from 'mocha';
+import {
+  airdrop,
+  bid,
+  confirmOptions,
+  createPaymentMint,
+  expectError,
+  getAta,
+  getChainTime,
+  getEventAuthority,
+  getOrderEpochPda,
+  getOrderPda,
+  getVerificationPda,
+  initMarket,
+  marketplace,
+  verifyMint,
+} from '../../utils/marketplace';
+
+const BUBBLEGUM_PROGRAM_ID = new PublicKey('BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY');
+const COMPRESSION_PROGRAM_ID = new PublicKey('cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK');
+const NOOP_PROGRAM_ID = new PublicKey('noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV');
+
+const getAssetId = (merkleTree: PublicKey, index: number) => {
+  const nonce = Buffer.alloc(8);
+  nonce.writeBigUInt64LE(BigInt(index));
+  return PublicKey.findProgramAddressSync([Buffer.from('asset'), merkleTree.toBuffer(), nonce], BUBBLEGUM_PROGRAM_ID)[0];
+};
+
+const getTreeConfig = (merkleTree: PublicKey) => PublicKey.findProgramAddressSync([merkleTree.toBuffer()], BUBBLEGUM_PROGRAM_ID)[0];
+
+// the checks below all run before the bubblegum transfer, so the leaf itself is never read
+describe('Marketplace: compressed nfts', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = marketplace();
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const merkleTree = Keypair.generate().publicKey;
+  const price = new anchor.BN(10_000_000);
+  const leaf = {
+    root: Array(32).fill(0),
+    dataHash: Array(32).fill(0),
+    creatorHash: Array(32).fill(0),
+  };
+
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+
+  const treeAccounts = {
+    treeConfig: getTreeConfig(merkleTree),
+    merkleTree,
+    logWrapper: NOOP_PROGRAM_ID,
+    compressionProgram: COMPRESSION_PROGRAM_ID,
+    mplBubblegum: BUBBLEGUM_PROGRAM_ID,
+    systemProgram: SystemProgram.programId,
+    eventAuthority: getEventAuthority(),
+    program: program.programId,
+  };
+
+  const listCompressed = (expiry: anchor.BN | null) => {
+    const nonce = Keypair.generate().publicKey;
+    return program.methods
+      .listCompressed({ nonce, paymentMint, price, expiry, ...leaf, index: 0 })
+      .accountsStrict({
+        initializer: seller.publicKey,
+        market,
+        order: getOrderPda(nonce, market, seller.publicKey),
+        orderEpoch: getOrderEpochPda(seller.publicKey),
+        verification: getVerificationPda(getAssetId(merkleTree, 0), market),
+        leafDelegate: seller.publicKey,
+        ...treeAccounts,
+      })
+      .signers([seller]);
+  };
+
+  before(async () => {
+    await airdrop(provider.connection, seller.publicKey);
+    await airdrop(provider.connection, buyer.publicKey);
+    market = await initMarket(provider, { feeRecipient: Keypair.generate().publicKey, feeBps: 100 });
+    paymentMint = await createPaymentMint(provider, [buyer.publicKey], 1_000_000_000);
+    await verifyMint(provider, market, getAssetId(merkleTree, 0));
+  });
+
+  it('should reject listings expiring in the past', async () => {
+    const expiry = new anchor.BN((await getChainTime(provider.connection)) - 1);
+    await expectError(listCompressed(expiry).rpc(), 'OrderExpired');
+  });
+
+  it('should only fill a bid with the asset it was placed on', async () => {
+    const buyOrder = await bid(market, buyer, getAssetId(merkleTree, 0), paymentMint, { price, size: new anchor.BN(1) });
+    const feeRecipient = (await program.account.market.fetch(market, 'confirmed')).feeRecipient;
+    await expectError(
+      program.methods
+        .fillCompressedBid({ ...leaf, index: 1 })
+        .accountsStrict({
+          taker: seller.publicKey,
+          maker: buyer.publicKey,
+          market,
+          order: buyOrder,
+          orderEpoch: getOrderEpochPda(buyer.publicKey),
+          leafDelegate: seller.publicKey,
+          orderPaymentTa: getAta(paymentMint, buyOrder, TOKEN_PROGRAM_ID),
+          sellerPaymentTa: getAta(paymentMint, seller.publicKey, TOKEN_PROGRAM_ID),
+          makerPaymentTa: getAta(paymentMint, buyer.publicKey, TOKEN_PROGRAM_ID),
+          feeRecipient,
+          feeRecipientTa: getAta(paymentMint, feeRecipient, TOKEN_PROGRAM_ID),
+          paymentMint,
+          paymentTokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          ...treeAccounts,
+        })
+        .signers([seller])
+        .rpc(),
+      'InvalidNft',
+    );
+  });
+
+  it('should reject listings in a payment mint outside the allowlist', async () => {
+    await program.methods
+      .updateMarket({
+        feeRecipient: null,
+        feeBps: null,
+        makerFeeBps: null,
+        takerFeeBps: null,
+        state: null,
+        paymentAllowlistOn: true,
+        nativeAllowed: false,
+        paymentMints: [Keypair.generate().publicKey],
+        keeperTipBps: null,
+      })
+      .accountsStrict({
+        initializer: provider.wallet.publicKey,
+        market,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .rpc(confirmOptions);
+
+    await expectError(listCompressed(null).rpc(), 'PaymentMintNotAllowed');
+  });
+});
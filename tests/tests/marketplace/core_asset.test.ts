@@ -0,0 +1,176 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from '@solana/web3.js';
+import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  airdrop,
+  bid,
+  confirmOptions,
+  createPaymentMint,
+  expectError,
+  getAta,
+  getEmittedEvents,
+  getEventAuthority,
+  getOrderEpochPda,
+  getOrderPda,
+  getTokenBalance,
+  initMarket,
+  isClosed,
+  marketplace,
+} from '../../utils/marketplace';
+import { CORE_PROGRAM_ID, createCoreCollection, getCoreAssetOwner, mintCoreAsset } from '../../utils/core';
+
+describe('Marketplace: core assets', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = marketplace();
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const feeRecipient = Keypair.generate().publicKey;
+  const price = new anchor.BN(10_000_000);
+
+  let collection: PublicKey;
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+
+  const listCore = (asset: PublicKey, nonce = Keypair.generate().publicKey, listPaymentMint = paymentMint, listPrice = price) =>
+    program.methods
+      .listCore({ nonce, paymentMint: listPaymentMint, price: listPrice, expiry: null })
+      .accountsStrict({
+        initializer: seller.publicKey,
+        market,
+        order: getOrderPda(nonce, market, seller.publicKey),
+        orderEpoch: getOrderEpochPda(seller.publicKey),
+        asset,
+        collection,
+        coreProgram: CORE_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .signers([seller]);
+
+  const list = async (asset: PublicKey, listPaymentMint = paymentMint, listPrice = price) => {
+    const nonce = Keypair.generate().publicKey;
+    await listCore(asset, nonce, listPaymentMint, listPrice).rpc(confirmOptions);
+    return getOrderPda(nonce, market, seller.publicKey);
+  };
+
+  // native SOL orders take no payment accounts
+  const fillCore = async (taker: Keypair, orderAddress: PublicKey, asset: PublicKey) => {
+    const order = await program.account.order.fetch(orderAddress, 'confirmed');
+    const isNative = order.paymentMint.equals(PublicKey.default);
+    const isBuy = order.side === 0;
+    const paymentTa = (owner: PublicKey) => (isNative ? null : getAta(order.paymentMint, owner, TOKEN_PROGRAM_ID));
+    return program.methods
+      .fillCoreOrder(null)
+      .accountsStrict({
+        taker: taker.publicKey,
+        maker: order.owner,
+        market,
+        order: orderAddress,
+        orderEpoch: getOrderEpochPda(order.owner),
+        asset,
+        collection,
+        coreProgram: CORE_PROGRAM_ID,
+        sellerPaymentTa: paymentTa(isBuy ? taker.publicKey : order.owner),
+        buyerPaymentTa: paymentTa(isBuy ? orderAddress : taker.publicKey),
+        makerPaymentTa: paymentTa(order.owner),
+        feeRecipient,
+        feeRecipientTa: paymentTa(feeRecipient),
+        paymentMint: isNative ? null : order.paymentMint,
+        paymentTokenProgram: isNative ? null : TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .signers([taker])
+      .rpc(confirmOptions);
+  };
+
+  before(async () => {
+    await airdrop(provider.connection, seller.publicKey);
+    await airdrop(provider.connection, buyer.publicKey);
+    collection = await createCoreCollection(provider);
+    // core markets are keyed by the collection their assets belong to
+    market = await initMarket(provider, { feeRecipient, feeBps: 100, marketIdentifier: collection });
+    paymentMint = await createPaymentMint(provider, [buyer.publicKey], 1_000_000_000);
+  });
+
+  it('should reject assets of another collection', async () => {
+    const otherCollection = await createCoreCollection(provider);
+    const asset = await mintCoreAsset(provider, otherCollection, seller.publicKey);
+    await expectError(listCore(asset).rpc(), 'InvalidNft');
+  });
+
+  it('should keep a listed asset with its owner until cancelled', async () => {
+    const asset = await mintCoreAsset(provider, collection, seller.publicKey);
+    const listing = await list(asset);
+    expect((await getCoreAssetOwner(provider.connection, asset)).toBase58()).to.eql(seller.publicKey.toBase58());
+
+    await program.methods
+      .cancelCoreListing()
+      .accountsStrict({
+        initializer: seller.publicKey,
+        order: listing,
+        market,
+        asset,
+        collection,
+        coreProgram: CORE_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .signers([seller])
+      .rpc(confirmOptions);
+    expect(await isClosed(provider.connection, listing)).to.be.true;
+  });
+
+  it('should transfer a sold asset through its transfer delegate', async () => {
+    const asset = await mintCoreAsset(provider, collection, seller.publicKey);
+    const listing = await list(asset);
+
+    const signature = await fillCore(buyer, listing, asset);
+
+    expect((await getCoreAssetOwner(provider.connection, asset)).toBase58()).to.eql(buyer.publicKey.toBase58());
+    expect(await getTokenBalance(provider.connection, getAta(paymentMint, seller.publicKey, TOKEN_PROGRAM_ID))).to.eql(BigInt(9_900_000));
+    expect(await getTokenBalance(provider.connection, getAta(paymentMint, feeRecipient, TOKEN_PROGRAM_ID))).to.eql(BigInt(100_000));
+
+    const fillEvent = (await getEmittedEvents(provider.connection, signature)).find((event) => event.name.toLowerCase() === 'orderfillevent');
+    expect(fillEvent?.data.makerFee.toNumber()).to.eql(100_000);
+    expect(fillEvent?.data.takerFee.toNumber()).to.eql(0);
+  });
+
+  it('should pay the seller of a native SOL listing in lamports', async () => {
+    const asset = await mintCoreAsset(provider, collection, seller.publicKey);
+    const listing = await list(asset, PublicKey.default, new anchor.BN(LAMPORTS_PER_SOL));
+    const orderRent = await provider.connection.getBalance(listing, 'confirmed');
+    const sellerBalance = await provider.connection.getBalance(seller.publicKey, 'confirmed');
+
+    await fillCore(buyer, listing, asset);
+
+    expect((await getCoreAssetOwner(provider.connection, asset)).toBase58()).to.eql(buyer.publicKey.toBase58());
+    expect((await provider.connection.getBalance(seller.publicKey, 'confirmed')) - sellerBalance).to.eql(LAMPORTS_PER_SOL - LAMPORTS_PER_SOL / 100 + orderRent);
+  });
+
+  it('should fill a collection bid with any asset of the collection', async () => {
+    const asset = await mintCoreAsset(provider, collection, seller.publicKey);
+    const buyOrder = await bid(market, buyer, null, paymentMint, { price, size: new anchor.BN(1) });
+    const sellerBalance = await getTokenBalance(provider.connection, getAta(paymentMint, seller.publicKey, TOKEN_PROGRAM_ID));
+
+    await fillCore(seller, buyOrder, asset);
+
+    expect((await getCoreAssetOwner(provider.connection, asset)).toBase58()).to.eql(buyer.publicKey.toBase58());
+    expect(await isClosed(provider.connection, buyOrder)).to.be.true;
+    expect((await getTokenBalance(provider.connection, getAta(paymentMint, seller.publicKey, TOKEN_PROGRAM_ID))) - sellerBalance).to.eql(BigInt(9_900_000));
+  });
+
+  it('should not fill a bid on one asset with another', async () => {
+    const [bidAsset, asset] = [await mintCoreAsset(provider, collection, seller.publicKey), await mintCoreAsset(provider, collection, seller.publicKey)];
+    const buyOrder = await bid(market, buyer, bidAsset, paymentMint, { price, size: new anchor.BN(1) });
+    await expectError(fillCore(seller, buyOrder, asset), 'InvalidNft');
+  });
+});
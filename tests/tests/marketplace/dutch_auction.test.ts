@@ -0,0 +1,111 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  airdrop,
+  confirmOptions,
+  createPaymentMint,
+  editOrder,
+  expectError,
+  fillOrder,
+  getAta,
+  getChainTime,
+  getEmittedEvents,
+  getTokenBalance,
+  initMarket,
+  list,
+  listNft,
+  mintNft,
+  waitUntil,
+} from '../../utils/marketplace';
+
+describe('Marketplace: dutch auctions', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const { connection } = provider;
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const startPrice = new anchor.BN(2_000_000);
+  const endPrice = new anchor.BN(1_000_000);
+  const size = new anchor.BN(1);
+
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+
+  const getBalance = async (owner: PublicKey) => Number(await getTokenBalance(connection, getAta(paymentMint, owner, TOKEN_PROGRAM_ID)));
+
+  const listAuction = async (nftMint: PublicKey, duration: number) => {
+    const endTime = new anchor.BN((await getChainTime(connection))! + duration);
+    const listing = await list(market, seller, nftMint, { paymentMint, price: startPrice, size, endPrice, endTime });
+    return { listing, endTime };
+  };
+
+  before(async () => {
+    await airdrop(connection, seller.publicKey);
+    await airdrop(connection, buyer.publicKey);
+    paymentMint = await createPaymentMint(provider, [buyer.publicKey], 10_000_000);
+    market = await initMarket(provider, { feeRecipient: Keypair.generate().publicKey, feeBps: 0 });
+  });
+
+  it('should fill below the start price while the price declines', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey);
+    const { listing } = await listAuction(nftMint, 60 * 60);
+    await waitUntil(connection, (await getChainTime(connection))! + 2);
+    const buyerBalance = await getBalance(buyer.publicKey);
+
+    const signature = await (await fillOrder(buyer, listing, size)).rpc(confirmOptions);
+
+    const paid = buyerBalance - (await getBalance(buyer.publicKey));
+    expect(paid).to.be.lessThan(startPrice.toNumber());
+    expect(paid).to.be.greaterThan(endPrice.toNumber());
+    // the fill event reports the price actually paid
+    const fillEvent = (await getEmittedEvents(connection, signature)).find((event) => event.name.toLowerCase() === 'orderfillevent');
+    expect(fillEvent?.data.value.toNumber()).to.eql(paid);
+  });
+
+  it('should fill at the end price once the end time has passed', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey);
+    const { listing, endTime } = await listAuction(nftMint, 3);
+    await waitUntil(connection, endTime.toNumber());
+    const buyerBalance = await getBalance(buyer.publicKey);
+
+    await (await fillOrder(buyer, listing, size)).rpc(confirmOptions);
+
+    expect(buyerBalance - (await getBalance(buyer.publicKey))).to.eql(endPrice.toNumber());
+  });
+
+  it('should reject an end price that is not below the start price', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey);
+    const endTime = new anchor.BN((await getChainTime(connection))! + 60);
+    await expectError(
+      listNft(market, seller, nftMint, { nonce: Keypair.generate().publicKey, paymentMint, price: endPrice, size, endPrice: startPrice, endTime }).rpc(),
+      'InvalidAuctionParams',
+    );
+  });
+
+  it('should reject an end time in the past', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey);
+    const endTime = new anchor.BN((await getChainTime(connection))! - 60);
+    await expectError(
+      listNft(market, seller, nftMint, { nonce: Keypair.generate().publicKey, paymentMint, price: startPrice, size, endPrice, endTime }).rpc(),
+      'InvalidAuctionParams',
+    );
+  });
+
+  it('should reject an end price without an end time', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey);
+    await expectError(
+      listNft(market, seller, nftMint, { nonce: Keypair.generate().publicKey, paymentMint, price: startPrice, size, endPrice }).rpc(),
+      'InvalidAuctionParams',
+    );
+  });
+
+  it('should not edit the start price down to the end price', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey);
+    const { listing } = await listAuction(nftMint, 60 * 60);
+    await expectError((await editOrder(seller, listing, endPrice, size)).rpc(), 'InvalidOrderEdit');
+  });
+});
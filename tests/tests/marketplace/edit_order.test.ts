@@ -0,0 +1,89 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  airdrop,
+  bid,
+  confirmOptions,
+  createPaymentMint,
+  editOrder,
+  expectError,
+  getAta,
+  getTokenBalance,
+  initMarket,
+  list,
+  marketplace,
+  mintNft,
+} from '../../utils/marketplace';
+
+describe('Marketplace: editing orders', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = marketplace();
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const paymentAmount = 1_000_000_000;
+
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+
+  before(async () => {
+    await airdrop(provider.connection, seller.publicKey);
+    await airdrop(provider.connection, buyer.publicKey);
+    market = await initMarket(provider, { feeRecipient: Keypair.generate().publicKey, feeBps: 100 });
+    paymentMint = await createPaymentMint(provider, [buyer.publicKey], paymentAmount);
+  });
+
+  describe('a listing', () => {
+    let listing: PublicKey;
+
+    before(async () => {
+      const nftMint = await mintNft(provider, seller.publicKey);
+      listing = await list(market, seller, nftMint, { paymentMint, price: new anchor.BN(1_000_000), size: new anchor.BN(1) });
+    });
+
+    it('should be repriced', async () => {
+      await (await editOrder(seller, listing, new anchor.BN(2_000_000), new anchor.BN(1))).rpc(confirmOptions);
+      const order = await program.account.order.fetch(listing, 'confirmed');
+      expect(order.price.toNumber()).to.eql(2_000_000);
+    });
+
+    it('should not be resized', async () => {
+      await expectError((await editOrder(seller, listing, new anchor.BN(2_000_000), new anchor.BN(2))).rpc(), 'InvalidOrderEdit');
+    });
+
+    it('should only be edited by its owner', async () => {
+      await expectError((await editOrder(buyer, listing, new anchor.BN(1), new anchor.BN(1))).rpc(), 'ConstraintSeeds');
+    });
+  });
+
+  describe('a bid', () => {
+    let buyOrder: PublicKey;
+
+    const getEscrow = () => getTokenBalance(provider.connection, getAta(paymentMint, buyOrder, TOKEN_PROGRAM_ID));
+    const getBuyerBalance = () => getTokenBalance(provider.connection, getAta(paymentMint, buyer.publicKey, TOKEN_PROGRAM_ID));
+
+    before(async () => {
+      const nftMint = await mintNft(provider, seller.publicKey);
+      buyOrder = await bid(market, buyer, nftMint, paymentMint, { price: new anchor.BN(1_000_000), size: new anchor.BN(1) });
+    });
+
+    it('should top up the escrow when its value grows', async () => {
+      await (await editOrder(buyer, buyOrder, new anchor.BN(1_500_000), new anchor.BN(2))).rpc(confirmOptions);
+      const order = await program.account.order.fetch(buyOrder, 'confirmed');
+      expect(order.price.toNumber()).to.eql(1_500_000);
+      expect(order.size.toNumber()).to.eql(2);
+      expect(await getEscrow()).to.eql(BigInt(3_000_000));
+      expect(await getBuyerBalance()).to.eql(BigInt(paymentAmount - 3_000_000));
+    });
+
+    it('should refund the escrow when its value shrinks', async () => {
+      await (await editOrder(buyer, buyOrder, new anchor.BN(500_000), new anchor.BN(1))).rpc(confirmOptions);
+      expect(await getEscrow()).to.eql(BigInt(500_000));
+      expect(await getBuyerBalance()).to.eql(BigInt(paymentAmount - 500_000));
+    });
+  });
+});
@@ -0,0 +1,93 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID, getAccount } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import { EDITIONS_PROGRAM_ID } from '../../constants';
+import {
+  NftParams,
+  airdrop,
+  confirmOptions,
+  createPaymentMint,
+  expectError,
+  fillOrder,
+  getAta,
+  getTokenBalance,
+  initMarket,
+  list,
+  plainNft,
+} from '../../utils/marketplace';
+import { mintEdition } from '../../utils/editions';
+
+describe('Marketplace: rarible editions', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const creator1 = Keypair.generate().publicKey;
+  const creator2 = Keypair.generate().publicKey;
+  const feeRecipient = Keypair.generate().publicKey;
+  const price = new anchor.BN(10_000_000);
+  const size = new anchor.BN(1);
+
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+  let nftMint: PublicKey;
+  let editionsDeployment: PublicKey;
+
+  const editionsNft: NftParams = {
+    nftTokenProgram: TOKEN_2022_PROGRAM_ID,
+    nftProgram: new PublicKey(EDITIONS_PROGRAM_ID),
+    remainingAccounts: [],
+  };
+  // the editions deployment, then each creator followed by its payment token account
+  const fillAccounts = (): NftParams => ({
+    ...editionsNft,
+    remainingAccounts: [
+      { pubkey: editionsDeployment, isSigner: false, isWritable: false },
+      ...[creator1, creator2].flatMap((creator) => [
+        { pubkey: creator, isSigner: false, isWritable: true },
+        { pubkey: getAta(paymentMint, creator, TOKEN_PROGRAM_ID), isSigner: false, isWritable: true },
+      ]),
+    ],
+  });
+
+  before(async () => {
+    await airdrop(provider.connection, seller.publicKey);
+    await airdrop(provider.connection, buyer.publicKey);
+    market = await initMarket(provider, { feeRecipient, feeBps: 100 });
+    paymentMint = await createPaymentMint(provider, [buyer.publicKey], 1_000_000_000);
+    ({ mint: nftMint, editionsDeployment } = await mintEdition(provider, seller, {
+      royaltyBasisPoints: new anchor.BN(1000),
+      creators: [
+        { address: creator1, share: 50 },
+        { address: creator2, share: 50 },
+      ],
+    }));
+  });
+
+  it('should not sell an edition without paying its royalties', async () => {
+    const listing = await list(market, seller, nftMint, { paymentMint, price, size }, editionsNft);
+    await expectError((await fillOrder(buyer, listing, size, plainNft)).rpc(), 'UnsupportedNft');
+  });
+
+  it('should not sell an edition with creator accounts missing', async () => {
+    const listing = await list(market, seller, nftMint, { paymentMint, price, size }, editionsNft);
+    const { remainingAccounts } = fillAccounts();
+    await expectError((await fillOrder(buyer, listing, size, { ...editionsNft, remainingAccounts: remainingAccounts.slice(0, 3) })).rpc(), 'WrongAccount');
+  });
+
+  it('should split royalties between the creators of the edition', async () => {
+    const listing = await list(market, seller, nftMint, { paymentMint, price, size }, editionsNft);
+    await (await fillOrder(buyer, listing, size, fillAccounts())).rpc(confirmOptions);
+
+    const buyerNftTa = await getAccount(provider.connection, getAta(nftMint, buyer.publicKey), 'confirmed', TOKEN_2022_PROGRAM_ID);
+    expect(Number(buyerNftTa.amount)).to.eql(1);
+    // 1% market fee and 10% royalties shared equally
+    expect(await getTokenBalance(provider.connection, getAta(paymentMint, creator1, TOKEN_PROGRAM_ID))).to.eql(BigInt(500_000));
+    expect(await getTokenBalance(provider.connection, getAta(paymentMint, creator2, TOKEN_PROGRAM_ID))).to.eql(BigInt(500_000));
+    expect(await getTokenBalance(provider.connection, getAta(paymentMint, feeRecipient, TOKEN_PROGRAM_ID))).to.eql(BigInt(100_000));
+    expect(await getTokenBalance(provider.connection, getAta(paymentMint, seller.publicKey, TOKEN_PROGRAM_ID))).to.eql(BigInt(8_900_000));
+  });
+});
@@ -0,0 +1,189 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY, Transaction } from '@solana/web3.js';
+import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, createRevokeInstruction } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  airdrop,
+  confirmOptions,
+  expectError,
+  getAta,
+  getChainTime,
+  getEventAuthority,
+  getOrderEpochPda,
+  getTokenBalance,
+  initMarket,
+  isClosed,
+  list,
+  marketplace,
+  mintNft,
+  waitUntil,
+} from '../../utils/marketplace';
+
+describe('Marketplace: english auctions', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const { connection } = provider;
+  const program = marketplace();
+
+  const seller = Keypair.generate();
+  const bidder = Keypair.generate();
+  const buyer = Keypair.generate();
+  const feeRecipient = Keypair.generate().publicKey;
+  const reserve = new anchor.BN(LAMPORTS_PER_SOL);
+  const minIncrement = new anchor.BN(LAMPORTS_PER_SOL / 10);
+  const winningBid = new anchor.BN((12 * LAMPORTS_PER_SOL) / 10);
+  const size = new anchor.BN(1);
+
+  let market: PublicKey;
+
+  const getBalance = (pubkey: PublicKey) => connection.getBalance(pubkey, 'confirmed');
+
+  const listAuction = async (nftMint: PublicKey, duration: number, timeExtension = 0) =>
+    list(market, seller, nftMint, {
+      paymentMint: PublicKey.default,
+      price: reserve,
+      size,
+      auction: {
+        endTime: new anchor.BN((await getChainTime(connection))! + duration),
+        minIncrement,
+        timeExtension: new anchor.BN(timeExtension),
+      },
+    });
+
+  // native SOL auctions take no payment accounts
+  const bidAuction = (auction: PublicKey, bidderKeypair: Keypair, previousBidder: PublicKey | null, amount: anchor.BN) =>
+    program.methods
+      .bidAuction(amount)
+      .accountsStrict({
+        bidder: bidderKeypair.publicKey,
+        market,
+        order: auction,
+        orderEpoch: getOrderEpochPda(seller.publicKey),
+        previousBidder,
+        bidderPaymentTa: null,
+        previousBidderPaymentTa: null,
+        orderPaymentTa: null,
+        paymentMint: null,
+        paymentTokenProgram: null,
+        systemProgram: SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .signers([bidderKeypair]);
+
+  const settleAuction = (auction: PublicKey, nftMint: PublicKey, winner: PublicKey) =>
+    program.methods.settleAuction().accountsStrict({
+      payer: provider.wallet.publicKey,
+      maker: seller.publicKey,
+      winner,
+      market,
+      order: auction,
+      nftMint,
+      sellerNftTa: getAta(nftMint, seller.publicKey),
+      winnerNftTa: getAta(nftMint, winner),
+      feeRecipient,
+      feeRecipientTa: null,
+      systemProgram: SystemProgram.programId,
+      nftTokenProgram: TOKEN_2022_PROGRAM_ID,
+      nftProgram: SystemProgram.programId,
+      sellerPaymentTa: null,
+      orderPaymentTa: null,
+      winnerPaymentTa: null,
+      paymentMint: null,
+      paymentTokenProgram: null,
+      sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      eventAuthority: getEventAuthority(),
+      program: program.programId,
+    });
+
+  before(async () => {
+    for (const keypair of [seller, bidder, buyer]) {
+      await airdrop(connection, keypair.publicKey);
+    }
+    await airdrop(connection, feeRecipient, LAMPORTS_PER_SOL);
+    market = await initMarket(provider, { feeRecipient, feeBps: 100 });
+  });
+
+  it('should reject a first bid below the reserve price', async () => {
+    const auction = await listAuction(await mintNft(provider, seller.publicKey), 60);
+    await expectError(bidAuction(auction, bidder, null, reserve.subn(1)).rpc(), 'BidTooLow');
+  });
+
+  it('should extend an auction bid on near its end', async () => {
+    const auction = await listAuction(await mintNft(provider, seller.publicKey), 10, 60);
+    await bidAuction(auction, bidder, null, reserve).rpc(confirmOptions);
+
+    const order = await program.account.order.fetch(auction, 'confirmed');
+    expect(order.endTime.toNumber()).to.be.greaterThanOrEqual((await getChainTime(connection))! + 50);
+  });
+
+  describe('with a highest bid', () => {
+    let nftMint: PublicKey;
+    let auction: PublicKey;
+
+    before(async () => {
+      nftMint = await mintNft(provider, seller.publicKey);
+      auction = await listAuction(nftMint, 15);
+      await bidAuction(auction, bidder, null, reserve).rpc(confirmOptions);
+    });
+
+    it('should reject a bid below the minimum increment', async () => {
+      await expectError(bidAuction(auction, buyer, bidder.publicKey, reserve.add(minIncrement).subn(1)).rpc(), 'BidTooLow');
+    });
+
+    it('should refund the previous bidder when outbid', async () => {
+      const bidderBalance = await getBalance(bidder.publicKey);
+
+      await bidAuction(auction, buyer, bidder.publicKey, winningBid).rpc(confirmOptions);
+
+      expect((await getBalance(bidder.publicKey)) - bidderBalance).to.eql(reserve.toNumber());
+      const order = await program.account.order.fetch(auction, 'confirmed');
+      expect(order.highestBidder.toBase58()).to.eql(buyer.publicKey.toBase58());
+      expect(order.highestBid.toString()).to.eql(winningBid.toString());
+    });
+
+    it('should not settle before the end time', async () => {
+      await expectError(settleAuction(auction, nftMint, buyer.publicKey).rpc(), 'AuctionNotEnded');
+    });
+
+    it('should deliver the nft to the winner and pay out the winning bid on settle', async () => {
+      const order = await program.account.order.fetch(auction, 'confirmed');
+      await waitUntil(connection, order.endTime.toNumber());
+      const sellerBalance = await getBalance(seller.publicKey);
+      const feeRecipientBalance = await getBalance(feeRecipient);
+
+      await settleAuction(auction, nftMint, buyer.publicKey).rpc(confirmOptions);
+
+      const fee = winningBid.muln(100).divn(10_000).toNumber();
+      expect(Number(await getTokenBalance(connection, getAta(nftMint, buyer.publicKey), TOKEN_2022_PROGRAM_ID))).to.eql(1);
+      expect((await getBalance(feeRecipient)) - feeRecipientBalance).to.eql(fee);
+      // the seller also gets the order rent back
+      expect((await getBalance(seller.publicKey)) - sellerBalance).to.be.greaterThan(winningBid.toNumber() - fee);
+      expect(await isClosed(connection, auction)).to.be.true;
+    });
+  });
+
+  it('should refund the winner when the seller no longer holds the nft', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey);
+    const auction = await listAuction(nftMint, 10);
+    await bidAuction(auction, bidder, null, reserve).rpc(confirmOptions);
+    await provider.sendAndConfirm(
+      new Transaction().add(createRevokeInstruction(getAta(nftMint, seller.publicKey), seller.publicKey, [], TOKEN_2022_PROGRAM_ID)),
+      [seller],
+      confirmOptions,
+    );
+
+    const order = await program.account.order.fetch(auction, 'confirmed');
+    await waitUntil(connection, order.endTime.toNumber());
+    const bidderBalance = await getBalance(bidder.publicKey);
+
+    await settleAuction(auction, nftMint, bidder.publicKey).rpc(confirmOptions);
+
+    expect((await getBalance(bidder.publicKey)) - bidderBalance).to.eql(reserve.toNumber());
+    expect(Number(await getTokenBalance(connection, getAta(nftMint, seller.publicKey), TOKEN_2022_PROGRAM_ID))).to.eql(1);
+    expect(await isClosed(connection, auction)).to.be.true;
+  });
+});
@@ -0,0 +1,128 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID, getAccount } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  airdrop,
+  cancelListing,
+  confirmOptions,
+  createPaymentMint,
+  expectError,
+  fillOrder,
+  getAta,
+  getChainTime,
+  getEventAuthority,
+  getTokenBalance,
+  initMarket,
+  isClosed,
+  list,
+  listNft,
+  marketplace,
+  mintNft,
+  waitUntil,
+} from '../../utils/marketplace';
+
+describe('Marketplace: escrow listings', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = marketplace();
+  const { connection } = provider;
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const price = new anchor.BN(1_000_000);
+  const size = new anchor.BN(1);
+
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+
+  const getNftBalance = (nftMint: PublicKey, owner: PublicKey) => getTokenBalance(connection, getAta(nftMint, owner), TOKEN_2022_PROGRAM_ID);
+
+  const listEscrow = (nftMint: PublicKey, expiry?: anchor.BN) => list(market, seller, nftMint, { paymentMint, price, size, expiry, escrow: true });
+
+  before(async () => {
+    await airdrop(connection, seller.publicKey);
+    await airdrop(connection, buyer.publicKey);
+    market = await initMarket(provider, { feeRecipient: Keypair.generate().publicKey, feeBps: 100 });
+    paymentMint = await createPaymentMint(provider, [buyer.publicKey], 1_000_000_000);
+  });
+
+  it('should move the nft into a token account of the order', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey);
+    const listing = await listEscrow(nftMint);
+
+    expect((await program.account.order.fetch(listing, 'confirmed')).escrowed).to.be.true;
+    expect(await getNftBalance(nftMint, seller.publicKey)).to.eql(BigInt(0));
+    expect(await getNftBalance(nftMint, listing)).to.eql(BigInt(1));
+    const sellerNftTa = await getAccount(connection, getAta(nftMint, seller.publicKey), 'confirmed', TOKEN_2022_PROGRAM_ID);
+    expect(sellerNftTa.delegate).to.be.null;
+  });
+
+  it('should sell the nft out of escrow and close the escrow', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey);
+    const listing = await listEscrow(nftMint);
+
+    await (await fillOrder(buyer, listing, size)).rpc(confirmOptions);
+
+    expect(await getNftBalance(nftMint, buyer.publicKey)).to.eql(BigInt(1));
+    expect(await isClosed(connection, getAta(nftMint, listing))).to.be.true;
+    expect(await isClosed(connection, listing)).to.be.true;
+    expect(await getTokenBalance(connection, getAta(paymentMint, seller.publicKey, TOKEN_PROGRAM_ID))).to.eql(BigInt(990_000));
+  });
+
+  it('should return the nft when cancelled', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey);
+    const listing = await listEscrow(nftMint);
+
+    await (await cancelListing(seller, listing)).rpc(confirmOptions);
+
+    expect(await getNftBalance(nftMint, seller.publicKey)).to.eql(BigInt(1));
+    expect(await isClosed(connection, getAta(nftMint, listing))).to.be.true;
+    expect(await isClosed(connection, listing)).to.be.true;
+  });
+
+  it('should return the nft when closed after expiry', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey);
+    const expiry = new anchor.BN((await getChainTime(connection))! + 5);
+    const listing = await listEscrow(nftMint, expiry);
+    await waitUntil(connection, expiry.toNumber());
+
+    await program.methods
+      .closeExpiredOrder()
+      .accountsStrict({
+        payer: provider.wallet.publicKey,
+        owner: seller.publicKey,
+        market,
+        order: listing,
+        ownerNftTa: getAta(nftMint, seller.publicKey),
+        orderNftTa: getAta(nftMint, listing),
+        nftMint,
+        nftTokenProgram: TOKEN_2022_PROGRAM_ID,
+        ownerPaymentTa: null,
+        orderPaymentTa: null,
+        paymentMint: null,
+        paymentTokenProgram: null,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .rpc(confirmOptions);
+
+    expect(await getNftBalance(nftMint, seller.publicKey)).to.eql(BigInt(1));
+    expect(await isClosed(connection, getAta(nftMint, listing))).to.be.true;
+    expect(await isClosed(connection, listing)).to.be.true;
+  });
+
+  it('should not escrow english auctions', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey);
+    const auction = {
+      endTime: new anchor.BN((await getChainTime(connection))! + 60),
+      minIncrement: new anchor.BN(1),
+      timeExtension: new anchor.BN(0),
+    };
+    await expectError(
+      listNft(market, seller, nftMint, { nonce: Keypair.generate().publicKey, paymentMint: PublicKey.default, price, size, auction, escrow: true }).rpc(),
+      'InvalidAuctionParams',
+    );
+  });
+});
@@ -0,0 +1,133 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID, createAccount, getAccount } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  airdrop,
+  bid,
+  confirmOptions,
+  createPaymentMint,
+  expectError,
+  getAta,
+  getChainTime,
+  getEventAuthority,
+  getTokenBalance,
+  initMarket,
+  isClosed,
+  list,
+  listNft,
+  marketplace,
+  mintNft,
+  waitUntil,
+} from '../../utils/marketplace';
+
+describe('Marketplace: order expiry', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = marketplace();
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const paymentAmount = 1_000_000_000;
+  const price = new anchor.BN(10_000_000);
+  const size = new anchor.BN(1);
+
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+
+  const closeExpiredListing = (listing: PublicKey, nftMint: PublicKey, ownerNftTa = getAta(nftMint, seller.publicKey)) =>
+    program.methods.closeExpiredOrder().accountsStrict({
+      payer: provider.wallet.publicKey,
+      owner: seller.publicKey,
+      market,
+      order: listing,
+      ownerNftTa,
+      orderNftTa: null,
+      nftMint: null,
+      nftTokenProgram: TOKEN_2022_PROGRAM_ID,
+      ownerPaymentTa: null,
+      orderPaymentTa: null,
+      paymentMint: null,
+      paymentTokenProgram: null,
+      eventAuthority: getEventAuthority(),
+      program: program.programId,
+    });
+
+  const closeExpiredBid = (buyOrder: PublicKey) =>
+    program.methods.closeExpiredOrder().accountsStrict({
+      payer: provider.wallet.publicKey,
+      owner: buyer.publicKey,
+      market,
+      order: buyOrder,
+      ownerNftTa: null,
+      orderNftTa: null,
+      nftMint: null,
+      nftTokenProgram: null,
+      ownerPaymentTa: getAta(paymentMint, buyer.publicKey, TOKEN_PROGRAM_ID),
+      orderPaymentTa: getAta(paymentMint, buyOrder, TOKEN_PROGRAM_ID),
+      paymentMint,
+      paymentTokenProgram: TOKEN_PROGRAM_ID,
+      eventAuthority: getEventAuthority(),
+      program: program.programId,
+    });
+
+  before(async () => {
+    await airdrop(provider.connection, seller.publicKey);
+    await airdrop(provider.connection, buyer.publicKey);
+    market = await initMarket(provider, { feeRecipient: Keypair.generate().publicKey, feeBps: 100 });
+    paymentMint = await createPaymentMint(provider, [buyer.publicKey], paymentAmount);
+  });
+
+  it('should reject orders expiring in the past', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey);
+    const expiry = new anchor.BN((await getChainTime(provider.connection)) - 1);
+    await expectError(
+      listNft(market, seller, nftMint, { nonce: Keypair.generate().publicKey, paymentMint, price, size, expiry }).rpc(),
+      'OrderExpired',
+    );
+  });
+
+  describe('closing expired orders', () => {
+    let nftMint: PublicKey;
+    let listing: PublicKey;
+    let buyOrder: PublicKey;
+
+    before(async () => {
+      nftMint = await mintNft(provider, seller.publicKey);
+      const expiry = new anchor.BN((await getChainTime(provider.connection)) + 5);
+      listing = await list(market, seller, nftMint, { paymentMint, price, size, expiry });
+      buyOrder = await bid(market, buyer, nftMint, paymentMint, { price, size, expiry });
+    });
+
+    it('should not close an order before its expiry', async () => {
+      await expectError(closeExpiredListing(listing, nftMint).rpc(), 'OrderNotExpired');
+    });
+
+    it('should only close listings into the associated token account of the owner', async () => {
+      const otherNftTa = await createAccount(provider.connection, seller, nftMint, seller.publicKey, Keypair.generate(), confirmOptions, TOKEN_2022_PROGRAM_ID);
+      await expectError(closeExpiredListing(listing, nftMint, otherNftTa).rpc(), 'ConstraintAssociated');
+    });
+
+    describe('after expiry', () => {
+      before(async () => {
+        const order = await program.account.order.fetch(listing, 'confirmed');
+        await waitUntil(provider.connection, order.expiry.toNumber());
+        await closeExpiredListing(listing, nftMint).rpc(confirmOptions);
+        await closeExpiredBid(buyOrder).rpc(confirmOptions);
+      });
+
+      it('should close the listing and revoke its delegate', async () => {
+        expect(await isClosed(provider.connection, listing)).to.be.true;
+        const sellerNftTa = await getAccount(provider.connection, getAta(nftMint, seller.publicKey), 'confirmed', TOKEN_2022_PROGRAM_ID);
+        expect(sellerNftTa.delegate).to.be.null;
+        expect(Number(sellerNftTa.amount)).to.eql(1);
+      });
+
+      it('should close the bid and refund its escrow', async () => {
+        expect(await isClosed(provider.connection, buyOrder)).to.be.true;
+        expect(await getTokenBalance(provider.connection, getAta(paymentMint, buyer.publicKey, TOKEN_PROGRAM_ID))).to.eql(BigInt(paymentAmount));
+      });
+    });
+  });
+});
@@ -0,0 +1,151 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  airdrop,
+  bid,
+  confirmOptions,
+  createPaymentMint,
+  expectError,
+  fillOrder,
+  getAta,
+  getEmittedEvents,
+  getEventAuthority,
+  getTokenBalance,
+  initMarket,
+  list,
+  marketplace,
+  mintNft,
+} from '../../utils/marketplace';
+
+describe('Marketplace: maker and taker fees', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const { connection } = provider;
+  const program = marketplace();
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const feeRecipient = Keypair.generate().publicKey;
+  const price = new anchor.BN(1_000_000);
+  const size = new anchor.BN(1);
+
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+
+  const setFees = (makerFeeBps: number, takerFeeBps: number) =>
+    program.methods
+      .updateMarket({
+        feeRecipient: null,
+        feeBps: null,
+        makerFeeBps: new anchor.BN(makerFeeBps),
+        takerFeeBps: new anchor.BN(takerFeeBps),
+        state: null,
+        paymentAllowlistOn: null,
+        nativeAllowed: null,
+        paymentMints: null,
+        keeperTipBps: null,
+      })
+      .accountsStrict({
+        initializer: provider.wallet.publicKey,
+        market,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      });
+
+  const getBalance = async (owner: PublicKey) => Number(await getTokenBalance(connection, getAta(paymentMint, owner, TOKEN_PROGRAM_ID)));
+
+  before(async () => {
+    await airdrop(connection, seller.publicKey);
+    await airdrop(connection, buyer.publicKey);
+    paymentMint = await createPaymentMint(provider, [seller.publicKey, buyer.publicKey, feeRecipient], 10_000_000);
+    market = await initMarket(provider, { feeRecipient, feeBps: 100 });
+  });
+
+  it('should charge the market fee to the seller without a maker/taker split', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey);
+    const listing = await list(market, seller, nftMint, { paymentMint, price, size });
+    const sellerBalance = await getBalance(seller.publicKey);
+    const buyerBalance = await getBalance(buyer.publicKey);
+
+    await (await fillOrder(buyer, listing, size)).rpc(confirmOptions);
+
+    expect(buyerBalance - (await getBalance(buyer.publicKey))).to.eql(1_000_000);
+    expect((await getBalance(seller.publicKey)) - sellerBalance).to.eql(990_000);
+  });
+
+  it('should reject a maker rebate larger than the taker fee', async () => {
+    await expectError(setFees(-200, 100).rpc(), 'InvalidFeeBps');
+  });
+
+  describe('with a maker rebate', () => {
+    before(async () => {
+      await setFees(-50, 100).rpc(confirmOptions);
+    });
+
+    it('should charge the taker of a listing on top of the price', async () => {
+      const nftMint = await mintNft(provider, seller.publicKey);
+      const listing = await list(market, seller, nftMint, { paymentMint, price, size });
+      const sellerBalance = await getBalance(seller.publicKey);
+      const buyerBalance = await getBalance(buyer.publicKey);
+      const feeRecipientBalance = await getBalance(feeRecipient);
+
+      const signature = await (await fillOrder(buyer, listing, size)).rpc(confirmOptions);
+
+      expect(buyerBalance - (await getBalance(buyer.publicKey))).to.eql(1_010_000);
+      expect((await getBalance(seller.publicKey)) - sellerBalance).to.eql(1_005_000);
+      expect((await getBalance(feeRecipient)) - feeRecipientBalance).to.eql(5_000);
+
+      const fillEvent = (await getEmittedEvents(connection, signature)).find((event) => event.name.toLowerCase() === 'orderfillevent');
+      expect(fillEvent?.data.makerFee.toNumber()).to.eql(-5_000);
+      expect(fillEvent?.data.takerFee.toNumber()).to.eql(10_000);
+    });
+
+    it('should pay the rebate of a bid maker out of the taker fee', async () => {
+      const nftMint = await mintNft(provider, seller.publicKey);
+      const buyOrder = await bid(market, buyer, nftMint, paymentMint, { price, size });
+      const sellerBalance = await getBalance(seller.publicKey);
+      const buyerBalance = await getBalance(buyer.publicKey);
+      const feeRecipientBalance = await getBalance(feeRecipient);
+
+      await (await fillOrder(seller, buyOrder, size)).rpc(confirmOptions);
+
+      expect((await getBalance(seller.publicKey)) - sellerBalance).to.eql(990_000);
+      expect((await getBalance(buyer.publicKey)) - buyerBalance).to.eql(5_000);
+      expect((await getBalance(feeRecipient)) - feeRecipientBalance).to.eql(5_000);
+    });
+  });
+
+  describe('with a positive maker fee', () => {
+    before(async () => {
+      await setFees(50, 100).rpc(confirmOptions);
+    });
+
+    it('should escrow the maker fee of a bid on top of its price', async () => {
+      const nftMint = await mintNft(provider, seller.publicKey);
+      const buyerBalance = await getBalance(buyer.publicKey);
+      const buyOrder = await bid(market, buyer, nftMint, paymentMint, { price, size });
+
+      expect(buyerBalance - (await getBalance(buyer.publicKey))).to.eql(1_005_000);
+      expect(Number(await getTokenBalance(connection, getAta(paymentMint, buyOrder, TOKEN_PROGRAM_ID)))).to.eql(1_005_000);
+    });
+
+    it('should charge the maker fee of a bid to its maker and only the taker fee to the seller', async () => {
+      const nftMint = await mintNft(provider, seller.publicKey);
+      const buyOrder = await bid(market, buyer, nftMint, paymentMint, { price, size });
+      const sellerBalance = await getBalance(seller.publicKey);
+      const feeRecipientBalance = await getBalance(feeRecipient);
+
+      const signature = await (await fillOrder(seller, buyOrder, size)).rpc(confirmOptions);
+
+      expect((await getBalance(seller.publicKey)) - sellerBalance).to.eql(990_000);
+      expect((await getBalance(feeRecipient)) - feeRecipientBalance).to.eql(15_000);
+
+      const fillEvent = (await getEmittedEvents(connection, signature)).find((event) => event.name.toLowerCase() === 'orderfillevent');
+      expect(fillEvent?.data.makerFee.toNumber()).to.eql(5_000);
+      expect(fillEvent?.data.takerFee.toNumber()).to.eql(10_000);
+    });
+  });
+});
@@ -0,0 +1,177 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID, getAccount } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  NftParams,
+  airdrop,
+  cancelListing,
+  confirmOptions,
+  createPaymentMint,
+  expectError,
+  fillOrder,
+  getAta,
+  getChainTime,
+  getEventAuthority,
+  getOrderEpochPda,
+  getTokenBalance,
+  getVerificationPda,
+  initMarket,
+  isClosed,
+  list,
+  marketplace,
+  verifyMint,
+  waitUntil,
+} from '../../utils/marketplace';
+import { TOKEN_METADATA_PROGRAM_ID, getMasterEditionPda, getMetadataPda, mintLegacyNft } from '../../utils/metaplex';
+
+describe('Marketplace: legacy metaplex nfts', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = marketplace();
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const feeRecipient = Keypair.generate().publicKey;
+  const paymentAmount = 1_000_000_000;
+  const price = new anchor.BN(10_000_000);
+  const size = new anchor.BN(1);
+
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+
+  const legacyNft = (nftMint: PublicKey): NftParams => ({
+    nftTokenProgram: TOKEN_PROGRAM_ID,
+    nftProgram: TOKEN_METADATA_PROGRAM_ID,
+    remainingAccounts: [
+      { pubkey: getMetadataPda(nftMint), isSigner: false, isWritable: true },
+      { pubkey: getMasterEditionPda(nftMint), isSigner: false, isWritable: false },
+      { pubkey: getVerificationPda(nftMint, market), isSigner: false, isWritable: false },
+    ],
+  });
+
+  const getNftAccount = (nftMint: PublicKey, owner: PublicKey) =>
+    getAccount(provider.connection, getAta(nftMint, owner, TOKEN_PROGRAM_ID), 'confirmed', TOKEN_PROGRAM_ID);
+
+  before(async () => {
+    await airdrop(provider.connection, seller.publicKey);
+    await airdrop(provider.connection, buyer.publicKey);
+    market = await initMarket(provider, { feeRecipient, feeBps: 100 });
+    paymentMint = await createPaymentMint(provider, [buyer.publicKey], paymentAmount);
+  });
+
+  it('should not list a mint the market has not verified', async () => {
+    const nftMint = await mintLegacyNft(provider, seller.publicKey);
+    await expectError(list(market, seller, nftMint, { paymentMint, price, size }, legacyNft(nftMint)), 'InvalidNft');
+  });
+
+  describe('a verified mint', () => {
+    let nftMint: PublicKey;
+
+    before(async () => {
+      nftMint = await mintLegacyNft(provider, seller.publicKey);
+      await verifyMint(provider, market, nftMint);
+    });
+
+    it('should be listed by delegating it to the order', async () => {
+      const listing = await list(market, seller, nftMint, { paymentMint, price, size }, legacyNft(nftMint));
+      const sellerNftTa = await getNftAccount(nftMint, seller.publicKey);
+      expect(sellerNftTa.delegate?.toBase58()).to.eql(listing.toBase58());
+      expect(Number(sellerNftTa.delegatedAmount)).to.eql(1);
+
+      await (await cancelListing(seller, listing, legacyNft(nftMint))).rpc(confirmOptions);
+      expect(await isClosed(provider.connection, listing)).to.be.true;
+      expect((await getNftAccount(nftMint, seller.publicKey)).delegate).to.be.null;
+    });
+
+    it('should be left to its owner to delist once expired', async () => {
+      const expiry = new anchor.BN((await getChainTime(provider.connection))! + 3);
+      const listing = await list(market, seller, nftMint, { paymentMint, price, size, expiry }, legacyNft(nftMint));
+      await waitUntil(provider.connection, expiry.toNumber());
+
+      await expectError(
+        program.methods
+          .closeExpiredOrder()
+          .accountsStrict({
+            payer: provider.wallet.publicKey,
+            owner: seller.publicKey,
+            market,
+            order: listing,
+            ownerNftTa: getAta(nftMint, seller.publicKey, TOKEN_PROGRAM_ID),
+            orderNftTa: null,
+            nftMint: null,
+            nftTokenProgram: TOKEN_PROGRAM_ID,
+            ownerPaymentTa: null,
+            orderPaymentTa: null,
+            paymentMint: null,
+            paymentTokenProgram: null,
+            eventAuthority: getEventAuthority(),
+            program: program.programId,
+          })
+          .remainingAccounts(legacyNft(nftMint).remainingAccounts)
+          .rpc(),
+        'OwnerMustRevoke',
+      );
+
+      await (await cancelListing(seller, listing, legacyNft(nftMint))).rpc(confirmOptions);
+    });
+
+    it('should be left to its owner to delist once stale', async () => {
+      const listing = await list(market, seller, nftMint, { paymentMint, price, size }, legacyNft(nftMint));
+      await program.methods
+        .cancelAllOrders()
+        .accountsStrict({
+          owner: seller.publicKey,
+          orderEpoch: getOrderEpochPda(seller.publicKey),
+          systemProgram: SystemProgram.programId,
+          eventAuthority: getEventAuthority(),
+          program: program.programId,
+        })
+        .signers([seller])
+        .rpc(confirmOptions);
+
+      await expectError(
+        program.methods
+          .closeStaleOrders()
+          .accountsStrict({
+            payer: provider.wallet.publicKey,
+            owner: seller.publicKey,
+            orderEpoch: getOrderEpochPda(seller.publicKey),
+            market,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            token2022Program: TOKEN_2022_PROGRAM_ID,
+            eventAuthority: getEventAuthority(),
+            program: program.programId,
+          })
+          .remainingAccounts(
+            [listing, getAta(nftMint, seller.publicKey, TOKEN_PROGRAM_ID), SystemProgram.programId, SystemProgram.programId].map((pubkey) => ({
+              pubkey,
+              isSigner: false,
+              isWritable: !pubkey.equals(SystemProgram.programId),
+            })),
+          )
+          .rpc(),
+        'OwnerMustRevoke',
+      );
+
+      await (await cancelListing(seller, listing, legacyNft(nftMint))).rpc(confirmOptions);
+    });
+
+    it('should not be sold without its metadata accounts', async () => {
+      const listing = await list(market, seller, nftMint, { paymentMint, price, size }, legacyNft(nftMint));
+      await expectError((await fillOrder(buyer, listing, size, { ...legacyNft(nftMint), remainingAccounts: [] })).rpc(), 'WrongAccount');
+      await (await cancelListing(seller, listing, legacyNft(nftMint))).rpc(confirmOptions);
+    });
+
+    it('should be sold through token metadata', async () => {
+      const listing = await list(market, seller, nftMint, { paymentMint, price, size }, legacyNft(nftMint));
+      await (await fillOrder(buyer, listing, size, legacyNft(nftMint))).rpc(confirmOptions);
+
+      expect(Number((await getNftAccount(nftMint, buyer.publicKey)).amount)).to.eql(1);
+      expect(Number((await getNftAccount(nftMint, seller.publicKey)).amount)).to.eql(0);
+      expect(await getTokenBalance(provider.connection, getAta(paymentMint, seller.publicKey, TOKEN_PROGRAM_ID))).to.eql(BigInt(9_900_000));
+      expect(await getTokenBalance(provider.connection, getAta(paymentMint, feeRecipient, TOKEN_PROGRAM_ID))).to.eql(BigInt(100_000));
+    });
+  });
+});
@@ -0,0 +1,120 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import { airdrop, cancelListing, confirmOptions, expectError, getEventAuthority, initMarket, isClosed, list, listNft, marketplace, mintNft } from '../../utils/marketplace';
+
+describe('Marketplace: market admin', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = marketplace();
+
+  const seller = Keypair.generate();
+  const stranger = Keypair.generate();
+  const price = new anchor.BN(1_000_000);
+  const size = new anchor.BN(1);
+
+  let market: PublicKey;
+
+  const updateMarket = (
+    params: Partial<Parameters<typeof program.methods.updateMarket>[0]>,
+    initializer = (provider.wallet as anchor.Wallet).payer,
+  ) =>
+    program.methods
+      .updateMarket({
+        feeRecipient: null,
+        feeBps: null,
+        makerFeeBps: null,
+        takerFeeBps: null,
+        state: null,
+        paymentAllowlistOn: null,
+        nativeAllowed: null,
+        paymentMints: null,
+        keeperTipBps: null,
+        ...params,
+      })
+      .accountsStrict({
+        initializer: initializer.publicKey,
+        market,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .signers([initializer]);
+
+  const closeMarket = () =>
+    program.methods.closeMarket().accountsStrict({
+      initializer: provider.wallet.publicKey,
+      market,
+      eventAuthority: getEventAuthority(),
+      program: program.programId,
+    });
+
+  before(async () => {
+    await airdrop(provider.connection, seller.publicKey);
+    await airdrop(provider.connection, stranger.publicKey);
+    market = await initMarket(provider, { feeRecipient: Keypair.generate().publicKey, feeBps: 100 });
+  });
+
+  // markets of earlier versions opened orders before they were counted and cannot be closed
+  it('should count the open orders of new markets', async () => {
+    const marketAccount = await program.account.market.fetch(market, 'confirmed');
+    expect(marketAccount.version).to.eql(2);
+    expect(marketAccount.openOrders.toNumber()).to.eql(0);
+  });
+
+  it('should update the fee settings', async () => {
+    const feeRecipient = Keypair.generate().publicKey;
+    await updateMarket({ feeRecipient, feeBps: new anchor.BN(250) }).rpc(confirmOptions);
+
+    const marketAccount = await program.account.market.fetch(market, 'confirmed');
+    expect(marketAccount.feeRecipient.toBase58()).to.eql(feeRecipient.toBase58());
+    expect(marketAccount.feeBps.toNumber()).to.eql(250);
+  });
+
+  it('should reject fees above 100%', async () => {
+    await expectError(updateMarket({ feeBps: new anchor.BN(10_001) }).rpc(), 'InvalidFeeBps');
+  });
+
+  it('should only accept updates from the initializer', async () => {
+    await expectError(updateMarket({ feeBps: new anchor.BN(0) }, stranger).rpc(), 'ConstraintRaw');
+  });
+
+  it('should not close an open market', async () => {
+    await expectError(closeMarket().rpc(), 'ConstraintRaw');
+  });
+
+  describe('after closing the market', () => {
+    let nftMint: PublicKey;
+    let listing: PublicKey;
+
+    before(async () => {
+      nftMint = await mintNft(provider, seller.publicKey);
+      listing = await list(market, seller, nftMint, { paymentMint: PublicKey.default, price, size });
+      await updateMarket({ state: { closed: {} } }).rpc(confirmOptions);
+    });
+
+    it('should reject new listings', async () => {
+      const otherMint = await mintNft(provider, seller.publicKey);
+      await expectError(
+        listNft(market, seller, otherMint, { nonce: Keypair.generate().publicKey, paymentMint: PublicKey.default, price, size }).rpc(),
+        'ConstraintRaw',
+      );
+    });
+
+    it('should not close the market account while it has open orders', async () => {
+      expect((await program.account.market.fetch(market, 'confirmed')).openOrders.toNumber()).to.eql(1);
+      await expectError(closeMarket().rpc(), 'MarketHasOpenOrders');
+    });
+
+    it('should still let sellers cancel their listings', async () => {
+      await (await cancelListing(seller, listing)).rpc(confirmOptions);
+      expect(await isClosed(provider.connection, listing)).to.be.true;
+      expect((await program.account.market.fetch(market, 'confirmed')).openOrders.toNumber()).to.eql(0);
+    });
+
+    it('should close the market account', async () => {
+      await closeMarket().rpc(confirmOptions);
+      expect(await isClosed(provider.connection, market)).to.be.true;
+    });
+  });
+});
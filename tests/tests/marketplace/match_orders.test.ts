@@ -0,0 +1,166 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  NftParams,
+  airdrop,
+  bid,
+  confirmOptions,
+  createPaymentMint,
+  expectError,
+  getAta,
+  getEventAuthority,
+  getOrderEpochPda,
+  getTokenBalance,
+  getVerificationPda,
+  initMarket,
+  isClosed,
+  list,
+  marketplace,
+  mintNft,
+  plainNft,
+  verifyMint,
+} from '../../utils/marketplace';
+import { TOKEN_METADATA_PROGRAM_ID, getMasterEditionPda, getMetadataPda, mintLegacyNft } from '../../utils/metaplex';
+
+describe('Marketplace: matching orders', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const { connection } = provider;
+  const program = marketplace();
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const keeper = Keypair.generate();
+  const feeRecipient = Keypair.generate().publicKey;
+  const askPrice = new anchor.BN(1_000_000);
+  const bidPrice = new anchor.BN(1_200_000);
+  const size = new anchor.BN(1);
+
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+
+  const matchOrders = (buyOrder: PublicKey, sellOrder: PublicKey, nftMint: PublicKey, nft = plainNft) =>
+    program.methods
+      .matchOrders(null)
+      .accountsStrict({
+        keeper: keeper.publicKey,
+        market,
+        buyOrder,
+        sellOrder,
+        buyerOrderEpoch: getOrderEpochPda(buyer.publicKey),
+        sellerOrderEpoch: getOrderEpochPda(seller.publicKey),
+        buyer: buyer.publicKey,
+        seller: seller.publicKey,
+        nftMint,
+        sellerNftTa: getAta(nftMint, seller.publicKey, nft.nftTokenProgram),
+        buyerNftTa: getAta(nftMint, buyer.publicKey, nft.nftTokenProgram),
+        feeRecipient,
+        feeRecipientTa: getAta(paymentMint, feeRecipient, TOKEN_PROGRAM_ID),
+        orderPaymentTa: getAta(paymentMint, buyOrder, TOKEN_PROGRAM_ID),
+        sellerPaymentTa: getAta(paymentMint, seller.publicKey, TOKEN_PROGRAM_ID),
+        buyerPaymentTa: getAta(paymentMint, buyer.publicKey, TOKEN_PROGRAM_ID),
+        keeperPaymentTa: getAta(paymentMint, keeper.publicKey, TOKEN_PROGRAM_ID),
+        paymentMint,
+        paymentTokenProgram: TOKEN_PROGRAM_ID,
+        nftTokenProgram: nft.nftTokenProgram,
+        nftProgram: nft.nftProgram,
+        systemProgram: SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .remainingAccounts(nft.remainingAccounts)
+      .signers([keeper]);
+
+  const getBalance = async (owner: PublicKey) => Number(await getTokenBalance(connection, getAta(paymentMint, owner, TOKEN_PROGRAM_ID)));
+
+  before(async () => {
+    for (const keypair of [seller, buyer, keeper]) {
+      await airdrop(connection, keypair.publicKey);
+    }
+    paymentMint = await createPaymentMint(provider, [buyer.publicKey], 10_000_000);
+    market = await initMarket(provider, { feeRecipient, feeBps: 100 });
+    // half of the bid surplus goes to the keeper
+    await program.methods
+      .updateMarket({
+        feeRecipient: null,
+        feeBps: null,
+        makerFeeBps: null,
+        takerFeeBps: null,
+        state: null,
+        paymentAllowlistOn: null,
+        nativeAllowed: null,
+        paymentMints: null,
+        keeperTipBps: new anchor.BN(5000),
+      })
+      .accountsStrict({
+        initializer: provider.wallet.publicKey,
+        market,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .rpc(confirmOptions);
+  });
+
+  it('should settle a crossed bid at the ask and split the surplus', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey);
+    const listing = await list(market, seller, nftMint, { paymentMint, price: askPrice, size });
+    const buyOrder = await bid(market, buyer, nftMint, paymentMint, { price: bidPrice, size });
+    const buyerBalance = await getBalance(buyer.publicKey);
+
+    await matchOrders(buyOrder, listing, nftMint).rpc(confirmOptions);
+
+    expect(Number(await getTokenBalance(connection, getAta(nftMint, buyer.publicKey), TOKEN_2022_PROGRAM_ID))).to.eql(1);
+    expect(await isClosed(connection, listing)).to.be.true;
+    expect(await isClosed(connection, buyOrder)).to.be.true;
+    expect(await getBalance(seller.publicKey)).to.eql(990_000);
+    expect(await getBalance(keeper.publicKey)).to.eql(100_000);
+    expect((await getBalance(buyer.publicKey)) - buyerBalance).to.eql(100_000);
+  });
+
+  it('should pay the creators of a metaplex nft their royalties', async () => {
+    const creators = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+    const nftMint = await mintLegacyNft(provider, seller.publicKey, {
+      sellerFeeBasisPoints: 1_000,
+      creators: creators.map((address) => ({ address, share: 50 })),
+    });
+    await verifyMint(provider, market, nftMint);
+    // metadata, edition and verification, then a creator and creator payment ta per creator
+    const legacyNft: NftParams = {
+      nftTokenProgram: TOKEN_PROGRAM_ID,
+      nftProgram: TOKEN_METADATA_PROGRAM_ID,
+      remainingAccounts: [
+        { pubkey: getMetadataPda(nftMint), isSigner: false, isWritable: true },
+        { pubkey: getMasterEditionPda(nftMint), isSigner: false, isWritable: false },
+        { pubkey: getVerificationPda(nftMint, market), isSigner: false, isWritable: false },
+      ],
+    };
+    const creatorAccounts = creators.flatMap((creator) => [
+      { pubkey: creator, isSigner: false, isWritable: true },
+      { pubkey: getAta(paymentMint, creator, TOKEN_PROGRAM_ID), isSigner: false, isWritable: true },
+    ]);
+    const listing = await list(market, seller, nftMint, { paymentMint, price: askPrice, size }, legacyNft);
+    const buyOrder = await bid(market, buyer, nftMint, paymentMint, { price: askPrice, size });
+    const sellerBalance = await getBalance(seller.publicKey);
+
+    await matchOrders(buyOrder, listing, nftMint, { ...legacyNft, remainingAccounts: [...legacyNft.remainingAccounts, ...creatorAccounts] }).rpc(
+      confirmOptions,
+    );
+
+    expect(Number(await getTokenBalance(connection, getAta(nftMint, buyer.publicKey, TOKEN_PROGRAM_ID)))).to.eql(1);
+    expect(await getBalance(creators[0])).to.eql(50_000);
+    expect(await getBalance(creators[1])).to.eql(50_000);
+    expect((await getBalance(seller.publicKey)) - sellerBalance).to.eql(890_000);
+  });
+
+  it('should not match a bid below the ask', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey);
+    const listing = await list(market, seller, nftMint, { paymentMint, price: bidPrice, size });
+    const buyOrder = await bid(market, buyer, nftMint, paymentMint, { price: askPrice, size });
+
+    await expectError(matchOrders(buyOrder, listing, nftMint).rpc(), 'OrdersNotCrossed');
+  });
+});
@@ -0,0 +1,80 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, getAccount } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import { airdrop, bid, cancelBid, confirmOptions, expectError, fillOrder, getAta, initMarket, isClosed, list, mintNft } from '../../utils/marketplace';
+
+describe('Marketplace: native SOL payments', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const { connection } = provider;
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const feeRecipient = Keypair.generate().publicKey;
+  const price = new anchor.BN(LAMPORTS_PER_SOL);
+  const size = new anchor.BN(1);
+  const fee = LAMPORTS_PER_SOL / 100;
+
+  let market: PublicKey;
+
+  const getBalance = (pubkey: PublicKey) => connection.getBalance(pubkey, 'confirmed');
+  const getNftAmount = async (nftMint: PublicKey, owner: PublicKey) =>
+    Number((await getAccount(connection, getAta(nftMint, owner), 'confirmed', TOKEN_2022_PROGRAM_ID)).amount);
+
+  before(async () => {
+    await airdrop(connection, seller.publicKey);
+    await airdrop(connection, buyer.publicKey);
+    market = await initMarket(provider, { feeRecipient, feeBps: 100 });
+  });
+
+  it('should pay the seller of a listing in lamports', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey);
+    const listing = await list(market, seller, nftMint, { paymentMint: PublicKey.default, price, size });
+    const orderRent = await getBalance(listing);
+    const sellerBalance = await getBalance(seller.publicKey);
+
+    await (await fillOrder(buyer, listing, size)).rpc(confirmOptions);
+
+    expect(await getNftAmount(nftMint, buyer.publicKey)).to.eql(1);
+    expect(await getBalance(feeRecipient)).to.eql(fee);
+    // the closed order's rent also goes back to the seller
+    expect((await getBalance(seller.publicKey)) - sellerBalance).to.eql(LAMPORTS_PER_SOL - fee + orderRent);
+  });
+
+  describe('a bid', () => {
+    let nftMint: PublicKey;
+
+    before(async () => {
+      nftMint = await mintNft(provider, seller.publicKey);
+    });
+
+    it('should escrow its value in the order account', async () => {
+      const buyOrder = await bid(market, buyer, nftMint, PublicKey.default, { price, size });
+      const orderRent = await connection.getMinimumBalanceForRentExemption((await connection.getAccountInfo(buyOrder, 'confirmed'))!.data.length);
+      expect(await getBalance(buyOrder)).to.eql(orderRent + LAMPORTS_PER_SOL);
+
+      const buyerBalance = await getBalance(buyer.publicKey);
+      await (await cancelBid(buyer, buyOrder)).rpc(confirmOptions);
+      expect(await isClosed(connection, buyOrder)).to.be.true;
+      // escrow and rent come back, less the transaction fee
+      expect((await getBalance(buyer.publicKey)) - buyerBalance).to.be.greaterThan(orderRent + LAMPORTS_PER_SOL - 10_000);
+    });
+
+    it('should pay the seller filling it out of the escrow', async () => {
+      const buyOrder = await bid(market, buyer, nftMint, PublicKey.default, { price, size });
+      const feeRecipientBalance = await getBalance(feeRecipient);
+
+      await (await fillOrder(seller, buyOrder, size)).rpc(confirmOptions);
+
+      expect(await getNftAmount(nftMint, buyer.publicKey)).to.eql(1);
+      expect(await isClosed(connection, buyOrder)).to.be.true;
+      expect((await getBalance(feeRecipient)) - feeRecipientBalance).to.eql(fee);
+    });
+
+    it('should not overflow its value', async () => {
+      await expectError(bid(market, buyer, nftMint, PublicKey.default, { price: new anchor.BN(2).pow(new anchor.BN(63)), size: new anchor.BN(2) }), 'AmountOverflow');
+    });
+  });
+});
@@ -0,0 +1,146 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID, createAccount, getAccount } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  airdrop,
+  bid,
+  confirmOptions,
+  createPaymentMint,
+  expectError,
+  fillOrder,
+  getAta,
+  getEventAuthority,
+  getOrderEpochPda,
+  getTokenBalance,
+  initMarket,
+  isClosed,
+  list,
+  marketplace,
+  mintNft,
+} from '../../utils/marketplace';
+
+describe('Marketplace: order epochs', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = marketplace();
+  const { connection } = provider;
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const price = new anchor.BN(1_000_000);
+  const size = new anchor.BN(1);
+
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+
+  const cancelAllOrders = (owner: Keypair) =>
+    program.methods
+      .cancelAllOrders()
+      .accountsStrict({
+        owner: owner.publicKey,
+        orderEpoch: getOrderEpochPda(owner.publicKey),
+        systemProgram: SystemProgram.programId,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .signers([owner])
+      .rpc(confirmOptions);
+
+  // slots an order does not use are filled with the system program
+  const closeStaleOrders = (owner: PublicKey, remainingAccounts: PublicKey[]) =>
+    program.methods
+      .closeStaleOrders()
+      .accountsStrict({
+        payer: provider.wallet.publicKey,
+        owner,
+        orderEpoch: getOrderEpochPda(owner),
+        market,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .remainingAccounts(
+        remainingAccounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: !pubkey.equals(SystemProgram.programId) })),
+      );
+
+  const bidAccounts = (order: PublicKey, owner: PublicKey) => [
+    order,
+    getAta(paymentMint, order, TOKEN_PROGRAM_ID),
+    getAta(paymentMint, owner, TOKEN_PROGRAM_ID),
+    paymentMint,
+  ];
+
+  before(async () => {
+    await airdrop(connection, seller.publicKey);
+    await airdrop(connection, buyer.publicKey);
+    market = await initMarket(provider, { feeRecipient: Keypair.generate().publicKey, feeBps: 100 });
+    paymentMint = await createPaymentMint(provider, [buyer.publicKey], 1_000_000_000);
+  });
+
+  it('should record the current epoch of the owner in new orders', async () => {
+    const oldListing = await list(market, seller, await mintNft(provider, seller.publicKey), { paymentMint, price, size });
+    await cancelAllOrders(seller);
+    const newListing = await list(market, seller, await mintNft(provider, seller.publicKey), { paymentMint, price, size });
+
+    expect((await program.account.order.fetch(oldListing, 'confirmed')).epoch.toNumber()).to.eql(0);
+    expect((await program.account.order.fetch(newListing, 'confirmed')).epoch.toNumber()).to.eql(1);
+    expect((await program.account.orderEpoch.fetch(getOrderEpochPda(seller.publicKey), 'confirmed')).epoch.toNumber()).to.eql(1);
+  });
+
+  it('should not close orders of the current epoch', async () => {
+    await cancelAllOrders(buyer);
+    const buyOrder = await bid(market, buyer, null, paymentMint, { price, size });
+    await expectError(closeStaleOrders(buyer.publicKey, bidAccounts(buyOrder, buyer.publicKey)).rpc(), 'OrderNotStale');
+  });
+
+  describe('after cancelling all orders', () => {
+    let nftMint: PublicKey;
+    let listing: PublicKey;
+    let buyOrder: PublicKey;
+    let buyerPaymentBefore: bigint;
+
+    before(async () => {
+      nftMint = await mintNft(provider, seller.publicKey);
+      listing = await list(market, seller, nftMint, { paymentMint, price, size });
+      buyerPaymentBefore = await getTokenBalance(connection, getAta(paymentMint, buyer.publicKey, TOKEN_PROGRAM_ID));
+      buyOrder = await bid(market, buyer, null, paymentMint, { price, size });
+      await cancelAllOrders(seller);
+      await cancelAllOrders(buyer);
+    });
+
+    it('should not fill a stale listing', async () => {
+      await expectError((await fillOrder(buyer, listing, size)).rpc(), 'StaleOrder');
+    });
+
+    it('should only close listings delegated from the associated token account of the owner', async () => {
+      const otherNftTa = await createAccount(provider.connection, seller, nftMint, seller.publicKey, Keypair.generate(), confirmOptions, TOKEN_2022_PROGRAM_ID);
+      await expectError(
+        closeStaleOrders(seller.publicKey, [listing, otherNftTa, SystemProgram.programId, SystemProgram.programId]).rpc(),
+        'WrongAccount',
+      );
+    });
+
+    it('should close the listing and revoke its delegate', async () => {
+      await closeStaleOrders(seller.publicKey, [
+        listing,
+        getAta(nftMint, seller.publicKey),
+        SystemProgram.programId,
+        SystemProgram.programId,
+      ]).rpc(confirmOptions);
+
+      expect(await isClosed(connection, listing)).to.be.true;
+      const sellerNftTa = await getAccount(connection, getAta(nftMint, seller.publicKey), 'confirmed', TOKEN_2022_PROGRAM_ID);
+      expect(sellerNftTa.delegate).to.be.null;
+    });
+
+    it('should close the bid and refund its escrow', async () => {
+      await closeStaleOrders(buyer.publicKey, bidAccounts(buyOrder, buyer.publicKey)).rpc(confirmOptions);
+
+      expect(await isClosed(connection, buyOrder)).to.be.true;
+      expect(await getTokenBalance(connection, getAta(paymentMint, buyer.publicKey, TOKEN_PROGRAM_ID))).to.eql(buyerPaymentBefore);
+    });
+  });
+});
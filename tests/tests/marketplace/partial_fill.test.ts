@@ -0,0 +1,87 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID, getAccount } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  airdrop,
+  bid,
+  confirmOptions,
+  createPaymentMint,
+  expectError,
+  fillOrder,
+  getAta,
+  getTokenBalance,
+  initMarket,
+  isClosed,
+  list,
+  marketplace,
+  mintNft,
+} from '../../utils/marketplace';
+
+// order states
+const Partial = 1;
+
+describe('Marketplace: partial fills', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const { connection } = provider;
+  const program = marketplace();
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const price = new anchor.BN(1_000_000);
+  const one = new anchor.BN(1);
+  const two = new anchor.BN(2);
+
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+
+  const getNftAmount = async (nftMint: PublicKey, owner: PublicKey) => Number(await getTokenBalance(connection, getAta(nftMint, owner), TOKEN_2022_PROGRAM_ID));
+
+  before(async () => {
+    await airdrop(connection, seller.publicKey);
+    await airdrop(connection, buyer.publicKey);
+    paymentMint = await createPaymentMint(provider, [buyer.publicKey], 10_000_000);
+    market = await initMarket(provider, { feeRecipient: Keypair.generate().publicKey, feeBps: 0 });
+  });
+
+  it('should keep a listing and its delegate until its whole size is filled', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey, 3);
+    const listing = await list(market, seller, nftMint, { paymentMint, price, size: new anchor.BN(3) });
+
+    await (await fillOrder(buyer, listing, one)).rpc(confirmOptions);
+
+    const order = await program.account.order.fetch(listing, 'confirmed');
+    expect(order.size.toNumber()).to.eql(2);
+    expect(order.state).to.eql(Partial);
+    const sellerNftTa = await getAccount(connection, getAta(nftMint, seller.publicKey), 'confirmed', TOKEN_2022_PROGRAM_ID);
+    expect(sellerNftTa.delegate?.toBase58()).to.eql(listing.toBase58());
+    expect(Number(sellerNftTa.delegatedAmount)).to.eql(2);
+    expect(await getNftAmount(nftMint, buyer.publicKey)).to.eql(1);
+
+    await (await fillOrder(buyer, listing, two)).rpc(confirmOptions);
+
+    expect(await getNftAmount(nftMint, buyer.publicKey)).to.eql(3);
+    expect(await isClosed(connection, listing)).to.be.true;
+  });
+
+  it('should keep the escrow of a bid for its unfilled size', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey, 2);
+    const buyOrder = await bid(market, buyer, nftMint, paymentMint, { price, size: two });
+
+    await (await fillOrder(seller, buyOrder, one)).rpc(confirmOptions);
+
+    const order = await program.account.order.fetch(buyOrder, 'confirmed');
+    expect(order.size.toNumber()).to.eql(1);
+    expect(order.state).to.eql(Partial);
+    expect(Number(await getTokenBalance(connection, getAta(paymentMint, buyOrder, TOKEN_PROGRAM_ID)))).to.eql(1_000_000);
+    expect(await getNftAmount(nftMint, buyer.publicKey)).to.eql(1);
+  });
+
+  it('should reject fills of zero units', async () => {
+    const nftMint = await mintNft(provider, seller.publicKey, 2);
+    const listing = await list(market, seller, nftMint, { paymentMint, price, size: two });
+    await expectError((await fillOrder(buyer, listing, new anchor.BN(0))).rpc(), 'InsufficientOrderSize');
+  });
+});
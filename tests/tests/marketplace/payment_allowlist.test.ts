@@ -0,0 +1,109 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  airdrop,
+  bidNft,
+  confirmOptions,
+  createPaymentMint,
+  expectError,
+  fillOrder,
+  getEventAuthority,
+  initMarket,
+  list,
+  listNft,
+  marketplace,
+  mintNft,
+} from '../../utils/marketplace';
+
+describe('Marketplace: payment mint allowlist', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const { connection } = provider;
+  const program = marketplace();
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const price = new anchor.BN(1_000_000);
+  const size = new anchor.BN(1);
+
+  let market: PublicKey;
+  let allowedMint: PublicKey;
+  let otherMint: PublicKey;
+
+  const updateMarket = (params: Partial<Parameters<typeof program.methods.updateMarket>[0]>) =>
+    program.methods
+      .updateMarket({
+        feeRecipient: null,
+        feeBps: null,
+        makerFeeBps: null,
+        takerFeeBps: null,
+        state: null,
+        paymentAllowlistOn: null,
+        nativeAllowed: null,
+        paymentMints: null,
+        keeperTipBps: null,
+        ...params,
+      })
+      .accountsStrict({
+        initializer: provider.wallet.publicKey,
+        market,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      });
+
+  before(async () => {
+    await airdrop(connection, seller.publicKey);
+    await airdrop(connection, buyer.publicKey);
+    allowedMint = await createPaymentMint(provider, [buyer.publicKey], 10_000_000);
+    otherMint = await createPaymentMint(provider, [buyer.publicKey], 10_000_000);
+    market = await initMarket(provider, { feeRecipient: Keypair.generate().publicKey, feeBps: 0 });
+  });
+
+  it('should reject more payment mints than the allowlist holds', async () => {
+    const paymentMints = Array.from({ length: 9 }, () => Keypair.generate().publicKey);
+    await expectError(updateMarket({ paymentMints }).rpc(), 'InvalidPaymentMints');
+  });
+
+  describe('when turned on', () => {
+    let listingInOtherMint: PublicKey;
+
+    before(async () => {
+      // listed before the allowlist limited the market
+      listingInOtherMint = await list(market, seller, await mintNft(provider, seller.publicKey), { paymentMint: otherMint, price, size });
+      await updateMarket({ paymentAllowlistOn: true, nativeAllowed: false, paymentMints: [allowedMint] }).rpc(confirmOptions);
+    });
+
+    it('should store the allowlist', async () => {
+      const marketAccount = await program.account.market.fetch(market, 'confirmed');
+      expect(marketAccount.paymentAllowlistOn).to.be.true;
+      expect(marketAccount.paymentMints[0].toBase58()).to.eql(allowedMint.toBase58());
+    });
+
+    it('should list in an allowlisted mint', async () => {
+      const nftMint = await mintNft(provider, seller.publicKey);
+      await list(market, seller, nftMint, { paymentMint: allowedMint, price, size });
+    });
+
+    it('should reject listings in other mints', async () => {
+      const nftMint = await mintNft(provider, seller.publicKey);
+      await expectError(
+        listNft(market, seller, nftMint, { nonce: Keypair.generate().publicKey, paymentMint: otherMint, price, size }).rpc(),
+        'PaymentMintNotAllowed',
+      );
+    });
+
+    it('should reject native SOL bids unless native SOL is allowed', async () => {
+      const nftMint = await mintNft(provider, seller.publicKey);
+      await expectError(
+        bidNft(market, buyer, nftMint, PublicKey.default, { nonce: Keypair.generate().publicKey, price, size }).rpc(),
+        'PaymentMintNotAllowed',
+      );
+    });
+
+    it('should not fill orders in mints removed from the allowlist', async () => {
+      await expectError((await fillOrder(buyer, listingInOtherMint, size)).rpc(), 'PaymentMintNotAllowed');
+    });
+  });
+});
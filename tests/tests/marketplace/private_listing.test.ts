@@ -0,0 +1,66 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  airdrop,
+  confirmOptions,
+  createPaymentMint,
+  expectError,
+  fillOrder,
+  getAta,
+  getEmittedEvents,
+  getOrderPda,
+  getTokenBalance,
+  initMarket,
+  listNft,
+  mintNft,
+} from '../../utils/marketplace';
+
+describe('Marketplace: private listings', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const { connection } = provider;
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const stranger = Keypair.generate();
+  const price = new anchor.BN(1_000_000);
+  const size = new anchor.BN(1);
+
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+  let nftMint: PublicKey;
+  let listing: PublicKey;
+  let listSignature: string;
+
+  before(async () => {
+    for (const keypair of [seller, buyer, stranger]) {
+      await airdrop(connection, keypair.publicKey);
+    }
+    paymentMint = await createPaymentMint(provider, [buyer.publicKey, stranger.publicKey], 10_000_000);
+    market = await initMarket(provider, { feeRecipient: Keypair.generate().publicKey, feeBps: 0 });
+
+    nftMint = await mintNft(provider, seller.publicKey);
+    const nonce = Keypair.generate().publicKey;
+    listSignature = await listNft(market, seller, nftMint, { nonce, paymentMint, price, size, taker: buyer.publicKey }).rpc(confirmOptions);
+    listing = getOrderPda(nonce, market, seller.publicKey);
+  });
+
+  it('should flag the listing as private in its events', async () => {
+    const editEvent = (await getEmittedEvents(connection, listSignature)).find((event) => event.name.toLowerCase() === 'ordereditevent');
+    expect(editEvent?.data.isPrivate).to.be.true;
+    expect(editEvent?.data.taker).to.eql(buyer.publicKey.toBase58());
+  });
+
+  it('should not be filled by another taker', async () => {
+    await expectError((await fillOrder(stranger, listing, size)).rpc(), 'InvalidTaker');
+  });
+
+  it('should be filled by its taker', async () => {
+    await (await fillOrder(buyer, listing, size)).rpc(confirmOptions);
+
+    expect(Number(await getTokenBalance(connection, getAta(nftMint, buyer.publicKey), TOKEN_2022_PROGRAM_ID))).to.eql(1);
+  });
+});
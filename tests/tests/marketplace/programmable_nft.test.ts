@@ -0,0 +1,131 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { TOKEN_PROGRAM_ID, getAccount } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  NftParams,
+  airdrop,
+  cancelListing,
+  confirmOptions,
+  createPaymentMint,
+  fillOrder,
+  getAta,
+  getTokenBalance,
+  getVerificationPda,
+  initMarket,
+  isClosed,
+  list,
+  verifyMint,
+} from '../../utils/marketplace';
+import {
+  TOKEN_METADATA_PROGRAM_ID,
+  TokenState,
+  getMasterEditionPda,
+  getMetadataPda,
+  getTokenRecordPda,
+  getTokenState,
+  mintProgrammableNft,
+} from '../../utils/metaplex';
+
+describe('Marketplace: programmable nfts', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const paymentAmount = 1_000_000_000;
+  const price = new anchor.BN(10_000_000);
+  const size = new anchor.BN(1);
+
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+  let nftMint: PublicKey;
+
+  const unused = { pubkey: PublicKey.default, isSigner: false, isWritable: false };
+  const tokenRecord = (owner: PublicKey) => ({
+    pubkey: getTokenRecordPda(nftMint, getAta(nftMint, owner, TOKEN_PROGRAM_ID)),
+    isSigner: false,
+    isWritable: true,
+  });
+  const metadataAccounts = () => [
+    { pubkey: getMetadataPda(nftMint), isSigner: false, isWritable: true },
+    { pubkey: getMasterEditionPda(nftMint), isSigner: false, isWritable: false },
+  ];
+  const programmableNft = (remainingAccounts: NftParams['remainingAccounts']): NftParams => ({
+    nftTokenProgram: TOKEN_PROGRAM_ID,
+    nftProgram: TOKEN_METADATA_PROGRAM_ID,
+    remainingAccounts,
+  });
+  const verification = () => ({ pubkey: getVerificationPda(nftMint, market), isSigner: false, isWritable: false });
+
+  // metadata, edition, verification, owner token record, rule set, rule set program, destination token record,
+  // delegate record, existing delegate and existing delegate record
+  const listAccounts = () =>
+    programmableNft([...metadataAccounts(), verification(), tokenRecord(seller.publicKey), unused, unused, unused, unused, unused, unused]);
+  // metadata, edition, owner token record, rule set, rule set program and destination token record
+  const cancelAccounts = () => programmableNft([...metadataAccounts(), tokenRecord(seller.publicKey), unused, unused, unused]);
+  // metadata, edition, verification, owner token record, rule set, rule set program and destination token record
+  const fillAccounts = () =>
+    programmableNft([...metadataAccounts(), verification(), tokenRecord(seller.publicKey), unused, unused, tokenRecord(buyer.publicKey)]);
+
+  const getSellerTokenState = () => getTokenState(provider, nftMint, getAta(nftMint, seller.publicKey, TOKEN_PROGRAM_ID));
+
+  before(async () => {
+    await airdrop(provider.connection, seller.publicKey);
+    await airdrop(provider.connection, buyer.publicKey);
+    market = await initMarket(provider, { feeRecipient: Keypair.generate().publicKey, feeBps: 100 });
+    paymentMint = await createPaymentMint(provider, [buyer.publicKey], paymentAmount);
+    nftMint = await mintProgrammableNft(provider, seller.publicKey);
+    await verifyMint(provider, market, nftMint);
+  });
+
+  it('should lock a listed pnft with the order as sale delegate', async () => {
+    const listing = await list(market, seller, nftMint, { paymentMint, price, size }, listAccounts());
+    expect(await getSellerTokenState()).to.eql(TokenState.Listed);
+    const sellerNftTa = await getAccount(provider.connection, getAta(nftMint, seller.publicKey, TOKEN_PROGRAM_ID), 'confirmed', TOKEN_PROGRAM_ID);
+    expect(sellerNftTa.delegate?.toBase58()).to.eql(listing.toBase58());
+
+    await (await cancelListing(seller, listing, cancelAccounts())).rpc(confirmOptions);
+    expect(await isClosed(provider.connection, listing)).to.be.true;
+    expect(await getSellerTokenState()).to.eql(TokenState.Unlocked);
+  });
+
+  it('should transfer a sold pnft through its token records', async () => {
+    const listing = await list(market, seller, nftMint, { paymentMint, price, size }, listAccounts());
+    await (await fillOrder(buyer, listing, size, fillAccounts())).rpc(confirmOptions);
+
+    const buyerNftTa = await getAccount(provider.connection, getAta(nftMint, buyer.publicKey, TOKEN_PROGRAM_ID), 'confirmed', TOKEN_PROGRAM_ID);
+    expect(Number(buyerNftTa.amount)).to.eql(1);
+    expect(buyerNftTa.isFrozen).to.be.true;
+    expect(await getTokenState(provider, nftMint, buyerNftTa.address)).to.eql(TokenState.Unlocked);
+    expect(await getTokenBalance(provider.connection, getAta(paymentMint, seller.publicKey, TOKEN_PROGRAM_ID))).to.eql(BigInt(9_900_000));
+  });
+
+  describe('with creator royalties', () => {
+    const creators = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+    const creatorAccounts = () =>
+      creators.flatMap((creator) => [
+        { pubkey: creator, isSigner: false, isWritable: true },
+        { pubkey: getAta(paymentMint, creator, TOKEN_PROGRAM_ID), isSigner: false, isWritable: true },
+      ]);
+    const getPaymentBalance = (owner: PublicKey) => getTokenBalance(provider.connection, getAta(paymentMint, owner, TOKEN_PROGRAM_ID));
+
+    before(async () => {
+      const royalties = { sellerFeeBasisPoints: 1_000, creators: creators.map((address) => ({ address, share: 50 })) };
+      nftMint = await mintProgrammableNft(provider, seller.publicKey, royalties);
+      await verifyMint(provider, market, nftMint);
+    });
+
+    it('should pay the creators their share of the royalties', async () => {
+      const sellerBalance = await getPaymentBalance(seller.publicKey);
+      const listing = await list(market, seller, nftMint, { paymentMint, price, size }, listAccounts());
+      const { remainingAccounts } = fillAccounts();
+      await (await fillOrder(buyer, listing, size, programmableNft([...remainingAccounts, ...creatorAccounts()]))).rpc(confirmOptions);
+
+      expect(await getPaymentBalance(creators[0])).to.eql(BigInt(500_000));
+      expect(await getPaymentBalance(creators[1])).to.eql(BigInt(500_000));
+      expect((await getPaymentBalance(seller.publicKey)) - sellerBalance).to.eql(BigInt(8_900_000));
+    });
+  });
+});
@@ -0,0 +1,240 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Ed25519Program, Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY, Transaction } from '@solana/web3.js';
+import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID, createApproveInstruction } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  NftParams,
+  airdrop,
+  confirmOptions,
+  createPaymentMint,
+  expectError,
+  getAta,
+  getEventAuthority,
+  getOrderEpochPda,
+  getTokenBalance,
+  getVerificationPda,
+  initMarket,
+  marketplace,
+  mintNft,
+  plainNft,
+  verifyMint,
+} from '../../utils/marketplace';
+import { TOKEN_METADATA_PROGRAM_ID, getMetadataPda, mintLegacyNft } from '../../utils/metaplex';
+
+type SignedListing = {
+  market: PublicKey;
+  nftMint: PublicKey;
+  paymentMint: PublicKey;
+  price: anchor.BN;
+  size: anchor.BN;
+  nonce: anchor.BN;
+  expiry: anchor.BN;
+  epoch: anchor.BN;
+};
+
+// borsh serialization of a signed listing
+const getSignedListingMessage = (listing: SignedListing) =>
+  Buffer.concat([
+    listing.market.toBuffer(),
+    listing.nftMint.toBuffer(),
+    listing.paymentMint.toBuffer(),
+    listing.price.toArrayLike(Buffer, 'le', 8),
+    listing.size.toArrayLike(Buffer, 'le', 8),
+    listing.nonce.toArrayLike(Buffer, 'le', 8),
+    listing.expiry.toTwos(64).toArrayLike(Buffer, 'le', 8),
+    listing.epoch.toArrayLike(Buffer, 'le', 8),
+  ]);
+
+describe('Marketplace: signed listings', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const { connection } = provider;
+  const program = marketplace();
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const feeRecipient = Keypair.generate().publicKey;
+  const price = new anchor.BN(LAMPORTS_PER_SOL);
+  const fee = LAMPORTS_PER_SOL / 100;
+
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+  let nonceBitmap: PublicKey;
+
+  // the nonce bitmap is the delegate of every listed token account
+  const signListing = async (
+    listingPaymentMint: PublicKey,
+    nonce: number,
+    { epoch = 0, nftMint, nft = plainNft }: { epoch?: number; nftMint?: PublicKey; nft?: NftParams } = {},
+  ): Promise<SignedListing> => {
+    nftMint = nftMint ?? (await mintNft(provider, seller.publicKey));
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        createApproveInstruction(getAta(nftMint, seller.publicKey, nft.nftTokenProgram), nonceBitmap, seller.publicKey, 1, [], nft.nftTokenProgram),
+      ),
+      [seller],
+      confirmOptions,
+    );
+    return {
+      market,
+      nftMint,
+      paymentMint: listingPaymentMint,
+      price,
+      size: new anchor.BN(1),
+      nonce: new anchor.BN(nonce),
+      expiry: new anchor.BN(0),
+      epoch: new anchor.BN(epoch),
+    };
+  };
+
+  // payment accounts follow the payment mint of the listing unless told otherwise
+  const fillSignedListing = (
+    listing: SignedListing,
+    signedListing = listing,
+    isNative = listing.paymentMint.equals(PublicKey.default),
+    nft = plainNft,
+  ) => {
+    return program.methods
+      .fillSignedListing(listing)
+      .accountsStrict({
+        taker: buyer.publicKey,
+        seller: seller.publicKey,
+        market,
+        nonceBitmap,
+        orderEpoch: getOrderEpochPda(seller.publicKey),
+        nftMint: listing.nftMint,
+        sellerNftTa: getAta(listing.nftMint, seller.publicKey, nft.nftTokenProgram),
+        buyerNftTa: getAta(listing.nftMint, buyer.publicKey, nft.nftTokenProgram),
+        feeRecipient,
+        feeRecipientTa: isNative ? null : getAta(paymentMint, feeRecipient, TOKEN_PROGRAM_ID),
+        sellerPaymentTa: isNative ? null : getAta(paymentMint, seller.publicKey, TOKEN_PROGRAM_ID),
+        buyerPaymentTa: isNative ? null : getAta(paymentMint, buyer.publicKey, TOKEN_PROGRAM_ID),
+        paymentMint: isNative ? null : paymentMint,
+        paymentTokenProgram: isNative ? null : TOKEN_PROGRAM_ID,
+        nftTokenProgram: nft.nftTokenProgram,
+        nftProgram: nft.nftProgram,
+        sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .remainingAccounts(nft.remainingAccounts)
+      .preInstructions([Ed25519Program.createInstructionWithPrivateKey({ privateKey: seller.secretKey, message: getSignedListingMessage(signedListing) })])
+      .signers([buyer]);
+  };
+
+  const ownsNft = async (nftMint: PublicKey) => Number(await getTokenBalance(connection, getAta(nftMint, buyer.publicKey), TOKEN_2022_PROGRAM_ID)) === 1;
+
+  before(async () => {
+    await airdrop(connection, seller.publicKey);
+    await airdrop(connection, buyer.publicKey);
+    await airdrop(connection, feeRecipient, LAMPORTS_PER_SOL);
+    paymentMint = await createPaymentMint(provider, [buyer.publicKey], 10 * LAMPORTS_PER_SOL);
+    market = await initMarket(provider, { feeRecipient, feeBps: 100 });
+    nonceBitmap = PublicKey.findProgramAddressSync([Buffer.from('nonce'), market.toBuffer(), seller.publicKey.toBuffer()], program.programId)[0];
+
+    await program.methods
+      .initNonceBitmap()
+      .accountsStrict({
+        owner: seller.publicKey,
+        market,
+        nonceBitmap,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([seller])
+      .rpc(confirmOptions);
+  });
+
+  it('should be filled in native SOL and burn its nonce', async () => {
+    const listing = await signListing(PublicKey.default, 0);
+    const sellerBalance = await connection.getBalance(seller.publicKey, 'confirmed');
+
+    await fillSignedListing(listing).rpc(confirmOptions);
+
+    expect(await ownsNft(listing.nftMint)).to.be.true;
+    expect((await connection.getBalance(seller.publicKey, 'confirmed')) - sellerBalance).to.eql(LAMPORTS_PER_SOL - fee);
+    await expectError(fillSignedListing(listing).rpc(), 'NonceUsed');
+  });
+
+  it('should be filled in an spl token', async () => {
+    const listing = await signListing(paymentMint, 1);
+
+    await fillSignedListing(listing).rpc(confirmOptions);
+
+    expect(await ownsNft(listing.nftMint)).to.be.true;
+    expect(Number(await getTokenBalance(connection, getAta(paymentMint, seller.publicKey, TOKEN_PROGRAM_ID)))).to.eql(LAMPORTS_PER_SOL - fee);
+    expect(Number(await getTokenBalance(connection, getAta(paymentMint, feeRecipient, TOKEN_PROGRAM_ID)))).to.eql(fee);
+  });
+
+  it('should pay the creators of a metaplex nft their royalties', async () => {
+    const creators = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+    const nftMint = await mintLegacyNft(provider, seller.publicKey, {
+      sellerFeeBasisPoints: 1_000,
+      creators: creators.map((address) => ({ address, share: 50 })),
+    });
+    await verifyMint(provider, market, nftMint);
+    // verification and metadata, then a creator and creator payment ta per creator
+    const nft: NftParams = {
+      nftTokenProgram: TOKEN_PROGRAM_ID,
+      nftProgram: TOKEN_METADATA_PROGRAM_ID,
+      remainingAccounts: [
+        { pubkey: getVerificationPda(nftMint, market), isSigner: false, isWritable: false },
+        { pubkey: getMetadataPda(nftMint), isSigner: false, isWritable: false },
+        ...creators.flatMap((creator) => [
+          { pubkey: creator, isSigner: false, isWritable: true },
+          { pubkey: getAta(paymentMint, creator, TOKEN_PROGRAM_ID), isSigner: false, isWritable: true },
+        ]),
+      ],
+    };
+    const listing = await signListing(paymentMint, 7, { nftMint, nft });
+    const getPaymentBalance = async (owner: PublicKey) => Number(await getTokenBalance(connection, getAta(paymentMint, owner, TOKEN_PROGRAM_ID)));
+    const sellerBalance = await getPaymentBalance(seller.publicKey);
+
+    await fillSignedListing(listing, listing, false, nft).rpc(confirmOptions);
+
+    expect(Number(await getTokenBalance(connection, getAta(nftMint, buyer.publicKey, TOKEN_PROGRAM_ID)))).to.eql(1);
+    expect(await getPaymentBalance(creators[0])).to.eql(LAMPORTS_PER_SOL / 20);
+    expect(await getPaymentBalance(creators[1])).to.eql(LAMPORTS_PER_SOL / 20);
+    expect((await getPaymentBalance(seller.publicKey)) - sellerBalance).to.eql(LAMPORTS_PER_SOL - fee - LAMPORTS_PER_SOL / 10);
+  });
+
+  it('should not pay a native listing in an spl token', async () => {
+    const listing = await signListing(PublicKey.default, 4);
+    await expectError(fillSignedListing(listing, listing, false).rpc(), 'WrongAccount');
+  });
+
+  it('should reject terms other than the signed ones', async () => {
+    const listing = await signListing(PublicKey.default, 2);
+    await expectError(fillSignedListing({ ...listing, price: price.divn(2) }, listing).rpc(), 'InvalidSignature');
+  });
+
+  it('should not fill a cancelled listing', async () => {
+    const listing = await signListing(PublicKey.default, 3);
+    await program.methods.cancelSignedListings([listing.nonce]).accountsStrict({ owner: seller.publicKey, nonceBitmap }).signers([seller]).rpc(confirmOptions);
+
+    await expectError(fillSignedListing(listing).rpc(), 'NonceUsed');
+  });
+
+  it('should not fill listings signed before the seller cancelled all their orders', async () => {
+    await program.methods
+      .cancelAllOrders()
+      .accountsStrict({
+        owner: seller.publicKey,
+        orderEpoch: getOrderEpochPda(seller.publicKey),
+        systemProgram: SystemProgram.programId,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .signers([seller])
+      .rpc(confirmOptions);
+
+    const staleListing = await signListing(PublicKey.default, 5);
+    await expectError(fillSignedListing(staleListing).rpc(), 'StaleOrder');
+
+    const listing = await signListing(PublicKey.default, 6, { epoch: 1 });
+    await fillSignedListing(listing).rpc(confirmOptions);
+    expect(await ownsNft(listing.nftMint)).to.be.true;
+  });
+});
@@ -0,0 +1,202 @@
+import * as anchor from '@coral-xyz/anchor';
+import { AccountMeta, Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from '@solana/web3.js';
+import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  airdrop,
+  cancelListing,
+  confirmOptions,
+  expectError,
+  getAta,
+  getEventAuthority,
+  getOrderEpochPda,
+  getOrderPda,
+  getTokenBalance,
+  getVerificationPda,
+  initMarket,
+  isClosed,
+  marketplace,
+  mintNft,
+  verifyMint,
+} from '../../utils/marketplace';
+
+describe('Marketplace: swaps', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const { connection } = provider;
+  const program = marketplace();
+
+  const maker = Keypair.generate();
+  const taker = Keypair.generate();
+  const feeRecipient = Keypair.generate().publicKey;
+  const sweetener = new anchor.BN(LAMPORTS_PER_SOL / 2);
+  // sweeteners are charged the 1% market fee
+  const fee = LAMPORTS_PER_SOL / 200;
+
+  let market: PublicKey;
+
+  const getBundlePda = (order: PublicKey) => PublicKey.findProgramAddressSync([Buffer.from('bundle'), order.toBuffer()], program.programId)[0];
+  const getBalance = (pubkey: PublicKey) => connection.getBalance(pubkey, 'confirmed');
+  const ownsNft = async (mint: PublicKey, owner: PublicKey) => Number(await getTokenBalance(connection, getAta(mint, owner), TOKEN_2022_PROGRAM_ID)) === 1;
+
+  type SwapParams = { swapMint: PublicKey; sweetener: anchor.BN; makerPays: boolean };
+
+  // sweeteners in native SOL take no payment accounts
+  const createSwap = async (offeredMint: PublicKey, params: SwapParams) => {
+    const nonce = Keypair.generate().publicKey;
+    const order = getOrderPda(nonce, market, maker.publicKey);
+    await program.methods
+      .createSwap({ nonce, paymentMint: PublicKey.default, merkleRoot: null, expiry: null, ...params })
+      .accountsStrict({
+        initializer: maker.publicKey,
+        market,
+        order,
+        orderEpoch: getOrderEpochPda(maker.publicKey),
+        bundle: getBundlePda(order),
+        initializerPaymentTa: null,
+        orderPaymentTa: null,
+        paymentMint: null,
+        paymentTokenProgram: null,
+        nftTokenProgram: TOKEN_2022_PROGRAM_ID,
+        nftProgram: SystemProgram.programId,
+        systemProgram: SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .remainingAccounts([
+        { pubkey: offeredMint, isSigner: false, isWritable: false },
+        { pubkey: getAta(offeredMint, maker.publicKey), isSigner: false, isWritable: true },
+      ])
+      .signers([maker])
+      .rpc(confirmOptions);
+    return order;
+  };
+
+  // the offered mint moves to the taker, then the wanted mint to the maker
+  const fillSwap = (order: PublicKey, offeredMint: PublicKey, wantedMint: PublicKey, membership: AccountMeta[] = []) => {
+    const offeredItem = [
+      { pubkey: offeredMint, isSigner: false, isWritable: false },
+      { pubkey: getAta(offeredMint, maker.publicKey), isSigner: false, isWritable: true },
+      { pubkey: getAta(offeredMint, taker.publicKey), isSigner: false, isWritable: true },
+    ];
+    const wantedItem = [
+      { pubkey: wantedMint, isSigner: false, isWritable: false },
+      { pubkey: getAta(wantedMint, taker.publicKey), isSigner: false, isWritable: true },
+      { pubkey: getAta(wantedMint, maker.publicKey), isSigner: false, isWritable: true },
+      ...membership,
+    ];
+    return program.methods
+      .fillSwap({ itemAccounts: Buffer.from([offeredItem.length, wantedItem.length]), merkleProof: null })
+      .accountsStrict({
+        taker: taker.publicKey,
+        maker: maker.publicKey,
+        market,
+        order,
+        orderEpoch: getOrderEpochPda(maker.publicKey),
+        bundle: getBundlePda(order),
+        feeRecipient,
+        feeRecipientTa: null,
+        makerPaymentTa: null,
+        takerPaymentTa: null,
+        orderPaymentTa: null,
+        paymentMint: null,
+        paymentTokenProgram: null,
+        nftTokenProgram: TOKEN_2022_PROGRAM_ID,
+        nftProgram: SystemProgram.programId,
+        systemProgram: SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .remainingAccounts([...offeredItem, ...wantedItem])
+      .signers([taker]);
+  };
+
+  const cancelSwap = (order: PublicKey, offeredMint: PublicKey) =>
+    program.methods
+      .cancelSwap()
+      .accountsStrict({
+        initializer: maker.publicKey,
+        order,
+        bundle: getBundlePda(order),
+        market,
+        initializerPaymentTa: null,
+        orderPaymentTa: null,
+        paymentMint: null,
+        paymentTokenProgram: null,
+        nftTokenProgram: TOKEN_2022_PROGRAM_ID,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .remainingAccounts([{ pubkey: getAta(offeredMint, maker.publicKey), isSigner: false, isWritable: true }])
+      .signers([maker]);
+
+  before(async () => {
+    await airdrop(connection, maker.publicKey);
+    await airdrop(connection, taker.publicKey);
+    market = await initMarket(provider, { feeRecipient, feeBps: 100 });
+  });
+
+  it('should trade both nfts and pay the escrowed sweetener to the taker', async () => {
+    const offeredMint = await mintNft(provider, maker.publicKey);
+    const wantedMint = await mintNft(provider, taker.publicKey);
+    const order = await createSwap(offeredMint, { swapMint: wantedMint, sweetener, makerPays: true });
+    const takerBalance = await getBalance(taker.publicKey);
+    const feeRecipientBalance = await getBalance(feeRecipient);
+
+    await fillSwap(order, offeredMint, wantedMint).rpc(confirmOptions);
+
+    expect(await ownsNft(offeredMint, taker.publicKey)).to.be.true;
+    expect(await ownsNft(wantedMint, maker.publicKey)).to.be.true;
+    expect(await isClosed(connection, order)).to.be.true;
+    // the taker also pays the rent of the maker's new token account and the transaction fee
+    expect((await getBalance(taker.publicKey)) - takerBalance).to.be.greaterThan(sweetener.toNumber() - fee - LAMPORTS_PER_SOL / 100);
+    expect((await getBalance(feeRecipient)) - feeRecipientBalance).to.eql(fee);
+  });
+
+  it('should not take a mint other than the wanted one', async () => {
+    const offeredMint = await mintNft(provider, maker.publicKey);
+    const wantedMint = await mintNft(provider, taker.publicKey);
+    const otherMint = await mintNft(provider, taker.publicKey);
+    const order = await createSwap(offeredMint, { swapMint: wantedMint, sweetener: new anchor.BN(0), makerPays: false });
+
+    await expectError(fillSwap(order, offeredMint, otherMint).rpc(), 'InvalidNft');
+  });
+
+  it('should take any verified mint and charge the sweetener to the taker', async () => {
+    const offeredMint = await mintNft(provider, maker.publicKey);
+    const wantedMint = await mintNft(provider, taker.publicKey);
+    await verifyMint(provider, market, wantedMint);
+    const order = await createSwap(offeredMint, { swapMint: PublicKey.default, sweetener, makerPays: false });
+    const makerBalance = await getBalance(maker.publicKey);
+    const feeRecipientBalance = await getBalance(feeRecipient);
+
+    await fillSwap(order, offeredMint, wantedMint, [
+      { pubkey: getVerificationPda(wantedMint, market), isSigner: false, isWritable: false },
+    ]).rpc(confirmOptions);
+
+    expect(await ownsNft(wantedMint, maker.publicKey)).to.be.true;
+    // the maker also gets the order and bundle rent back
+    expect((await getBalance(maker.publicKey)) - makerBalance).to.be.greaterThan(sweetener.toNumber() - fee);
+    expect((await getBalance(feeRecipient)) - feeRecipientBalance).to.eql(fee);
+  });
+
+  it('should refund the escrowed sweetener when cancelled', async () => {
+    const offeredMint = await mintNft(provider, maker.publicKey);
+    const order = await createSwap(offeredMint, { swapMint: Keypair.generate().publicKey, sweetener, makerPays: true });
+    const makerBalance = await getBalance(maker.publicKey);
+
+    await cancelSwap(order, offeredMint).rpc(confirmOptions);
+
+    expect(await isClosed(connection, order)).to.be.true;
+    expect((await getBalance(maker.publicKey)) - makerBalance).to.be.greaterThan(sweetener.toNumber());
+  });
+
+  it('should not be cancelled as a listing of its offered nft', async () => {
+    const offeredMint = await mintNft(provider, maker.publicKey);
+    const order = await createSwap(offeredMint, { swapMint: Keypair.generate().publicKey, sweetener, makerPays: true });
+    await expectError((await cancelListing(maker, order, undefined, offeredMint)).rpc(), 'ConstraintRaw');
+  });
+});
@@ -0,0 +1,137 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  airdrop,
+  cancelListing,
+  confirmOptions,
+  createPaymentMint,
+  expectError,
+  getAta,
+  getEventAuthority,
+  getOrderEpochPda,
+  getTokenBalance,
+  getVerificationPda,
+  initMarket,
+  isClosed,
+  list,
+  marketplace,
+  mintNft,
+  verifyMint,
+} from '../../utils/marketplace';
+import { TOKEN_METADATA_PROGRAM_ID, getMasterEditionPda, getMetadataPda, mintLegacyNft } from '../../utils/metaplex';
+
+describe('Marketplace: sweep', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const { connection } = provider;
+  const program = marketplace();
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const feeRecipient = Keypair.generate().publicKey;
+  const price = new anchor.BN(1_000_000);
+  const size = new anchor.BN(1);
+
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+
+  type Listing = { order: PublicKey; nftMint: PublicKey };
+
+  const listNfts = async (count: number) => {
+    const listings: Listing[] = [];
+    for (let i = 0; i < count; i++) {
+      const nftMint = await mintNft(provider, seller.publicKey);
+      listings.push({ order: await list(market, seller, nftMint, { paymentMint, price, size }), nftMint });
+    }
+    return listings;
+  };
+
+  const sweep = (listings: Listing[], maxSpend: number, nftTokenProgram = TOKEN_2022_PROGRAM_ID) =>
+    program.methods
+      .sweep(new anchor.BN(maxSpend))
+      .accountsStrict({
+        taker: buyer.publicKey,
+        market,
+        feeRecipient,
+        feeRecipientTa: getAta(paymentMint, feeRecipient, TOKEN_PROGRAM_ID),
+        buyerPaymentTa: getAta(paymentMint, buyer.publicKey, TOKEN_PROGRAM_ID),
+        paymentMint,
+        paymentTokenProgram: TOKEN_PROGRAM_ID,
+        nftTokenProgram,
+        systemProgram: SystemProgram.programId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        eventAuthority: getEventAuthority(),
+        program: program.programId,
+      })
+      .remainingAccounts(
+        listings.flatMap(({ order, nftMint }) => [
+          { pubkey: order, isSigner: false, isWritable: true },
+          { pubkey: seller.publicKey, isSigner: false, isWritable: true },
+          { pubkey: nftMint, isSigner: false, isWritable: false },
+          { pubkey: getAta(nftMint, seller.publicKey, nftTokenProgram), isSigner: false, isWritable: true },
+          { pubkey: getAta(nftMint, buyer.publicKey, nftTokenProgram), isSigner: false, isWritable: true },
+          { pubkey: getAta(paymentMint, seller.publicKey, TOKEN_PROGRAM_ID), isSigner: false, isWritable: true },
+          { pubkey: getOrderEpochPda(seller.publicKey), isSigner: false, isWritable: false },
+        ]),
+      )
+      .signers([buyer]);
+
+  const getBalance = async (owner: PublicKey) => Number(await getTokenBalance(connection, getAta(paymentMint, owner, TOKEN_PROGRAM_ID)));
+  const ownsNft = async (nftMint: PublicKey) =>
+    Number(await getTokenBalance(connection, getAta(nftMint, buyer.publicKey), TOKEN_2022_PROGRAM_ID).catch(() => BigInt(0))) === 1;
+
+  before(async () => {
+    await airdrop(connection, seller.publicKey);
+    await airdrop(connection, buyer.publicKey);
+    paymentMint = await createPaymentMint(provider, [buyer.publicKey, feeRecipient], 10_000_000);
+    market = await initMarket(provider, { feeRecipient, feeBps: 100 });
+  });
+
+  it('should fill every listing with a single fee transfer', async () => {
+    const listings = await listNfts(2);
+    const buyerBalance = await getBalance(buyer.publicKey);
+    const feeRecipientBalance = await getBalance(feeRecipient);
+
+    await sweep(listings, 2_000_000).rpc(confirmOptions);
+
+    for (const { order, nftMint } of listings) {
+      expect(await ownsNft(nftMint)).to.be.true;
+      expect(await isClosed(connection, order)).to.be.true;
+    }
+    expect(buyerBalance - (await getBalance(buyer.publicKey))).to.eql(2_000_000);
+    expect((await getBalance(feeRecipient)) - feeRecipientBalance).to.eql(20_000);
+  });
+
+  it('should skip cancelled listings and listings over the max spend', async () => {
+    const [filled, cancelled, overSpend] = await listNfts(3);
+    await (await cancelListing(seller, cancelled.order)).rpc(confirmOptions);
+    const buyerBalance = await getBalance(buyer.publicKey);
+
+    await sweep([filled, cancelled, overSpend], 1_500_000).rpc(confirmOptions);
+
+    expect(await ownsNft(filled.nftMint)).to.be.true;
+    expect(await ownsNft(cancelled.nftMint)).to.be.false;
+    expect(await ownsNft(overSpend.nftMint)).to.be.false;
+    expect(await isClosed(connection, overSpend.order)).to.be.false;
+    expect(buyerBalance - (await getBalance(buyer.publicKey))).to.eql(1_000_000);
+  });
+
+  it('should reject legacy metaplex nfts', async () => {
+    const nftMint = await mintLegacyNft(provider, seller.publicKey);
+    await verifyMint(provider, market, nftMint);
+    const order = await list(market, seller, nftMint, { paymentMint, price, size }, {
+      nftTokenProgram: TOKEN_PROGRAM_ID,
+      nftProgram: TOKEN_METADATA_PROGRAM_ID,
+      remainingAccounts: [
+        { pubkey: getMetadataPda(nftMint), isSigner: false, isWritable: true },
+        { pubkey: getMasterEditionPda(nftMint), isSigner: false, isWritable: false },
+        { pubkey: getVerificationPda(nftMint, market), isSigner: false, isWritable: false },
+      ],
+    });
+
+    await expectError(sweep([{ order, nftMint }], 1_000_000, TOKEN_PROGRAM_ID).rpc(), 'UnsupportedNft');
+  });
+});
@@ -0,0 +1,92 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { TOKEN_2022_PROGRAM_ID } from '@solana/spl-token';
+import { createHash } from 'crypto';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  NftParams,
+  airdrop,
+  bid,
+  confirmOptions,
+  createPaymentMint,
+  expectError,
+  fillOrder,
+  getAta,
+  getTokenBalance,
+  getVerificationPda,
+  initMarket,
+  mintNft,
+  verifyMint,
+} from '../../utils/marketplace';
+
+// leaves and nodes are hashed with a 0 and 1 prefix, node pairs are sorted
+const hashLeaf = (mint: PublicKey) => createHash('sha256').update(Buffer.from([0])).update(mint.toBuffer()).digest();
+const hashNode = (a: Buffer, b: Buffer) => {
+  const [left, right] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+  return createHash('sha256').update(Buffer.from([1])).update(left).update(right).digest();
+};
+
+describe('Marketplace: trait bids', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const { connection } = provider;
+
+  const buyer = Keypair.generate();
+  const seller = Keypair.generate();
+  const price = new anchor.BN(1_000_000);
+  const size = new anchor.BN(1);
+
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+  let eligibleMints: PublicKey[];
+  let otherMint: PublicKey;
+  let merkleRoot: Buffer;
+
+  const verifiedNft = (nftMint: PublicKey): NftParams => ({
+    nftTokenProgram: TOKEN_2022_PROGRAM_ID,
+    nftProgram: SystemProgram.programId,
+    remainingAccounts: [{ pubkey: getVerificationPda(nftMint, market), isSigner: false, isWritable: false }],
+  });
+
+  const traitBid = () => bid(market, buyer, null, paymentMint, { price, size, merkleRoot: [...merkleRoot] });
+
+  before(async () => {
+    await airdrop(connection, buyer.publicKey);
+    await airdrop(connection, seller.publicKey);
+    paymentMint = await createPaymentMint(provider, [buyer.publicKey], 10_000_000);
+    market = await initMarket(provider, { feeRecipient: Keypair.generate().publicKey, feeBps: 0 });
+
+    eligibleMints = [await mintNft(provider, seller.publicKey), await mintNft(provider, seller.publicKey)];
+    otherMint = await mintNft(provider, seller.publicKey);
+    for (const nftMint of [...eligibleMints, otherMint]) {
+      await verifyMint(provider, market, nftMint);
+    }
+    merkleRoot = hashNode(hashLeaf(eligibleMints[0]), hashLeaf(eligibleMints[1]));
+  });
+
+  it('should be filled by an eligible mint with its proof', async () => {
+    const buyOrder = await traitBid();
+    const [nftMint, sibling] = eligibleMints;
+
+    await (await fillOrder(seller, buyOrder, size, verifiedNft(nftMint), nftMint, [hashLeaf(sibling)])).rpc(confirmOptions);
+
+    expect(Number(await getTokenBalance(connection, getAta(nftMint, buyer.publicKey), TOKEN_2022_PROGRAM_ID))).to.eql(1);
+  });
+
+  it('should require a merkle proof', async () => {
+    const buyOrder = await traitBid();
+    const nftMint = eligibleMints[1];
+
+    await expectError((await fillOrder(seller, buyOrder, size, verifiedNft(nftMint), nftMint)).rpc(), 'MerkleProofRequired');
+  });
+
+  it('should reject a verified mint outside of the merkle root', async () => {
+    const buyOrder = await traitBid();
+
+    await expectError(
+      (await fillOrder(seller, buyOrder, size, verifiedNft(otherMint), otherMint, [hashLeaf(eligibleMints[0])])).rpc(),
+      'InvalidMerkleProof',
+    );
+  });
+});
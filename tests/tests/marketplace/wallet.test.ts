@@ -0,0 +1,103 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram } from '@solana/web3.js';
+import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID } from '@solana/spl-token';
+import { expect } from 'chai';
+import { describe, it } from 'mocha';
+import {
+  airdrop,
+  bid,
+  confirmOptions,
+  createPaymentMint,
+  expectError,
+  fillOrder,
+  getAta,
+  getEventAuthority,
+  getTokenBalance,
+  getWalletPda,
+  initMarket,
+  marketplace,
+  mintNft,
+} from '../../utils/marketplace';
+
+describe('Marketplace: wallet backed bids', () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = marketplace();
+  const { connection } = provider;
+
+  const seller = Keypair.generate();
+  const buyer = Keypair.generate();
+  const paymentAmount = 1_000_000_000;
+  const price = new anchor.BN(1_000_000);
+  const size = new anchor.BN(1);
+  const wallet = getWalletPda(buyer.publicKey);
+
+  let market: PublicKey;
+  let paymentMint: PublicKey;
+
+  const walletAccounts = () => ({
+    owner: buyer.publicKey,
+    wallet,
+    ownerPaymentTa: getAta(paymentMint, buyer.publicKey, TOKEN_PROGRAM_ID),
+    walletPaymentTa: getAta(paymentMint, wallet, TOKEN_PROGRAM_ID),
+    paymentMint,
+    paymentTokenProgram: TOKEN_PROGRAM_ID,
+    systemProgram: SystemProgram.programId,
+    associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    eventAuthority: getEventAuthority(),
+    program: program.programId,
+  });
+
+  const deposit = (amount: number) =>
+    program.methods.depositWallet(new anchor.BN(amount)).accountsStrict(walletAccounts()).signers([buyer]).rpc(confirmOptions);
+
+  const withdraw = (amount: number) =>
+    program.methods.withdrawWallet(new anchor.BN(amount)).accountsStrict(walletAccounts()).signers([buyer]).rpc(confirmOptions);
+
+  const getBalance = (owner: PublicKey) => getTokenBalance(connection, getAta(paymentMint, owner, TOKEN_PROGRAM_ID));
+
+  const walletBid = async () => {
+    const nftMint = await mintNft(provider, seller.publicKey);
+    const buyOrder = await bid(market, buyer, nftMint, paymentMint, { price, size, walletBacked: true });
+    return { nftMint, buyOrder };
+  };
+
+  before(async () => {
+    await airdrop(connection, seller.publicKey);
+    await airdrop(connection, buyer.publicKey);
+    market = await initMarket(provider, { feeRecipient: Keypair.generate().publicKey, feeBps: 100 });
+    paymentMint = await createPaymentMint(provider, [buyer.publicKey], paymentAmount);
+    await deposit(1_500_000);
+  });
+
+  it('should hold the deposited balance in the wallet', async () => {
+    expect(await getBalance(wallet)).to.eql(BigInt(1_500_000));
+    expect(await getBalance(buyer.publicKey)).to.eql(BigInt(paymentAmount - 1_500_000));
+  });
+
+  it('should not escrow wallet backed bids', async () => {
+    const { buyOrder } = await walletBid();
+    expect((await program.account.order.fetch(buyOrder, 'confirmed')).walletBacked).to.be.true;
+    expect(await getBalance(buyer.publicKey)).to.eql(BigInt(paymentAmount - 1_500_000));
+    expect(await getBalance(buyOrder)).to.eql(BigInt(0));
+  });
+
+  it('should pay a filled bid out of the wallet', async () => {
+    const { nftMint, buyOrder } = await walletBid();
+    await (await fillOrder(seller, buyOrder, size, undefined, nftMint)).rpc(confirmOptions);
+
+    expect(await getBalance(wallet)).to.eql(BigInt(500_000));
+    expect(await getBalance(seller.publicKey)).to.eql(BigInt(990_000));
+  });
+
+  it('should not fill a bid the wallet no longer covers', async () => {
+    const { nftMint, buyOrder } = await walletBid();
+    await expectError((await fillOrder(seller, buyOrder, size, undefined, nftMint)).rpc(), 'InsufficientBalance');
+  });
+
+  it('should not withdraw more than the balance', async () => {
+    await expectError(withdraw(500_001), 'InsufficientBalance');
+    await withdraw(500_000);
+    expect(await getBalance(wallet)).to.eql(BigInt(0));
+  });
+});
@@ -0,0 +1,61 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Connection, Keypair, PublicKey, SystemProgram, Transaction, TransactionInstruction } from '@solana/web3.js';
+
+export const CORE_PROGRAM_ID = new PublicKey('CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d');
+
+const confirmOptions = {
+  skipPreflight: true,
+  preflightCommitment: 'confirmed',
+  commitment: 'confirmed',
+} as const;
+
+const borshString = (value: string) => {
+  const bytes = Buffer.from(value);
+  const length = Buffer.alloc(4);
+  length.writeUInt32LE(bytes.length);
+  return Buffer.concat([length, bytes]);
+};
+
+// CreateCollectionV1 without plugins, the payer is the update authority
+export const createCoreCollection = async (provider: anchor.AnchorProvider) => {
+  const collection = Keypair.generate();
+  const ix = new TransactionInstruction({
+    programId: CORE_PROGRAM_ID,
+    keys: [
+      { pubkey: collection.publicKey, isSigner: true, isWritable: true },
+      { pubkey: CORE_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: provider.wallet.publicKey, isSigner: true, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.concat([Buffer.from([1]), borshString('Collection'), borshString('https://example.com/collection.json'), Buffer.from([0])]),
+  });
+  await provider.sendAndConfirm(new Transaction().add(ix), [collection], confirmOptions);
+  return collection.publicKey;
+};
+
+// CreateV1 of an asset in the collection without plugins, held by owner
+export const mintCoreAsset = async (provider: anchor.AnchorProvider, collection: PublicKey, owner: PublicKey) => {
+  const asset = Keypair.generate();
+  const ix = new TransactionInstruction({
+    programId: CORE_PROGRAM_ID,
+    keys: [
+      { pubkey: asset.publicKey, isSigner: true, isWritable: true },
+      { pubkey: collection, isSigner: false, isWritable: true },
+      { pubkey: provider.wallet.publicKey, isSigner: true, isWritable: false },
+      { pubkey: provider.wallet.publicKey, isSigner: true, isWritable: true },
+      { pubkey: owner, isSigner: false, isWritable: false },
+      { pubkey: CORE_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: CORE_PROGRAM_ID, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.concat([Buffer.from([0, 0]), borshString('Asset'), borshString('https://example.com/asset.json'), Buffer.from([0])]),
+  });
+  await provider.sendAndConfirm(new Transaction().add(ix), [asset], confirmOptions);
+  return asset.publicKey;
+};
+
+export const getCoreAssetOwner = async (connection: Connection, asset: PublicKey) => {
+  const account = await connection.getAccountInfo(asset, 'confirmed');
+  // the owner follows the account key
+  return new PublicKey(account!.data.subarray(1, 33));
+};
@@ -0,0 +1,117 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { ComputeBudgetProgram, Keypair, PublicKey, SystemProgram, Transaction } from '@solana/web3.js';
+import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, getAssociatedTokenAddressSync } from '@solana/spl-token';
+import { RaribleEditionsControls } from '../../target/types/rarible_editions_controls';
+import { RaribleEditions } from '../../target/types/rarible_editions';
+import { TOKEN_GROUP_EXTENSION_PROGRAM_ID } from '../constants';
+import { getEditionsControlsPda, getEditionsPda, getHashlistMarkerPda, getHashlistPda, getMinterStatsPda, getMinterStatsPhasePda } from './pdas';
+
+export type EditionsRoyalties = {
+  royaltyBasisPoints: anchor.BN;
+  creators: { address: PublicKey; share: number }[];
+};
+
+// deploys a collection with a free open phase and mints one edition to minter, paid by the provider wallet
+export const mintEdition = async (provider: anchor.AnchorProvider, minter: Keypair, royalties: EditionsRoyalties) => {
+  const editionsControlsProgram = anchor.workspace.RaribleEditionsControls as Program<RaribleEditionsControls>;
+  const editionsProgram = anchor.workspace.RaribleEditions as Program<RaribleEditions>;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const computeUnits = ComputeBudgetProgram.setComputeUnitLimit({ units: 800000 });
+
+  const symbol = `M${Keypair.generate().publicKey.toBase58().slice(0, 6)}`;
+  const editionsDeployment = getEditionsPda(symbol, editionsProgram.programId);
+  const editionsControls = getEditionsControlsPda(editionsDeployment, editionsControlsProgram.programId);
+  const hashlist = getHashlistPda(editionsDeployment, editionsProgram.programId);
+  const groupMint = Keypair.generate();
+  const group = Keypair.generate();
+
+  const initialiseIx = await editionsControlsProgram.methods
+    .initialiseEditionsControls({
+      maxMintsPerWallet: new anchor.BN(10),
+      treasury: payer.publicKey,
+      maxNumberOfTokens: new anchor.BN(10),
+      symbol,
+      collectionName: 'Marketplace editions',
+      collectionUri: 'https://example.com/editions.json',
+      cosignerProgramId: null,
+      royalties,
+      platformFee: {
+        platformFeeValue: new anchor.BN(0),
+        recipients: [{ address: payer.publicKey, share: 100 }],
+        isFeeFlat: true,
+      },
+      extraMeta: [],
+      itemBaseUri: 'https://example.com/editions/{}.json',
+      itemBaseName: 'Edition #{}',
+    })
+    .accountsStrict({
+      editionsControls,
+      editionsDeployment,
+      hashlist,
+      payer: payer.publicKey,
+      creator: payer.publicKey,
+      groupMint: groupMint.publicKey,
+      group: group.publicKey,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      raribleEditionsProgram: editionsProgram.programId,
+      groupExtensionProgram: new PublicKey(TOKEN_GROUP_EXTENSION_PROGRAM_ID),
+    })
+    .instruction();
+  await provider.sendAndConfirm(new Transaction().add(computeUnits).add(initialiseIx), [groupMint, group, payer]);
+
+  const now = Math.floor(Date.now() / 1000);
+  await editionsControlsProgram.methods
+    .addPhase({
+      maxMintsPerWallet: new anchor.BN(10),
+      maxMintsTotal: new anchor.BN(10),
+      priceAmount: new anchor.BN(0),
+      startTime: new anchor.BN(now - 60),
+      endTime: new anchor.BN(now + 60 * 60),
+      priceToken: new PublicKey('So11111111111111111111111111111111111111112'),
+      isPrivate: false,
+      merkleRoot: null,
+    })
+    .accountsStrict({
+      editionsControls,
+      creator: payer.publicKey,
+      payer: payer.publicKey,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      raribleEditionsProgram: editionsProgram.programId,
+    })
+    .rpc();
+
+  const mint = Keypair.generate();
+  const member = Keypair.generate();
+  const mintIx = await editionsControlsProgram.methods
+    .mintWithControls({ phaseIndex: 0, merkleProof: null, allowListPrice: null, allowListMaxClaims: null })
+    .accountsStrict({
+      editionsDeployment,
+      editionsControls,
+      hashlist,
+      hashlistMarker: getHashlistMarkerPda(editionsDeployment, mint.publicKey, editionsProgram.programId),
+      payer: minter.publicKey,
+      signer: minter.publicKey,
+      minter: minter.publicKey,
+      minterStats: getMinterStatsPda(editionsDeployment, minter.publicKey, editionsControlsProgram.programId),
+      minterStatsPhase: getMinterStatsPhasePda(editionsDeployment, minter.publicKey, 0, editionsControlsProgram.programId),
+      mint: mint.publicKey,
+      member: member.publicKey,
+      group: group.publicKey,
+      groupMint: groupMint.publicKey,
+      platformFeeRecipient1: payer.publicKey,
+      tokenAccount: getAssociatedTokenAddressSync(mint.publicKey, minter.publicKey, false, TOKEN_2022_PROGRAM_ID),
+      treasury: payer.publicKey,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      groupExtensionProgram: new PublicKey(TOKEN_GROUP_EXTENSION_PROGRAM_ID),
+      systemProgram: SystemProgram.programId,
+      raribleEditionsProgram: editionsProgram.programId,
+    })
+    .instruction();
+  await provider.sendAndConfirm(new Transaction().add(computeUnits).add(mintIx), [minter, mint, member]);
+
+  return { mint: mint.publicKey, editionsDeployment };
+};
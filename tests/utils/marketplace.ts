@@ -0,0 +1,378 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { AccountMeta, Connection, Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY } from '@solana/web3.js';
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccount,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  mintTo,
+} from '@solana/spl-token';
+import { expect } from 'chai';
+import { Marketplace } from '../../target/types/marketplace';
+
+export const confirmOptions = {
+  skipPreflight: true,
+  preflightCommitment: 'confirmed',
+  commitment: 'confirmed',
+} as const;
+
+export const marketplace = () => anchor.workspace.Marketplace as Program<Marketplace>;
+
+export const getMarketPda = (marketIdentifier: PublicKey) =>
+  PublicKey.findProgramAddressSync([Buffer.from('market'), marketIdentifier.toBuffer()], marketplace().programId)[0];
+
+export const getOrderPda = (nonce: PublicKey, market: PublicKey, owner: PublicKey) =>
+  PublicKey.findProgramAddressSync([Buffer.from('order'), nonce.toBuffer(), market.toBuffer(), owner.toBuffer()], marketplace().programId)[0];
+
+export const getOrderEpochPda = (owner: PublicKey) =>
+  PublicKey.findProgramAddressSync([Buffer.from('epoch'), owner.toBuffer()], marketplace().programId)[0];
+
+export const getWalletPda = (owner: PublicKey) =>
+  PublicKey.findProgramAddressSync([Buffer.from('wallet'), owner.toBuffer()], marketplace().programId)[0];
+
+export const getVerificationPda = (nftMint: PublicKey, market: PublicKey) =>
+  PublicKey.findProgramAddressSync([Buffer.from('verification'), nftMint.toBuffer(), market.toBuffer()], marketplace().programId)[0];
+
+export const getEventAuthority = () => PublicKey.findProgramAddressSync([Buffer.from('__event_authority')], marketplace().programId)[0];
+
+export const getAta = (mint: PublicKey, owner: PublicKey, tokenProgram = TOKEN_2022_PROGRAM_ID) =>
+  getAssociatedTokenAddressSync(mint, owner, true, tokenProgram);
+
+export const airdrop = async (connection: Connection, pubkey: PublicKey, lamports = 10 * LAMPORTS_PER_SOL) => {
+  const signature = await connection.requestAirdrop(pubkey, lamports);
+  const latestBlockhash = await connection.getLatestBlockhash('confirmed');
+  await connection.confirmTransaction({ signature, ...latestBlockhash }, 'confirmed');
+};
+
+export const isClosed = async (connection: Connection, pubkey: PublicKey) => (await connection.getAccountInfo(pubkey, 'confirmed')) === null;
+
+export const getTokenBalance = async (connection: Connection, tokenAccount: PublicKey, tokenProgram = TOKEN_PROGRAM_ID) =>
+  (await getAccount(connection, tokenAccount, 'confirmed', tokenProgram)).amount;
+
+export const getChainTime = async (connection: Connection) => connection.getBlockTime(await connection.getSlot('confirmed'));
+
+export const waitUntil = async (connection: Connection, time: number) => {
+  while ((await getChainTime(connection)) <= time) {
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+  }
+};
+
+export const expectError = async (tx: Promise<unknown>, code: string) => {
+  const err = await tx.then(
+    () => null,
+    (err) => err,
+  );
+  expect(err?.error?.errorCode?.code, `expected ${code}`).to.eql(code);
+};
+
+// events emitted through self cpi by the marketplace in a confirmed transaction
+export const getEmittedEvents = async (connection: Connection, signature: string) => {
+  const program = marketplace();
+  const tx = await connection.getTransaction(signature, { commitment: 'confirmed', maxSupportedTransactionVersion: 0 });
+  const accountKeys = tx!.transaction.message.getAccountKeys().staticAccountKeys;
+  return (tx!.meta?.innerInstructions ?? [])
+    .flatMap((inner) => inner.instructions)
+    .filter((ix) => accountKeys[ix.programIdIndex].equals(program.programId))
+    .map((ix) => program.coder.events.decode(Buffer.from(anchor.utils.bytes.bs58.decode(ix.data)).subarray(8).toString('base64')))
+    .filter((event) => event !== null);
+};
+
+// token 2022 mint without extensions held by owner
+export const mintNft = async (provider: anchor.AnchorProvider, owner: PublicKey, supply = 1) => {
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const mint = await createMint(provider.connection, payer, payer.publicKey, null, 0, undefined, confirmOptions, TOKEN_2022_PROGRAM_ID);
+  const tokenAccount = await createAssociatedTokenAccount(provider.connection, payer, mint, owner, confirmOptions, TOKEN_2022_PROGRAM_ID);
+  await mintTo(provider.connection, payer, mint, tokenAccount, payer, supply, [], confirmOptions, TOKEN_2022_PROGRAM_ID);
+  return mint;
+};
+
+// spl token mint with 6 decimals, funding each holder with amount
+export const createPaymentMint = async (provider: anchor.AnchorProvider, holders: PublicKey[], amount: number) => {
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const mint = await createMint(provider.connection, payer, payer.publicKey, null, 6, undefined, confirmOptions, TOKEN_PROGRAM_ID);
+  for (const holder of holders) {
+    const tokenAccount = await createAssociatedTokenAccount(provider.connection, payer, mint, holder, confirmOptions, TOKEN_PROGRAM_ID);
+    await mintTo(provider.connection, payer, mint, tokenAccount, payer, amount, [], confirmOptions, TOKEN_PROGRAM_ID);
+  }
+  return mint;
+};
+
+export type MarketParams = {
+  feeRecipient: PublicKey;
+  feeBps: number;
+  marketIdentifier?: PublicKey;
+};
+
+export const initMarket = async (provider: anchor.AnchorProvider, params: MarketParams) => {
+  const program = marketplace();
+  const marketIdentifier = params.marketIdentifier ?? Keypair.generate().publicKey;
+  const market = getMarketPda(marketIdentifier);
+  await program.methods
+    .initMarket({ feeRecipient: params.feeRecipient, feeBps: new anchor.BN(params.feeBps) })
+    .accountsStrict({
+      initializer: provider.wallet.publicKey,
+      marketIdentifier,
+      market,
+      systemProgram: SystemProgram.programId,
+      eventAuthority: getEventAuthority(),
+      program: program.programId,
+    })
+    .rpc(confirmOptions);
+  return market;
+};
+
+export const verifyMint = async (provider: anchor.AnchorProvider, market: PublicKey, nftMint: PublicKey) => {
+  const program = marketplace();
+  await program.methods
+    .verifyMint()
+    .accountsStrict({
+      initializer: provider.wallet.publicKey,
+      market,
+      nftMint,
+      verification: getVerificationPda(nftMint, market),
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc(confirmOptions);
+};
+
+// token and nft programs of a mint, with the remaining accounts its standard needs
+export type NftParams = {
+  nftTokenProgram: PublicKey;
+  nftProgram: PublicKey;
+  remainingAccounts: AccountMeta[];
+};
+
+export const plainNft: NftParams = {
+  nftTokenProgram: TOKEN_2022_PROGRAM_ID,
+  nftProgram: SystemProgram.programId,
+  remainingAccounts: [],
+};
+
+export type ListParams = {
+  nonce: PublicKey;
+  paymentMint: PublicKey;
+  price: anchor.BN;
+  size: anchor.BN;
+  expiry?: anchor.BN;
+  endPrice?: anchor.BN;
+  endTime?: anchor.BN;
+  auction?: { endTime: anchor.BN; minIncrement: anchor.BN; timeExtension: anchor.BN };
+  taker?: PublicKey;
+  escrow?: boolean;
+};
+
+// escrow listings move the nft into a token account of the order
+export const listNft = (market: PublicKey, owner: Keypair, nftMint: PublicKey, params: ListParams, nft = plainNft) => {
+  const program = marketplace();
+  const order = getOrderPda(params.nonce, market, owner.publicKey);
+  return program.methods
+    .list({
+      nonce: params.nonce,
+      paymentMint: params.paymentMint,
+      price: params.price,
+      size: params.size,
+      expiry: params.expiry ?? null,
+      endPrice: params.endPrice ?? null,
+      endTime: params.endTime ?? null,
+      auction: params.auction ?? null,
+      taker: params.taker ?? null,
+      escrow: params.escrow ?? null,
+    })
+    .accountsStrict({
+      initializer: owner.publicKey,
+      market,
+      order,
+      orderEpoch: getOrderEpochPda(owner.publicKey),
+      nftMint,
+      initializerNftTa: getAta(nftMint, owner.publicKey, nft.nftTokenProgram),
+      orderNftTa: params.escrow ? getAta(nftMint, order, nft.nftTokenProgram) : null,
+      sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      systemProgram: SystemProgram.programId,
+      nftTokenProgram: nft.nftTokenProgram,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      nftProgram: nft.nftProgram,
+      eventAuthority: getEventAuthority(),
+      program: program.programId,
+    })
+    .remainingAccounts(nft.remainingAccounts)
+    .signers([owner]);
+};
+
+export const list = async (market: PublicKey, owner: Keypair, nftMint: PublicKey, params: Omit<ListParams, 'nonce'>, nft = plainNft) => {
+  const nonce = Keypair.generate().publicKey;
+  await listNft(market, owner, nftMint, { nonce, ...params }, nft).rpc(confirmOptions);
+  return getOrderPda(nonce, market, owner.publicKey);
+};
+
+export const cancelListing = async (owner: Keypair, orderAddress: PublicKey, nft = plainNft, nftMint?: PublicKey) => {
+  const program = marketplace();
+  const order = await program.account.order.fetch(orderAddress, 'confirmed');
+  const mint = nftMint ?? order.nftMint;
+  return program.methods
+    .cancelListing()
+    .accountsStrict({
+      initializer: owner.publicKey,
+      order: orderAddress,
+      market: order.market,
+      nftMint: mint,
+      initializerNftTa: getAta(mint, owner.publicKey, nft.nftTokenProgram),
+      orderNftTa: order.escrowed ? getAta(mint, orderAddress, nft.nftTokenProgram) : null,
+      systemProgram: SystemProgram.programId,
+      sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      nftTokenProgram: nft.nftTokenProgram,
+      nftProgram: nft.nftProgram,
+      eventAuthority: getEventAuthority(),
+      program: program.programId,
+    })
+    .remainingAccounts(nft.remainingAccounts)
+    .signers([owner]);
+};
+
+export type BidParams = {
+  nonce: PublicKey;
+  price: anchor.BN;
+  size: anchor.BN;
+  expiry?: anchor.BN;
+  merkleRoot?: number[];
+  walletBacked?: boolean;
+};
+
+// a null nft mint places a collection bid that any verified mint of the market can fill
+export const bidNft = (market: PublicKey, owner: Keypair, nftMint: PublicKey | null, paymentMint: PublicKey, params: BidParams) => {
+  const program = marketplace();
+  const order = getOrderPda(params.nonce, market, owner.publicKey);
+  // native SOL bids escrow lamports in the order and take no payment accounts
+  const isNative = paymentMint.equals(PublicKey.default);
+  return program.methods
+    .bid({
+      nonce: params.nonce,
+      price: params.price,
+      size: params.size,
+      expiry: params.expiry ?? null,
+      merkleRoot: params.merkleRoot ?? null,
+      walletBacked: params.walletBacked ?? null,
+    })
+    .accountsStrict({
+      initializer: owner.publicKey,
+      market,
+      order,
+      orderEpoch: getOrderEpochPda(owner.publicKey),
+      initializerPaymentTa: isNative ? null : getAta(paymentMint, owner.publicKey, TOKEN_PROGRAM_ID),
+      orderPaymentTa: isNative ? null : getAta(paymentMint, order, TOKEN_PROGRAM_ID),
+      paymentMint: isNative ? null : paymentMint,
+      paymentTokenProgram: isNative ? null : TOKEN_PROGRAM_ID,
+      nftMint,
+      systemProgram: SystemProgram.programId,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      eventAuthority: getEventAuthority(),
+      program: program.programId,
+    })
+    .signers([owner]);
+};
+
+export const bid = async (market: PublicKey, owner: Keypair, nftMint: PublicKey | null, paymentMint: PublicKey, params: Omit<BidParams, 'nonce'>) => {
+  const nonce = Keypair.generate().publicKey;
+  await bidNft(market, owner, nftMint, paymentMint, { nonce, ...params }).rpc(confirmOptions);
+  return getOrderPda(nonce, market, owner.publicKey);
+};
+
+export const cancelBid = async (owner: Keypair, orderAddress: PublicKey) => {
+  const program = marketplace();
+  const order = await program.account.order.fetch(orderAddress, 'confirmed');
+  const isNative = order.paymentMint.equals(PublicKey.default);
+  return program.methods
+    .cancelBid()
+    .accountsStrict({
+      initializer: owner.publicKey,
+      order: orderAddress,
+      market: order.market,
+      initializerPaymentTa: isNative ? null : getAta(order.paymentMint, owner.publicKey, TOKEN_PROGRAM_ID),
+      orderPaymentTa: isNative ? null : getAta(order.paymentMint, orderAddress, TOKEN_PROGRAM_ID),
+      paymentMint: isNative ? null : order.paymentMint,
+      paymentTokenProgram: isNative ? null : TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      eventAuthority: getEventAuthority(),
+      program: program.programId,
+    })
+    .signers([owner]);
+};
+
+export const editOrder = async (owner: Keypair, orderAddress: PublicKey, price: anchor.BN, size: anchor.BN) => {
+  const program = marketplace();
+  const order = await program.account.order.fetch(orderAddress, 'confirmed');
+  const hasEscrow = order.side === 0 && !order.paymentMint.equals(PublicKey.default);
+  return program.methods
+    .editOrder({ price, size })
+    .accountsStrict({
+      initializer: owner.publicKey,
+      market: order.market,
+      order: orderAddress,
+      initializerPaymentTa: hasEscrow ? getAta(order.paymentMint, owner.publicKey, TOKEN_PROGRAM_ID) : null,
+      orderPaymentTa: hasEscrow ? getAta(order.paymentMint, orderAddress, TOKEN_PROGRAM_ID) : null,
+      paymentMint: hasEscrow ? order.paymentMint : null,
+      paymentTokenProgram: hasEscrow ? TOKEN_PROGRAM_ID : null,
+      systemProgram: SystemProgram.programId,
+      eventAuthority: getEventAuthority(),
+      program: program.programId,
+    })
+    .signers([owner]);
+};
+
+// fills an order paid in native SOL or an spl token, the nft mint defaults to the one of the order
+// trait bids also take a merkle proof of the nft mint
+export const fillOrder = async (
+  taker: Keypair,
+  orderAddress: PublicKey,
+  amount: anchor.BN,
+  nft = plainNft,
+  nftMint?: PublicKey,
+  merkleProof?: Buffer[],
+) => {
+  const program = marketplace();
+  const order = await program.account.order.fetch(orderAddress, 'confirmed');
+  const market = await program.account.market.fetch(order.market, 'confirmed');
+  const isBuy = order.side === 0;
+  const isNative = order.paymentMint.equals(PublicKey.default);
+  const mint = nftMint ?? order.nftMint;
+  const seller = isBuy ? taker.publicKey : order.owner;
+  const buyer = isBuy ? order.owner : taker.publicKey;
+  // escrow listings sell out of the token account of the order
+  const nftHolder = order.escrowed ? orderAddress : seller;
+  // wallet backed bids pay out of the wallet account of the bidder
+  const wallet = order.walletBacked ? getWalletPda(order.owner) : null;
+  return program.methods
+    .fillOrder(amount, merkleProof ?? null)
+    .accountsStrict({
+      taker: taker.publicKey,
+      maker: order.owner,
+      market: order.market,
+      order: orderAddress,
+      orderEpoch: getOrderEpochPda(order.owner),
+      wallet,
+      nftMint: mint,
+      sellerNftTa: getAta(mint, nftHolder, nft.nftTokenProgram),
+      buyerNftTa: getAta(mint, buyer, nft.nftTokenProgram),
+      feeRecipient: market.feeRecipient,
+      feeRecipientTa: isNative ? null : getAta(order.paymentMint, market.feeRecipient, TOKEN_PROGRAM_ID),
+      systemProgram: SystemProgram.programId,
+      nftTokenProgram: nft.nftTokenProgram,
+      nftProgram: nft.nftProgram,
+      sellerPaymentTa: isNative ? null : getAta(order.paymentMint, seller, TOKEN_PROGRAM_ID),
+      buyerPaymentTa: isNative ? null : getAta(order.paymentMint, isBuy ? (wallet ?? orderAddress) : taker.publicKey, TOKEN_PROGRAM_ID),
+      makerPaymentTa: isNative ? null : getAta(order.paymentMint, order.owner, TOKEN_PROGRAM_ID),
+      paymentMint: isNative ? null : order.paymentMint,
+      paymentTokenProgram: isNative ? null : TOKEN_PROGRAM_ID,
+      sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      eventAuthority: getEventAuthority(),
+      program: program.programId,
+    })
+    .remainingAccounts(nft.remainingAccounts)
+    .signers([taker]);
+};
@@ -0,0 +1,196 @@
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram, SYSVAR_INSTRUCTIONS_PUBKEY, SYSVAR_RENT_PUBKEY, Transaction, TransactionInstruction } from '@solana/web3.js';
+import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID, createAssociatedTokenAccount, createMint, getAssociatedTokenAddressSync, mintTo } from '@solana/spl-token';
+
+export const TOKEN_METADATA_PROGRAM_ID = new PublicKey('metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s');
+
+const confirmOptions = {
+  skipPreflight: true,
+  preflightCommitment: 'confirmed',
+  commitment: 'confirmed',
+} as const;
+
+export const getMetadataPda = (mint: PublicKey) =>
+  PublicKey.findProgramAddressSync([Buffer.from('metadata'), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()], TOKEN_METADATA_PROGRAM_ID)[0];
+
+export const getMasterEditionPda = (mint: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from('metadata'), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer(), Buffer.from('edition')],
+    TOKEN_METADATA_PROGRAM_ID,
+  )[0];
+
+export const getTokenRecordPda = (mint: PublicKey, tokenAccount: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from('metadata'), TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer(), Buffer.from('token_record'), tokenAccount.toBuffer()],
+    TOKEN_METADATA_PROGRAM_ID,
+  )[0];
+
+// token record states
+export const TokenState = {
+  Unlocked: 0,
+  Locked: 1,
+  Listed: 2,
+} as const;
+
+const borshString = (value: string) => {
+  const bytes = Buffer.from(value);
+  const length = Buffer.alloc(4);
+  length.writeUInt32LE(bytes.length);
+  return Buffer.concat([length, bytes]);
+};
+
+const u16 = (value: number) => {
+  const buffer = Buffer.alloc(2);
+  buffer.writeUInt16LE(value);
+  return buffer;
+};
+
+const u32 = (value: number) => {
+  const buffer = Buffer.alloc(4);
+  buffer.writeUInt32LE(value);
+  return buffer;
+};
+
+export type Royalties = {
+  sellerFeeBasisPoints: number;
+  creators: { address: PublicKey; share: number }[];
+};
+
+const noRoyalties: Royalties = { sellerFeeBasisPoints: 0, creators: [] };
+
+// seller fee basis points followed by the optional list of unverified creators
+const borshRoyalties = ({ sellerFeeBasisPoints, creators }: Royalties) =>
+  Buffer.concat([
+    u16(sellerFeeBasisPoints),
+    creators.length === 0
+      ? Buffer.from([0])
+      : Buffer.concat([Buffer.from([1]), u32(creators.length), ...creators.map(({ address, share }) => Buffer.concat([address.toBuffer(), Buffer.from([0, share])]))]),
+  ]);
+
+// CreateMetadataAccountV3 without collection, uses or collection details
+const createMetadataIx = (mint: PublicKey, authority: PublicKey, name: string, symbol: string, uri: string, royalties: Royalties) =>
+  new TransactionInstruction({
+    programId: TOKEN_METADATA_PROGRAM_ID,
+    keys: [
+      { pubkey: getMetadataPda(mint), isSigner: false, isWritable: true },
+      { pubkey: mint, isSigner: false, isWritable: false },
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.concat([
+      Buffer.from([33]),
+      borshString(name),
+      borshString(symbol),
+      borshString(uri),
+      borshRoyalties(royalties),
+      Buffer.from([0, 0]),
+      Buffer.from([1, 0]),
+    ]),
+  });
+
+// CreateMasterEditionV3 with a max supply of 0
+const createMasterEditionIx = (mint: PublicKey, authority: PublicKey) =>
+  new TransactionInstruction({
+    programId: TOKEN_METADATA_PROGRAM_ID,
+    keys: [
+      { pubkey: getMasterEditionPda(mint), isSigner: false, isWritable: true },
+      { pubkey: mint, isSigner: false, isWritable: true },
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: getMetadataPda(mint), isSigner: false, isWritable: true },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.concat([Buffer.from([17, 1]), Buffer.alloc(8)]),
+  });
+
+// legacy token metadata nft with a master edition held by owner
+export const mintLegacyNft = async (provider: anchor.AnchorProvider, owner: PublicKey, royalties = noRoyalties) => {
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const mint = await createMint(provider.connection, payer, payer.publicKey, payer.publicKey, 0, undefined, confirmOptions, TOKEN_PROGRAM_ID);
+  const tokenAccount = await createAssociatedTokenAccount(provider.connection, payer, mint, owner, confirmOptions, TOKEN_PROGRAM_ID);
+  await mintTo(provider.connection, payer, mint, tokenAccount, payer, 1, [], confirmOptions, TOKEN_PROGRAM_ID);
+
+  const tx = new Transaction().add(
+    createMetadataIx(mint, payer.publicKey, 'Legacy', 'LGCY', 'https://example.com/legacy.json', royalties),
+    createMasterEditionIx(mint, payer.publicKey),
+  );
+  await provider.sendAndConfirm(tx, [], confirmOptions);
+  return mint;
+};
+
+// CreateV1 of a programmable nft without collection or rule set
+const createProgrammableIx = (mint: PublicKey, authority: PublicKey, royalties: Royalties) =>
+  new TransactionInstruction({
+    programId: TOKEN_METADATA_PROGRAM_ID,
+    keys: [
+      { pubkey: getMetadataPda(mint), isSigner: false, isWritable: true },
+      { pubkey: getMasterEditionPda(mint), isSigner: false, isWritable: true },
+      { pubkey: mint, isSigner: true, isWritable: true },
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.concat([
+      Buffer.from([42, 0]),
+      borshString('Programmable'),
+      borshString('PNFT'),
+      borshString('https://example.com/pnft.json'),
+      borshRoyalties(royalties),
+      // primary sale not happened, mutable, programmable non fungible
+      Buffer.from([0, 1, 4]),
+      // no collection, uses, collection details, rule set or decimals, a print supply of zero
+      Buffer.from([0, 0, 0, 0, 0, 1, 0]),
+    ]),
+  });
+
+// MintV1 of a single token to the owner's associated token account
+const mintProgrammableIx = (mint: PublicKey, owner: PublicKey, authority: PublicKey) => {
+  const tokenAccount = getAssociatedTokenAddressSync(mint, owner, true, TOKEN_PROGRAM_ID);
+  const amount = Buffer.alloc(8);
+  amount.writeBigUInt64LE(BigInt(1));
+  return new TransactionInstruction({
+    programId: TOKEN_METADATA_PROGRAM_ID,
+    keys: [
+      { pubkey: tokenAccount, isSigner: false, isWritable: true },
+      { pubkey: owner, isSigner: false, isWritable: false },
+      { pubkey: getMetadataPda(mint), isSigner: false, isWritable: false },
+      { pubkey: getMasterEditionPda(mint), isSigner: false, isWritable: false },
+      { pubkey: getTokenRecordPda(mint, tokenAccount), isSigner: false, isWritable: true },
+      { pubkey: mint, isSigner: false, isWritable: true },
+      { pubkey: authority, isSigner: true, isWritable: false },
+      { pubkey: TOKEN_METADATA_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: authority, isSigner: true, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_INSTRUCTIONS_PUBKEY, isSigner: false, isWritable: false },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: ASSOCIATED_TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: TOKEN_METADATA_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: TOKEN_METADATA_PROGRAM_ID, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.concat([Buffer.from([43, 0]), amount, Buffer.from([0])]),
+  });
+};
+
+// programmable nft without a rule set held by owner
+export const mintProgrammableNft = async (provider: anchor.AnchorProvider, owner: PublicKey, royalties = noRoyalties) => {
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const mint = Keypair.generate();
+  const tx = new Transaction().add(createProgrammableIx(mint.publicKey, payer.publicKey, royalties), mintProgrammableIx(mint.publicKey, owner, payer.publicKey));
+  await provider.sendAndConfirm(tx, [mint], confirmOptions);
+  return mint.publicKey;
+};
+
+export const getTokenState = async (provider: anchor.AnchorProvider, mint: PublicKey, tokenAccount: PublicKey) => {
+  const tokenRecord = await provider.connection.getAccountInfo(getTokenRecordPda(mint, tokenAccount), 'confirmed');
+  // key and bump come before the state
+  return tokenRecord?.data[2];
+};